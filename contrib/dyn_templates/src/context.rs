@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::borrow::Cow;
 use std::error::Error;
 
 use crate::{Engines, TemplateInfo};
@@ -17,31 +18,129 @@ pub(crate) struct Context {
     pub templates: HashMap<String, TemplateInfo>,
     /// Loaded template engines
     pub engines: Engines,
+    /// Whether the templates were embedded, and so can't be reloaded.
+    pub embedded: bool,
 }
 
 pub(crate) use self::manager::ContextManager;
 
+/// Where templates are discovered and read from.
+#[derive(Clone, Copy)]
+pub(crate) enum Source<'a> {
+    /// A template directory on disk.
+    Dir(&'a Path),
+    /// Templates embedded into the binary.
+    #[cfg(feature = "embed")]
+    Embedded(&'static crate::Embedded),
+}
+
 impl Context {
-    /// Load all of the templates at `root`, initialize them using the relevant
-    /// template engine, and store all of the initialized state in a `Context`
-    /// structure, which is returned if all goes well.
-    pub fn initialize(root: &Path, callback: &Callback) -> Option<Context> {
-        let root = match root.normalize() {
-            Ok(root) => root.into_path_buf(),
-            Err(e) => {
-                error!("Invalid template directory '{}': {}.", root.display(), e);
-                return None;
-            }
+    /// Load all of the templates in `source`, initialize them using the
+    /// relevant template engine, and store all of the initialized state in a
+    /// `Context` structure, which is returned if all goes well.
+    pub fn initialize(source: Source<'_>, callback: &Callback) -> Option<Context> {
+        let root = match source {
+            Source::Dir(root) => match root.normalize() {
+                Ok(root) => root.into_path_buf(),
+                Err(e) => {
+                    error!("Invalid template directory '{}': {}.", root.display(), e);
+                    return None;
+                }
+            },
+            #[cfg(feature = "embed")]
+            Source::Embedded(embedded) => PathBuf::from(embedded.path()),
         };
 
+        let (source, embedded) = match source {
+            Source::Dir(_) => (Source::Dir(&root), false),
+            #[cfg(feature = "embed")]
+            source@Source::Embedded(_) => (source, true),
+        };
+
+        // Templates for the built-in engines are loaded before customization
+        // so that the callback can build on them.
         let mut templates: HashMap<String, TemplateInfo> = HashMap::new();
-        for ext in Engines::ENABLED_EXTENSIONS {
-            let mut glob_path = root.join("**").join("*");
-            glob_path.set_extension(ext);
-            let glob_path = glob_path.to_str().expect("valid glob path string");
+        source.discover(Engines::BUILTIN_EXTENSIONS, &mut templates);
+
+        let mut engines = Engines::new();
+        let result = source.read(&templates, Engines::BUILTIN_EXTENSIONS)
+            .and_then(|sources| engines.load_builtin(&loadable(&sources)));
+
+        if let Err(e) = result {
+            error!("Failed to initialize templating engines.");
+            log_error_chain(&*e);
+            return None;
+        }
+
+        if let Err(e) = callback(&mut engines) {
+            error_!("Template customization callback failed.");
+            error_!("{}", e);
+            return None;
+        }
+
+        // Engines registered by the callback claim the remaining extensions.
+        let registered_exts = engines.extensions().into_iter()
+            .filter(|ext| !Engines::BUILTIN_EXTENSIONS.contains(ext))
+            .collect::<Vec<_>>();
+
+        source.discover(&registered_exts, &mut templates);
+        let result = source.read(&templates, &registered_exts)
+            .and_then(|sources| engines.load_registered(&loadable(&sources)));
+
+        if let Err(e) = result {
+            error!("Failed to initialize registered templating engines.");
+            log_error_chain(&*e);
+            return None;
+        }
+
+        for (name, engine_ext) in engines.templates() {
+            if !templates.contains_key(name) {
+                let data_type = Path::new(name).extension()
+                    .and_then(|osstr| osstr.to_str())
+                    .and_then(|ext| ContentType::from_extension(ext))
+                    .unwrap_or(ContentType::Text);
+
+                let info = TemplateInfo { path: None, engine_ext, data_type };
+                templates.insert(name.to_string(), info);
+            }
+        }
 
-            for path in glob::glob(glob_path).unwrap().filter_map(Result::ok) {
-                let (name, data_type_str) = split_path(&root, &path);
+        Some(Context { root, templates, engines, embedded })
+    }
+}
+
+impl Source<'_> {
+    /// Returns the paths of the templates with engine extension `ext` and the
+    /// root they are relative to.
+    fn paths(&self, ext: &str) -> (&Path, Vec<PathBuf>) {
+        match *self {
+            Source::Dir(root) => {
+                let mut glob_path = root.join("**").join("*");
+                glob_path.set_extension(ext);
+                let glob_path = glob_path.to_str().expect("valid glob path string");
+                let paths = glob::glob(glob_path).unwrap().filter_map(Result::ok).collect();
+                (root, paths)
+            }
+            #[cfg(feature = "embed")]
+            Source::Embedded(embedded) => {
+                let paths = embedded.files().into_iter()
+                    .map(|file| file.path())
+                    .filter(|path| path.extension().map_or(false, |e| e == ext))
+                    .map(|path| path.to_path_buf())
+                    .collect();
+
+                (Path::new(""), paths)
+            }
+        }
+    }
+
+    /// Discovers the templates with one of the engine extensions in `exts`,
+    /// adding them to `templates`.
+    fn discover(&self, exts: &[&'static str], templates: &mut HashMap<String, TemplateInfo>) {
+        for &ext in exts {
+            let (root, paths) = self.paths(ext);
+            for path in paths {
+                let (name, data_type_str) = split_path(root, &path);
                 if let Some(info) = templates.get(&*name) {
                     warn_!("Template name '{}' does not have a unique path.", name);
                     info_!("Existing path: {:?}", info.path);
@@ -61,27 +160,60 @@ impl Context {
                 });
             }
         }
+    }
 
-        let mut engines = Engines::init(&templates)?;
-        if let Err(e) = callback(&mut engines) {
-            error_!("Template customization callback failed.");
-            error_!("{}", e);
-            return None;
-        }
+    /// Reads the source of each discovered template with one of the engine
+    /// extensions in `exts`. Returns (name, path, source, engine extension).
+    fn read<'t>(
+        &self,
+        templates: &'t HashMap<String, TemplateInfo>,
+        exts: &[&str],
+    ) -> Result<Vec<(&'t str, &'t Path, Cow<'static, str>, &'t str)>, Box<dyn Error>> {
+        let mut sources = vec![];
+        for (name, info) in templates {
+            let path = match info.path.as_deref() {
+                Some(path) if exts.contains(&info.engine_ext) => path,
+                _ => continue,
+            };
 
-        for (name, engine_ext) in engines.templates() {
-            if !templates.contains_key(name) {
-                let data_type = Path::new(name).extension()
-                    .and_then(|osstr| osstr.to_str())
-                    .and_then(|ext| ContentType::from_extension(ext))
-                    .unwrap_or(ContentType::Text);
+            let source = match *self {
+                Source::Dir(_) => std::fs::read_to_string(path).map(Cow::Owned),
+                #[cfg(feature = "embed")]
+                Source::Embedded(embedded) => embedded.contents(path)
+                    .map(Cow::Borrowed)
+                    .ok_or_else(|| {
+                        let kind = std::io::ErrorKind::InvalidData;
+                        std::io::Error::new(kind, "template is not valid UTF-8")
+                    }),
+            };
 
-                let info = TemplateInfo { path: None, engine_ext, data_type };
-                templates.insert(name.to_string(), info);
+            match source {
+                Ok(source) => sources.push((name.as_str(), path, source, info.engine_ext)),
+                Err(e) => {
+                    error_!("Failed to read template '{}'.", name);
+                    info_!("Template path: '{}'.", path.display());
+                    return Err(e.into());
+                }
             }
         }
 
-        Some(Context { root, templates, engines })
+        Ok(sources)
+    }
+}
+
+/// Borrows the read `sources` for loading into engines.
+fn loadable<'a>(
+    sources: &'a [(&'a str, &'a Path, Cow<'static, str>, &'a str)]
+) -> Vec<(&'a str, &'a Path, &'a str, &'a str)> {
+    sources.iter().map(|(name, path, source, ext)| (*name, *path, &**source, *ext)).collect()
+}
+
+/// Logs `error` followed by each of its sources.
+pub(crate) fn log_error_chain(error: &dyn Error) {
+    let mut error = Some(error);
+    while let Some(err) = error {
+        error_!("{}", err);
+        error = err.source();
     }
 }
 
@@ -117,7 +249,7 @@ mod manager {
 
     use notify::{raw_watcher, RawEvent, RecommendedWatcher, RecursiveMode, Watcher};
 
-    use super::{Callback, Context};
+    use super::{Callback, Context, Source};
 
     /// Wraps a Context. With `cfg(debug_assertions)` active, this structure
     /// additionally provides a method to reload the context at runtime.
@@ -130,6 +262,10 @@ mod manager {
 
     impl ContextManager {
         pub fn new(ctxt: Context) -> ContextManager {
+            if ctxt.embedded {
+                return ContextManager { watcher: None, context: RwLock::new(ctxt) };
+            }
+
             let (tx, rx) = channel();
             let watcher = raw_watcher(tx).and_then(|mut watcher| {
                 watcher.watch(ctxt.root.canonicalize()?, RecursiveMode::Recursive)?;
@@ -172,7 +308,7 @@ mod manager {
             if let Some(true) = templates_changes {
                 info_!("Change detected: reloading templates.");
                 let root = self.context().root.clone();
-                if let Some(new_ctxt) = Context::initialize(&root, &callback) {
+                if let Some(new_ctxt) = Context::initialize(Source::Dir(&root), callback) {
                     *self.context_mut() = new_ctxt;
                 } else {
                     warn_!("An error occurred while reloading templates.");
@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use crate::include_dir::{Dir, File};
+
+/// Templates embedded into the binary at compile time via [`embed!`].
+///
+/// Pass a `&'static Embedded` to [`Template::embedded()`] or
+/// [`Template::try_embedded()`] to use the embedded templates in release
+/// builds. In debug builds, templates continue to be discovered from disk, and
+/// live reloaded, from the configured `template_dir`, or if it is not
+/// configured, from the directory the templates were embedded from. The
+/// `template_embedded` configuration parameter overrides the choice.
+///
+/// This type is only available when the `embed` feature is enabled.
+///
+/// [`Template::embedded()`]: crate::Template::embedded()
+/// [`Template::try_embedded()`]: crate::Template::try_embedded()
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_dyn_templates::{Template, Embedded, embed};
+///
+/// static TEMPLATES: Embedded = embed!("$CARGO_MANIFEST_DIR/tests/templates");
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().attach(Template::embedded(&TEMPLATES, |_| {}))
+/// }
+/// ```
+pub struct Embedded {
+    path: &'static str,
+    manifest_dir: &'static str,
+    dir: Dir<'static>,
+}
+
+impl Embedded {
+    #[doc(hidden)]
+    pub const fn new(path: &'static str, manifest_dir: &'static str, dir: Dir<'static>) -> Self {
+        Embedded { path, manifest_dir, dir }
+    }
+
+    /// The path, as passed to [`embed!`], of the directory the templates were
+    /// embedded from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_dyn_templates::{Embedded, embed};
+    ///
+    /// static TEMPLATES: Embedded = embed!("$CARGO_MANIFEST_DIR/tests/templates");
+    /// assert_eq!(TEMPLATES.path(), "$CARGO_MANIFEST_DIR/tests/templates");
+    /// ```
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// The directory on disk the templates were embedded from, with
+    /// `$CARGO_MANIFEST_DIR` in [`Embedded::path()`] expanded.
+    pub(crate) fn source_dir(&self) -> PathBuf {
+        self.path.replace("$CARGO_MANIFEST_DIR", self.manifest_dir).into()
+    }
+
+    /// Returns the contents of the embedded file at `path`, relative to the
+    /// embedded directory, if it exists and is valid UTF-8.
+    pub(crate) fn contents(&'static self, path: &Path) -> Option<&'static str> {
+        self.dir.get_file(path)?.contents_utf8()
+    }
+
+    /// Returns every embedded file, recursively. File paths are relative to
+    /// the embedded directory.
+    pub(crate) fn files(&'static self) -> Vec<&'static File<'static>> {
+        fn collect(dir: &'static Dir<'static>, files: &mut Vec<&'static File<'static>>) {
+            files.extend(dir.files());
+            dir.dirs().for_each(|dir| collect(dir, files));
+        }
+
+        let mut files = vec![];
+        collect(&self.dir, &mut files);
+        files
+    }
+}
+
+/// Embeds the templates in a directory into the binary at compile time.
+///
+/// The directory path must be a string literal. It may contain environment
+/// variables, such as `$CARGO_MANIFEST_DIR`, which are expanded at compile
+/// time. Relative paths are resolved against the compiler's working directory,
+/// so paths should begin with `$CARGO_MANIFEST_DIR`. The macro expands to an
+/// [`Embedded`] and can be used to initialize a `static`.
+///
+/// This macro is only available when the `embed` feature is enabled. See
+/// [`Embedded`] for an example.
+#[macro_export]
+macro_rules! embed {
+    ($path:tt) => {{
+        use $crate::include_dir;
+        $crate::Embedded::new(
+            $path,
+            ::std::env!("CARGO_MANIFEST_DIR"),
+            include_dir::include_dir!($path),
+        )
+    }};
+}
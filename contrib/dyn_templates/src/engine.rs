@@ -1,18 +1,135 @@
 use std::path::Path;
-use std::collections::HashMap;
+use std::error::Error;
 
-use rocket::serde::Serialize;
-
-use crate::TemplateInfo;
+use rocket::figment::value::Value;
 
 #[cfg(feature = "tera")] use crate::tera::Tera;
 #[cfg(feature = "handlebars")] use crate::handlebars::Handlebars;
+#[cfg(feature = "minijinja")] use crate::minijinja::Environment;
 
-pub(crate) trait Engine: Send + Sync + Sized + 'static {
-    const EXT: &'static str;
+/// A templating engine that renders templates with a given set of extensions.
+///
+/// The built-in engines, [Tera], [Handlebars], and [MiniJinja], implement this
+/// trait. Other engines can be added by implementing `Engine` and registering
+/// an instance via [`Engines::register()`] in the callback to
+/// [`Template::custom()`] or [`Template::try_custom()`]. A registered engine:
+///
+///   * Claims the template file extensions returned by
+///     [`Engine::extensions()`]. Files in `template_dir` with one of these
+///     extensions are discovered and passed to [`Engine::load()`].
+///   * Takes part in live reloading in debug mode: the customization callback
+///     is called anew on each reload, registering a fresh engine instance.
+///   * Renders templates with its extensions via [`Template::render()`].
+///
+/// The trait is object-safe: registered engines are stored as `Box<dyn
+/// Engine>`.
+///
+/// [Tera]: https://docs.rs/crate/tera/1
+/// [Handlebars]: https://docs.rs/crate/handlebars/3
+/// [MiniJinja]: https://docs.rs/crate/minijinja/2
+/// [`Template::custom()`]: crate::Template::custom()
+/// [`Template::try_custom()`]: crate::Template::try_custom()
+/// [`Template::render()`]: crate::Template::render()
+///
+/// # Example
+///
+/// A toy engine that replaces `$name` in templates with the string value of
+/// the context field `name`:
+///
+/// ```rust
+/// use std::path::Path;
+/// use std::error::Error;
+/// use std::collections::HashMap;
+///
+/// use rocket::figment::value::Value;
+/// use rocket_dyn_templates::{Template, Engine};
+///
+/// #[derive(Default)]
+/// struct Dollar(HashMap<String, String>);
+///
+/// impl Engine for Dollar {
+///     fn extensions(&self) -> &[&'static str] {
+///         &["dollar"]
+///     }
+///
+///     fn load(&mut self, templates: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+///         for (name, _, source) in templates {
+///             self.0.insert(name.to_string(), source.to_string());
+///         }
+///
+///         Ok(())
+///     }
+///
+///     fn templates(&self) -> Vec<&str> {
+///         self.0.keys().map(|name| name.as_str()).collect()
+///     }
+///
+///     fn render(&self, name: &str, context: &Value) -> Result<String, Box<dyn Error>> {
+///         let template = self.0.get(name).ok_or("missing template")?;
+///         let dict = context.as_dict().ok_or("context must be a map")?;
+///         Ok(dict.iter().fold(template.clone(), |string, (key, value)| {
+///             let value = value.as_str().map(String::from).unwrap_or_default();
+///             string.replace(&format!("${}", key), &value)
+///         }))
+///     }
+/// }
+///
+/// # let _ = {
+/// rocket::build().attach(Template::custom(|engines| {
+///     engines.register(Dollar::default());
+/// }))
+/// # };
+/// ```
+pub trait Engine: Send + Sync + 'static {
+    /// The file extensions, without a leading `.`, of the templates rendered
+    /// by this engine. For example, `&["tera"]`.
+    fn extensions(&self) -> &[&'static str];
+
+    /// Loads the templates discovered in `template_dir`, or embedded via
+    /// [`embed!`](crate::embed!), with one of this engine's extensions. Each
+    /// template is a triple of its name, as it would be passed to
+    /// [`Template::render()`](crate::Template::render()), its path, and its
+    /// source. The path is complete for templates in `template_dir` and
+    /// relative to the embedded directory for embedded templates; it need not
+    /// exist. An error aborts initialization or, when reloading, discards the
+    /// reloaded templates.
+    fn load(&mut self, templates: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>>;
+
+    /// Returns the names of all of the templates known to this engine,
+    /// including those not discovered in `template_dir`, such as those
+    /// registered directly from strings.
+    fn templates(&self) -> Vec<&str>;
+
+    /// Renders the template named `name` with the serialized `context`.
+    fn render(&self, name: &str, context: &Value) -> Result<String, Box<dyn Error>>;
+
+    /// Renders only the block named `block` of the template named `name` with
+    /// the serialized `context`, as requested via [`Template::render_block()`].
+    ///
+    /// The default implementation returns an error: block rendering is not
+    /// supported. Of the built-in engines, MiniJinja and Tera support it;
+    /// Handlebars has no blocks to render.
+    ///
+    /// [`Template::render_block()`]: crate::Template::render_block()
+    fn render_block(
+        &self,
+        name: &str,
+        block: &str,
+        context: &Value
+    ) -> Result<String, Box<dyn Error>> {
+        let _ = (name, context);
+        Err(format!("engine cannot render block '{}': unsupported", block).into())
+    }
 
-    fn init<'a>(templates: impl Iterator<Item = (&'a str, &'a Path)>) -> Option<Self>;
-    fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String>;
+    /// Returns the names of the blocks defined by the template named `name`,
+    /// including those inherited from parent templates, or `None` if the
+    /// template does not exist or the engine cannot determine its blocks.
+    ///
+    /// The default implementation returns `None`.
+    fn blocks(&self, name: &str) -> Option<Vec<String>> {
+        let _ = name;
+        None
+    }
 }
 
 /// A structure exposing access to templating engines.
@@ -49,6 +166,8 @@ pub(crate) trait Engine: Send + Sync + Sized + 'static {
 /// # }
 /// ```
 ///
+/// Additional engines are registered via [`Engines::register()`].
+///
 /// [`tera::Value`]: crate::tera::Value
 /// [`tera::Result`]: crate::tera::Result
 pub struct Engines {
@@ -64,79 +183,209 @@ pub struct Engines {
     /// `rocket_dyn_templates::handlebars` to avoid version mismatches.
     #[cfg(feature = "handlebars")]
     pub handlebars: Handlebars<'static>,
+    /// The MiniJinja templating engine. This field is only available when the
+    /// `minijinja` feature is enabled. When calling methods on the
+    /// `Environment` instance, ensure you use types imported from
+    /// `rocket_dyn_templates::minijinja` to avoid version mismatches.
+    #[cfg(feature = "minijinja")]
+    pub minijinja: Environment<'static>,
+    /// Engines registered via `Engines::register()`.
+    registered: Vec<Box<dyn Engine>>,
 }
 
 impl Engines {
-    pub(crate) const ENABLED_EXTENSIONS: &'static [&'static str] = &[
-        #[cfg(feature = "tera")] Tera::EXT,
-        #[cfg(feature = "handlebars")] Handlebars::EXT,
-    ];
-
-    pub(crate) fn init(templates: &HashMap<String, TemplateInfo>) -> Option<Engines> {
-        fn inner<E: Engine>(templates: &HashMap<String, TemplateInfo>) -> Option<E> {
-            let named_templates = templates.iter()
-                .filter(|&(_, i)| i.engine_ext == E::EXT)
-                .filter_map(|(k, i)| Some((k.as_str(), i.path.as_ref()?)))
-                .map(|(k, p)| (k, p.as_path()));
-
-            E::init(named_templates)
-        }
-
-        Some(Engines {
+    /// Creates the built-in engines, each with a `uri_for` helper.
+    pub(crate) fn new() -> Engines {
+        Engines {
             #[cfg(feature = "tera")]
-            tera: match inner::<Tera>(templates) {
-                Some(tera) => tera,
-                None => return None
-            },
+            tera: crate::tera_templates::new(),
             #[cfg(feature = "handlebars")]
-            handlebars: match inner::<Handlebars<'static>>(templates) {
-                Some(hb) => hb,
-                None => return None
-            },
-        })
+            handlebars: crate::handlebars_templates::new(),
+            #[cfg(feature = "minijinja")]
+            minijinja: crate::minijinja_templates::new(),
+            registered: vec![],
+        }
     }
 
-    pub(crate) fn render<C: Serialize>(
-        &self,
-        name: &str,
-        info: &TemplateInfo,
-        context: C
-    ) -> Option<String> {
-        #[cfg(feature = "tera")] {
-            if info.engine_ext == Tera::EXT {
-                return Engine::render(&self.tera, name, context);
+    /// Registers `engine`, which renders templates with its
+    /// [`extensions()`](Engine::extensions()).
+    ///
+    /// Templates in `template_dir` with one of the engine's extensions are
+    /// discovered and [loaded](Engine::load()) once the customization callback
+    /// returns. If an extension is already claimed by another engine, the
+    /// extension remains with the existing engine and a warning is emitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_dyn_templates::{Template, Engine};
+    /// # use std::{path::Path, error::Error};
+    /// # use rocket::figment::value::Value;
+    ///
+    /// struct MyEngine;
+    ///
+    /// impl Engine for MyEngine {
+    ///     // ...
+    ///     # fn extensions(&self) -> &[&'static str] { &["mine"] }
+    ///     # fn load(&mut self, _: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+    ///     #     Ok(())
+    ///     # }
+    ///     # fn templates(&self) -> Vec<&str> { vec![] }
+    ///     # fn render(&self, _: &str, _: &Value) -> Result<String, Box<dyn Error>> {
+    ///     #     Ok(String::new())
+    ///     # }
+    /// }
+    ///
+    /// # let _ = {
+    /// rocket::build().attach(Template::custom(|engines| {
+    ///     engines.register(MyEngine);
+    /// }))
+    /// # };
+    /// ```
+    pub fn register<E: Engine>(&mut self, engine: E) -> &mut Self {
+        let existing = self.extensions();
+        for ext in engine.extensions() {
+            if existing.contains(ext) {
+                warn!("Template extension '{}' is already claimed by an engine.", ext);
+                warn_!("Templates with extension '{}' will use the existing engine.", ext);
             }
         }
 
-        #[cfg(feature = "handlebars")] {
-            if info.engine_ext == Handlebars::EXT {
-                return Engine::render(&self.handlebars, name, context);
+        self.registered.push(Box::new(engine));
+        self
+    }
+
+    /// The extensions of the built-in engines.
+    pub(crate) const BUILTIN_EXTENSIONS: &'static [&'static str] = &[
+        #[cfg(feature = "tera")] crate::tera_templates::EXT,
+        #[cfg(feature = "handlebars")] crate::handlebars_templates::EXT,
+        #[cfg(feature = "minijinja")] crate::minijinja_templates::EXT,
+    ];
+
+    /// The built-in engines, which are loaded before customization, followed
+    /// by the registered engines, in order of precedence.
+    fn engines(&self) -> impl Iterator<Item = &dyn Engine> {
+        let builtin: Vec<&dyn Engine> = vec![
+            #[cfg(feature = "tera")] &self.tera,
+            #[cfg(feature = "handlebars")] &self.handlebars,
+            #[cfg(feature = "minijinja")] &self.minijinja,
+        ];
+
+        builtin.into_iter().chain(self.registered.iter().map(|e| &**e))
+    }
+
+    /// Returns the engine that renders templates with extension `ext`.
+    fn engine(&self, ext: &str) -> Option<&dyn Engine> {
+        self.engines().find(|e| e.extensions().contains(&ext))
+    }
+
+    /// Returns the extensions claimed by all engines, in order of precedence,
+    /// without duplicates.
+    pub(crate) fn extensions(&self) -> Vec<&'static str> {
+        let mut extensions = vec![];
+        for ext in self.engines().flat_map(|e| e.extensions().iter().copied()) {
+            if !extensions.contains(&ext) {
+                extensions.push(ext);
             }
         }
 
-        None
+        extensions
     }
 
-    /// Returns iterator over template (name, engine_extension).
-    pub(crate) fn templates(&self) -> impl Iterator<Item = (&str, &'static str)> {
-        #[cfg(all(feature = "tera", feature = "handlebars"))] {
-            self.tera.get_template_names()
-                .map(|name| (name, Tera::EXT))
-                .chain(self.handlebars.get_templates().keys()
-                    .map(|name| (name.as_str(), Handlebars::EXT)))
-        }
+    /// Loads the `templates`, each a tuple of (name, path, source, engine
+    /// extension), into the built-in engines.
+    pub(crate) fn load_builtin(
+        &mut self,
+        templates: &[(&str, &Path, &str, &str)]
+    ) -> Result<(), Box<dyn Error>> {
+        #[cfg(feature = "tera")]
+        load(&mut self.tera, templates, Self::BUILTIN_EXTENSIONS)?;
 
-        #[cfg(all(feature = "tera", not(feature = "handlebars")))] {
-            self.tera.get_template_names().map(|name| (name, Tera::EXT))
-        }
+        #[cfg(feature = "handlebars")]
+        load(&mut self.handlebars, templates, Self::BUILTIN_EXTENSIONS)?;
 
-        #[cfg(all(feature = "handlebars", not(feature = "tera")))] {
-            self.handlebars.get_templates().keys()
-                .map(|name| (name.as_str(), Handlebars::EXT))
-        }
+        #[cfg(feature = "minijinja")]
+        load(&mut self.minijinja, templates, Self::BUILTIN_EXTENSIONS)?;
+
+        Ok(())
+    }
+
+    /// Loads the `templates`, each a tuple of (name, path, source, engine
+    /// extension), into the registered engines. Each engine is passed only the
+    /// templates with extensions it claims that no engine before it claimed.
+    pub(crate) fn load_registered(
+        &mut self,
+        templates: &[(&str, &Path, &str, &str)]
+    ) -> Result<(), Box<dyn Error>> {
+        let mut claimed = Self::BUILTIN_EXTENSIONS.to_vec();
+        for engine in &mut self.registered {
+            let owned = engine.extensions().iter()
+                .filter(|ext| !claimed.contains(ext))
+                .copied()
+                .collect::<Vec<_>>();
 
-        #[cfg(not(any(feature = "tera", feature = "handlebars")))] {
-            None.into_iter()
+            load(&mut **engine, templates, &owned)?;
+            claimed.extend(owned);
         }
+
+        Ok(())
+    }
+
+    /// Renders the template `name` with the engine for `engine_ext`.
+    pub(crate) fn render(
+        &self,
+        name: &str,
+        engine_ext: &str,
+        context: &Value
+    ) -> Result<String, Box<dyn Error>> {
+        let engine = self.engine(engine_ext)
+            .ok_or_else(|| format!("no engine for extension '{}'", engine_ext))?;
+
+        engine.render(name, context)
+    }
+
+    /// Renders the block `block` of template `name` with the engine for
+    /// `engine_ext`.
+    pub(crate) fn render_block(
+        &self,
+        name: &str,
+        block: &str,
+        engine_ext: &str,
+        context: &Value
+    ) -> Result<String, Box<dyn Error>> {
+        let engine = self.engine(engine_ext)
+            .ok_or_else(|| format!("no engine for extension '{}'", engine_ext))?;
+
+        engine.render_block(name, block, context)
     }
+
+    /// Returns the blocks of template `name` as reported by the engine for
+    /// `engine_ext`.
+    pub(crate) fn blocks(&self, name: &str, engine_ext: &str) -> Option<Vec<String>> {
+        self.engine(engine_ext)?.blocks(name)
+    }
+
+    /// Returns the (name, engine extension) of every template known to any
+    /// engine, in order of engine precedence.
+    pub(crate) fn templates(&self) -> Vec<(&str, &'static str)> {
+        self.engines()
+            .filter_map(|engine| Some((engine, *engine.extensions().first()?)))
+            .flat_map(|(engine, ext)| engine.templates().into_iter().map(move |n| (n, ext)))
+            .collect()
+    }
+}
+
+/// Loads the templates in `templates` with an extension in `exts` and claimed
+/// by `engine` into `engine`.
+fn load(
+    engine: &mut dyn Engine,
+    templates: &[(&str, &Path, &str, &str)],
+    exts: &[&str],
+) -> Result<(), Box<dyn Error>> {
+    let named = templates.iter()
+        .filter(|(_, _, _, ext)| exts.contains(ext) && engine.extensions().contains(ext))
+        .map(|&(name, path, source, _)| (name, path, source))
+        .collect::<Vec<_>>();
+
+    engine.load(&named)
 }
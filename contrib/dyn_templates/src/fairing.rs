@@ -1,18 +1,106 @@
-use crate::{DEFAULT_TEMPLATE_DIR, Context, Engines};
-use crate::context::{Callback, ContextManager};
+use crate::{DEFAULT_TEMPLATE_DIR, Context};
+use crate::context::{Callback, ContextManager, Source};
+use crate::provider::Providers;
 
-use rocket::{Rocket, Build, Orbit};
+use rocket::{Rocket, Build, Orbit, Request};
 use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::figment::value::Value;
+use rocket::serde::Serialize;
 
-/// The TemplateFairing initializes the template system on attach, running
-/// custom_callback after templates have been loaded. In debug mode, the fairing
-/// checks for modifications to templates before every request and reloads them
-/// if necessary.
+/// The fairing that initializes and maintains templating state.
+///
+/// The fairing is created via [`Template::fairing()`], [`Template::custom()`],
+/// or [`Template::try_custom()`]. It initializes the template system on
+/// ignite, running the customization callback after templates have been
+/// loaded. In debug mode, the fairing checks for modifications to templates
+/// before every request and reloads them if necessary.
+///
+/// The fairing additionally accepts _context providers_ whose values are
+/// merged into the context of every rendered template: global values, via
+/// [`TemplateFairing::context()`], and values derived from the request being
+/// handled, via [`TemplateFairing::request_context()`]. Values in a
+/// template's own context take precedence over provided values, and
+/// request-derived values take precedence over global ones. Provided values
+/// are only merged into contexts that serialize to a map or to nothing, such
+/// as `()`.
+///
+/// `TemplateFairing` is opaque: it can only be created by the constructors
+/// above, its fields are private, and its only public API is the builder
+/// methods below and its implementation of [`Fairing`].
+///
+/// [`Template::fairing()`]: crate::Template::fairing()
+/// [`Template::custom()`]: crate::Template::custom()
+/// [`Template::try_custom()`]: crate::Template::try_custom()
 pub struct TemplateFairing {
     /// The user-provided customization callback, allowing the use of
     /// functionality specific to individual template engines. In debug mode,
     /// this callback might be run multiple times as templates are reloaded.
-    pub callback: Callback,
+    pub(crate) callback: Callback,
+    /// Templates embedded into the binary, used in place of `template_dir`
+    /// when `template_embedded` is `true`, as it is by default in release mode.
+    #[cfg(feature = "embed")]
+    pub(crate) embedded: Option<&'static crate::Embedded>,
+    /// The registered context providers.
+    pub(crate) providers: Providers,
+}
+
+impl TemplateFairing {
+    /// Merges `context`, which must serialize to a map, into the context of
+    /// every rendered template. If `context` fails to serialize or does not
+    /// serialize to a map, ignition fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_dyn_templates::{Template, context};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         // ...
+    ///         .attach(Template::fairing().context(context! {
+    ///             version: env!("CARGO_PKG_VERSION"),
+    ///         }))
+    /// }
+    /// ```
+    pub fn context<C: Serialize>(mut self, context: C) -> Self {
+        self.providers.add_global(Value::serialize(context));
+        self
+    }
+
+    /// Merges the value returned by `f`, which should serialize to a map or to
+    /// nothing, such as `None`, into the context of templates rendered in
+    /// response to a request. `f` is called with the request each time a
+    /// template is rendered as a responder. It is not called by
+    /// [`Template::show()`], which has no request. The value may not borrow
+    /// from the request. If it fails to serialize, rendering fails.
+    ///
+    /// [`Template::show()`]: crate::Template::show()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    /// use rocket_dyn_templates::{Template, context};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         // ...
+    ///         .attach(Template::fairing().request_context(|req: &Request<'_>| {
+    ///             let user = req.cookies().get("user");
+    ///             context! { user: user.map(|c| c.value().to_string()) }
+    ///         }))
+    /// }
+    /// ```
+    pub fn request_context<F, C>(mut self, f: F) -> Self
+        where F: Fn(&Request<'_>) -> C + Send + Sync + 'static, C: Serialize
+    {
+        self.providers.add_request(move |req| Value::serialize(f(req)));
+        self
+    }
 }
 
 #[rocket::async_trait]
@@ -26,31 +114,45 @@ impl Fairing for TemplateFairing {
 
     /// Initializes the template context. Templates will be searched for in the
     /// `template_dir` config variable or the default ([DEFAULT_TEMPLATE_DIR]).
-    /// The user's callback, if any was supplied, is called to customize the
+    /// If templates were embedded, they are used instead when the
+    /// `template_embedded` config variable is `true`, as it is by default in
+    /// release mode, and their source directory is the default otherwise. The
+    /// user's callback, if any was supplied, is called to customize the
     /// template engines. In debug mode, the `ContextManager::new` method
     /// initializes a directory watcher for auto-reloading of templates.
     async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
         use rocket::figment::value::magic::RelativePathBuf;
 
+        #[cfg(feature = "embed")]
+        if let Some(embedded) = self.embedded {
+            let use_embedded = match rocket.figment().extract_inner::<bool>("template_embedded") {
+                Ok(use_embedded) => use_embedded,
+                Err(e) if e.missing() => !cfg!(debug_assertions),
+                Err(e) => {
+                    rocket::config::pretty_print_error(e);
+                    return Err(rocket);
+                }
+            };
+
+            if use_embedded {
+                return self.initialize(rocket, Source::Embedded(embedded));
+            }
+        }
+
         let configured_dir = rocket.figment()
             .extract_inner::<RelativePathBuf>("template_dir")
             .map(|path| path.relative());
 
         let path = match configured_dir {
             Ok(dir) => dir,
-            Err(e) if e.missing() => DEFAULT_TEMPLATE_DIR.into(),
+            Err(e) if e.missing() => self.default_dir(),
             Err(e) => {
                 rocket::config::pretty_print_error(e);
                 return Err(rocket);
             }
         };
 
-        if let Some(ctxt) = Context::initialize(&path, &self.callback) {
-            Ok(rocket.manage(ContextManager::new(ctxt)))
-        } else {
-            error_!("Template initialization failed. Aborting launch.");
-            Err(rocket)
-        }
+        self.initialize(rocket, Source::Dir(&path))
     }
 
     async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
@@ -60,8 +162,14 @@ impl Fairing for TemplateFairing {
             .expect("Template ContextManager registered in on_ignite");
 
         info!("{}{}:", Paint::emoji("📐 "), Paint::magenta("Templating"));
-        info_!("directory: {}", Paint::white(Source::from(&*cm.context().root)));
-        info_!("engines: {:?}", Paint::white(Engines::ENABLED_EXTENSIONS));
+        let context = cm.context();
+        if context.embedded {
+            info_!("embedded: {}", Paint::white(context.root.display()));
+        } else {
+            info_!("directory: {}", Paint::white(Source::from(&*context.root)));
+        }
+
+        info_!("engines: {:?}", Paint::white(context.engines.extensions()));
     }
 
     #[cfg(debug_assertions)]
@@ -73,3 +181,32 @@ impl Fairing for TemplateFairing {
     }
 
 }
+
+impl TemplateFairing {
+    /// The template directory to use when `template_dir` is not configured.
+    fn default_dir(&self) -> std::path::PathBuf {
+        #[cfg(feature = "embed")]
+        if let Some(embedded) = self.embedded {
+            return embedded.source_dir();
+        }
+
+        DEFAULT_TEMPLATE_DIR.into()
+    }
+
+    fn initialize(&self, rocket: Rocket<Build>, source: Source<'_>) -> fairing::Result {
+        let providers = match self.providers.validate() {
+            Some(providers) => providers,
+            None => {
+                error_!("Template initialization failed. Aborting launch.");
+                return Err(rocket);
+            }
+        };
+
+        if let Some(ctxt) = Context::initialize(source, &self.callback) {
+            Ok(rocket.manage(ContextManager::new(ctxt)).manage(providers))
+        } else {
+            error_!("Template initialization failed. Aborting launch.");
+            Err(rocket)
+        }
+    }
+}
@@ -1,36 +1,89 @@
 use std::path::Path;
+use std::error::Error;
 
-use rocket::serde::Serialize;
+use rocket::figment::value::Value;
 
 use crate::engine::Engine;
+use crate::handlebars::{Context, Helper, HelperDef, JsonValue, RenderContext};
+use crate::handlebars::{RenderError, ScopedJson};
 pub use crate::handlebars::Handlebars;
 
+pub(crate) const EXT: &str = "hbs";
+
+/// Creates the Handlebars instance with the `uri_for` helper registered.
+pub(crate) fn new() -> Handlebars<'static> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_helper("uri_for", Box::new(UriFor));
+    handlebars
+}
+
+/// The `uri_for name [base] ..params` helper, with `base` defaulting to `/`
+/// and route parameters passed as hash arguments.
+struct UriFor;
+
+impl HelperDef for UriFor {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        h: &Helper<'reg, 'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc Context,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<Option<ScopedJson<'reg, 'rc>>, RenderError> {
+        fn string(key: &str, value: &JsonValue) -> Result<String, RenderError> {
+            match value {
+                JsonValue::String(s) => Ok(s.clone()),
+                JsonValue::Number(n) => Ok(n.to_string()),
+                JsonValue::Bool(b) => Ok(b.to_string()),
+                _ => Err(RenderError::new(format!("uri_for: invalid value for `{}`", key))),
+            }
+        }
+
+        let name = h.param(0)
+            .ok_or_else(|| RenderError::new("uri_for: missing route name"))
+            .and_then(|name| string("name", name.value()))?;
+
+        let base = match h.param(1) {
+            Some(base) => string("base", base.value())?,
+            None => "/".to_string(),
+        };
+
+        let params = h.hash().iter()
+            .map(|(key, value)| Ok((key.to_string(), string(key, value.value())?)))
+            .collect::<Result<Vec<_>, RenderError>>()?;
+
+        let uri = crate::routes::uri_for(&name, &base, params)
+            .map_err(|e| RenderError::new(format!("uri_for: {}", e)))?;
+
+        Ok(Some(ScopedJson::Derived(JsonValue::String(uri))))
+    }
+}
+
 impl Engine for Handlebars<'static> {
-    const EXT: &'static str = "hbs";
-
-    fn init<'a>(templates: impl Iterator<Item = (&'a str, &'a Path)>) -> Option<Self> {
-        let mut hb = Handlebars::new();
-        let mut ok = true;
-        for (name, path) in templates {
-            if let Err(e) = hb.register_template_file(name, path) {
-                error!("Handlebars template '{}' failed to register.", name);
-                error_!("{}", e);
+    fn extensions(&self) -> &[&'static str] {
+        &[EXT]
+    }
+
+    fn load(&mut self, templates: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+        for &(name, path, source) in templates {
+            if let Err(e) = self.register_template_string(name, source) {
+                error_!("Handlebars template '{}' failed to register.", name);
                 info_!("Template path: '{}'.", path.to_string_lossy());
-                ok = false;
+                return Err(e.into());
             }
         }
 
-        ok.then(|| hb)
+        Ok(())
+    }
+
+    fn templates(&self) -> Vec<&str> {
+        self.get_templates().keys().map(|name| name.as_str()).collect()
     }
 
-    fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String> {
+    fn render(&self, name: &str, context: &Value) -> Result<String, Box<dyn Error>> {
         if self.get_template(name).is_none() {
-            error_!("Handlebars template '{}' does not exist.", name);
-            return None;
+            return Err(format!("Handlebars template '{}' does not exist", name).into());
         }
 
-        Handlebars::render(self, name, &context)
-            .map_err(|e| error_!("Handlebars: {}", e))
-            .ok()
+        Ok(Handlebars::render(self, name, context)?)
     }
 }
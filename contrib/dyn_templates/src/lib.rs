@@ -3,7 +3,7 @@
 //! This crate adds support for dynamic template rendering to Rocket. It
 //! automatically discovers templates, provides a `Responder` to render
 //! templates, and automatically reloads templates when compiled in debug mode.
-//! At present, it supports [Handlebars] and [Tera].
+//! At present, it supports [Handlebars], [Tera], and [MiniJinja].
 //!
 //! # Usage
 //!
@@ -13,12 +13,12 @@
 //!      ```toml
 //!      [dependencies.rocket_dyn_templates]
 //!      version = "0.1.0-rc.1"
-//!      features = ["handlebars", "tera"]
+//!      features = ["handlebars", "tera", "minijinja"]
 //!      ```
 //!
-//!   1. Write your template files in Handlebars (`.hbs`) and/or Tera (`.tera`)
-//!      in the configurable `template_dir` directory (default:
-//!      `{rocket_root}/templates`).
+//!   1. Write your template files in Handlebars (`.hbs`), Tera (`.tera`),
+//!      and/or MiniJinja (`.j2`) in the configurable `template_dir` directory
+//!      (default: `{rocket_root}/templates`).
 //!
 //!   2. Attach `Template::fairing()` return a `Template` using
 //!      `Template::render()`, supplying the name of the template file **minus
@@ -78,9 +78,18 @@
 //! |--------------|---------|-----------|
 //! | [Tera]       | 1       | `.tera`   |
 //! | [Handlebars] | 3       | `.hbs`    |
+//! | [MiniJinja]  | 2       | `.j2`     |
 //!
 //! [Tera]: https://docs.rs/crate/tera/1
 //! [Handlebars]: https://docs.rs/crate/handlebars/3
+//! [MiniJinja]: https://docs.rs/crate/minijinja/2
+//!
+//! **Note:** The `minijinja` feature requires Rust 1.70 or newer, the minimum
+//! supported by MiniJinja 2, while the rest of this crate builds with the same
+//! Rust version as Rocket.
+//!
+//! Additional engines, claiming additional extensions, can be registered via
+//! [`Engines::register()`]. See [`Engine`] for details.
 //!
 //! Any file that ends with one of these extension will be discovered and
 //! rendered with the corresponding templating engine. The _name_ of the
@@ -110,10 +119,40 @@
 //! templating engines such as registering template helpers and register
 //! templates from strings.
 //!
+//! The fairing also accepts context providers, whose values are merged into
+//! the context of every rendered template. See [`TemplateFairing`] for
+//! details.
+//!
 //! In order for _any_ templates to be rendered, the template fairing _must_ be
 //! [attached](rocket::Rocket::attach()) to the running Rocket instance. Failure
 //! to do so will result in an ignite-time error.
 //!
+//! ## Embedding
+//!
+//! With the `embed` feature enabled, templates can be embedded into the binary
+//! at compile time via [`embed!`] and used with [`Template::embedded()`] or
+//! [`Template::try_embedded()`]. Release builds then render the embedded
+//! templates, so the template directory need not be deployed alongside the
+//! binary. Debug builds continue to discover templates on disk and reload
+//! them as they change. The `template_embedded` configuration parameter
+//! overrides this choice: when `true`, the embedded templates are rendered,
+//! without reloading, even in debug builds; when `false`, templates are
+//! discovered on disk even in release builds.
+//!
+//! ```rust
+//! # #[cfg(feature = "embed")] mod example {
+//! # use rocket::launch;
+//! use rocket_dyn_templates::{Template, Embedded, embed};
+//!
+//! static TEMPLATES: Embedded = embed!("$CARGO_MANIFEST_DIR/tests/templates");
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().attach(Template::embedded(&TEMPLATES, |_| {}))
+//! }
+//! # }
+//! ```
+//!
 //! ## Rendering
 //!
 //! Templates are rendered with the `render` method. The method takes in the
@@ -122,6 +161,47 @@
 //! `Object` value. The [`context!`] macro can also be used to create inline
 //! `Serialize`-able context objects.
 //!
+//! ## Fragments
+//!
+//! A single block of a MiniJinja or Tera template can be rendered on its own
+//! with [`Template::render_block()`], as is useful for partial page updates.
+//! The [`Partial`] request guard identifies requests for such updates, and
+//! [`Metadata::blocks()`] reports the blocks a template defines. Handlebars
+//! templates have no blocks, so rendering a block of one fails.
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket_dyn_templates::{Template, Partial, context};
+//!
+//! #[get("/")]
+//! fn index(partial: Option<Partial<'_>>) -> Template {
+//!     let context = context! { rows: ["a", "b", "c"] };
+//!     match partial {
+//!         Some(_) => Template::render_block("index", "rows", context),
+//!         None => Template::render("index", context),
+//!     }
+//! }
+//! ```
+//!
+//! ## Links to Routes
+//!
+//! The built-in engines provide a `uri_for` helper that builds the URI to a
+//! route by its name, mount point, which defaults to `/`, and parameters, as
+//! [`Rocket::uri_for()`] does:
+//!
+//! | Engine     | Usage                                                     |
+//! |------------|-----------------------------------------------------------|
+//! | Tera       | `{{ uri_for(route="post", base="/blog", id=id) }}`        |
+//! | Handlebars | `{{uri_for "post" "/blog" id=id}}`                        |
+//! | MiniJinja  | `{{ uri_for("post", "/blog", id=id) }}`                   |
+//!
+//! Parameter values must be strings, numbers, or booleans. Tera only supports
+//! named arguments, so Tera templates can't pass route parameters named
+//! `route` or `base`. URIs are built with the instance of Rocket the template
+//! is rendered for. When the parameters don't fit the route, rendering fails.
+//!
+//! [`Rocket::uri_for()`]: rocket::Rocket::uri_for()
+//!
 //! ## Automatic Reloading
 //!
 //! In debug mode (without the `--release` flag passed to `cargo`), templates
@@ -135,8 +215,9 @@
 
 #[macro_use] extern crate rocket;
 
-#[cfg(not(any(feature = "tera", feature = "handlebars")))]
-compile_error!("at least one of \"tera\" or \"handlebars\" features must be enabled");
+#[cfg(not(any(feature = "tera", feature = "handlebars", feature = "minijinja")))]
+compile_error!("at least one of \"tera\", \"handlebars\", or \"minijinja\" features \
+    must be enabled");
 
 /// The tera templating engine library, reexported.
 #[doc(inline)]
@@ -154,16 +235,39 @@ pub use handlebars_ as handlebars;
 #[cfg(feature = "handlebars")]
 mod handlebars_templates;
 
+/// The minijinja templating engine library, reexported.
+#[doc(inline)]
+#[cfg(feature = "minijinja")]
+pub use minijinja_ as minijinja;
+
+#[cfg(feature = "minijinja")]
+mod minijinja_templates;
+
+#[doc(hidden)]
+#[cfg(feature = "embed")]
+pub use include_dir;
+
+#[cfg(feature = "embed")]
+mod embed;
+
 mod engine;
 mod fairing;
 mod context;
 mod metadata;
+mod partial;
+mod provider;
+mod routes;
 
-pub use self::engine::Engines;
+pub use self::engine::{Engines, Engine};
+pub use self::fairing::TemplateFairing;
 pub use self::metadata::Metadata;
+pub use self::partial::Partial;
+
+#[cfg(feature = "embed")]
+pub use self::embed::Embedded;
 
-use self::fairing::TemplateFairing;
 use self::context::{Context, ContextManager};
+use self::provider::ContextProviders;
 
 use std::borrow::Cow;
 use std::path::PathBuf;
@@ -173,7 +277,6 @@ pub use rocket::serde;
 
 use rocket::{Rocket, Orbit, Ignite, Sentinel};
 use rocket::request::Request;
-use rocket::fairing::Fairing;
 use rocket::response::{self, Responder};
 use rocket::http::{ContentType, Status};
 use rocket::figment::{value::Value, error::Error};
@@ -191,6 +294,7 @@ const DEFAULT_TEMPLATE_DIR: &str = "templates";
 #[derive(Debug)]
 pub struct Template {
     name: Cow<'static, str>,
+    block: Option<Cow<'static, str>>,
     value: Result<Value, Error>
 }
 
@@ -235,7 +339,7 @@ impl Template {
     ///     # ;
     /// }
     /// ```
-    pub fn fairing() -> impl Fairing {
+    pub fn fairing() -> TemplateFairing {
         Template::custom(|_| {})
     }
 
@@ -244,6 +348,7 @@ impl Template {
     /// Unlike [`Template::fairing()`], this method allows you to configure
     /// templating engines via the function `f`. Note that only the enabled
     /// templating engines will be accessible from the `Engines` type.
+    /// Additional engines can be registered via [`Engines::register()`].
     ///
     /// This method does not allow the function `f` to fail. If `f` is fallible,
     /// use [`Template::try_custom()`] instead.
@@ -266,7 +371,7 @@ impl Template {
     ///     # ;
     /// }
     /// ```
-    pub fn custom<F: Send + Sync + 'static>(f: F) -> impl Fairing
+    pub fn custom<F: Send + Sync + 'static>(f: F) -> TemplateFairing
         where F: Fn(&mut Engines)
     {
         Self::try_custom(move |engines| { f(engines); Ok(()) })
@@ -298,10 +403,79 @@ impl Template {
     ///     # ;
     /// }
     /// ```
-    pub fn try_custom<F: Send + Sync + 'static>(f: F) -> impl Fairing
+    pub fn try_custom<F: Send + Sync + 'static>(f: F) -> TemplateFairing
+        where F: Fn(&mut Engines) -> Result<(), Box<dyn std::error::Error>>
+    {
+        TemplateFairing {
+            callback: Box::new(f),
+            #[cfg(feature = "embed")]
+            embedded: None,
+            providers: Default::default(),
+        }
+    }
+
+    /// Returns a fairing that initializes and maintains templating state using
+    /// the `templates` embedded into the binary via [`embed!`] in release
+    /// builds. The templating engines are configured via the function `f`, as
+    /// with [`Template::custom()`].
+    ///
+    /// In debug builds, templates are instead discovered and live reloaded
+    /// from `template_dir` or, if it is not configured, from the directory the
+    /// templates were embedded from. As a result, the same calls to
+    /// [`Template::render()`] work in both modes. Setting the
+    /// `template_embedded` configuration parameter to `true` or `false` uses
+    /// or ignores the embedded templates regardless of the build mode.
+    ///
+    /// This method is only available when the `embed` feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_dyn_templates::{Template, Embedded, embed};
+    ///
+    /// static TEMPLATES: Embedded = embed!("$CARGO_MANIFEST_DIR/tests/templates");
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         // ...
+    ///         .attach(Template::embedded(&TEMPLATES, |engines| {
+    ///             // engines.handlebars.register_helper ...
+    ///         }))
+    ///         // ...
+    /// }
+    /// ```
+    #[cfg(feature = "embed")]
+    pub fn embedded<F: Send + Sync + 'static>(
+        templates: &'static Embedded,
+        f: F
+    ) -> TemplateFairing
+        where F: Fn(&mut Engines)
+    {
+        Self::try_embedded(templates, move |engines| { f(engines); Ok(()) })
+    }
+
+    /// Returns a fairing that initializes and maintains templating state using
+    /// the `templates` embedded into the binary via [`embed!`] in release
+    /// builds.
+    ///
+    /// This variant of [`Template::embedded()`] allows a fallible `f`. See
+    /// [`Template::try_custom()`] for details.
+    ///
+    /// This method is only available when the `embed` feature is enabled.
+    #[cfg(feature = "embed")]
+    pub fn try_embedded<F: Send + Sync + 'static>(
+        templates: &'static Embedded,
+        f: F
+    ) -> TemplateFairing
         where F: Fn(&mut Engines) -> Result<(), Box<dyn std::error::Error>>
     {
-        TemplateFairing { callback: Box::new(f) }
+        TemplateFairing {
+            callback: Box::new(f),
+            embedded: Some(templates),
+            providers: Default::default(),
+        }
     }
 
     /// Render the template named `name` with the context `context`. The
@@ -337,7 +511,38 @@ impl Template {
     pub fn render<S, C>(name: S, context: C) -> Template
         where S: Into<Cow<'static, str>>, C: Serialize
     {
-        Template { name: name.into(), value: Value::serialize(context) }
+        Template { name: name.into(), block: None, value: Value::serialize(context) }
+    }
+
+    /// Render only the block named `block` of the template named `name` with
+    /// the context `context`. The block is rendered as it would be when
+    /// rendering the entire template, including any overrides from child
+    /// templates, but without the surrounding content. The response's
+    /// `Content-Type` is that of the template.
+    ///
+    /// Of the built-in engines, MiniJinja and Tera support block rendering. If
+    /// the template's engine does not support it, or the template has no such
+    /// block, rendering fails as it would for a missing template.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_dyn_templates::{Template, context};
+    ///
+    /// let template = Template::render_block("page", "row", context! {
+    ///     id: 10,
+    ///     name: "Rocket",
+    /// });
+    /// ```
+    #[inline]
+    pub fn render_block<S, B, C>(name: S, block: B, context: C) -> Template
+        where S: Into<Cow<'static, str>>, B: Into<Cow<'static, str>>, C: Serialize
+    {
+        Template {
+            name: name.into(),
+            block: Some(block.into()),
+            value: Value::serialize(context)
+        }
     }
 
     /// Render the template named `name` with the context `context` into a
@@ -384,14 +589,23 @@ impl Template {
             None
         })?;
 
-        Template::render(name, context).finalize(&ctxt).ok().map(|v| v.0)
+        let providers = rocket.state::<ContextProviders>();
+        Template::render(name, context).finalize(rocket, &ctxt, providers, None).ok().map(|v| v.0)
     }
 
-    /// Actually render this template given a template context. This method is
-    /// called by the `Template` `Responder` implementation as well as
+    /// Actually render this template given a template context, merging in the
+    /// context from `providers` and, if there is a request, `req`. The
+    /// `uri_for` helpers link to the routes of `rocket`. This method is called
+    /// by the `Template` `Responder` implementation as well as
     /// `Template::show()`.
     #[inline(always)]
-    fn finalize(self, ctxt: &Context) -> Result<(String, ContentType), Status> {
+    fn finalize(
+        self,
+        rocket: &Rocket<Orbit>,
+        ctxt: &Context,
+        providers: Option<&ContextProviders>,
+        req: Option<&Request<'_>>,
+    ) -> Result<(String, ContentType), Status> {
         let name = &*self.name;
         let info = ctxt.templates.get(name).ok_or_else(|| {
             let ts: Vec<_> = ctxt.templates.keys().map(|s| s.as_str()).collect();
@@ -406,9 +620,27 @@ impl Template {
             Status::InternalServerError
         })?;
 
-        let string = ctxt.engines.render(name, &info, value).ok_or_else(|| {
-            error_!("Template '{}' failed to render.", name);
-            Status::InternalServerError
+        let value = match providers {
+            Some(providers) => providers.merge(req, value).map_err(|e| {
+                error_!("Provided template context failed to serialize: {}.", e);
+                Status::InternalServerError
+            })?,
+            None => value,
+        };
+
+        let block = self.block.as_deref();
+        let string = routes::with_rocket(rocket, || match block {
+            Some(block) => ctxt.engines.render_block(name, block, info.engine_ext, &value)
+                .map_err(|e| {
+                    error_!("Block '{}' of template '{}' failed to render.", block, name);
+                    context::log_error_chain(&*e);
+                    Status::InternalServerError
+                }),
+            None => ctxt.engines.render(name, info.engine_ext, &value).map_err(|e| {
+                error_!("Template '{}' failed to render.", name);
+                context::log_error_chain(&*e);
+                Status::InternalServerError
+            }),
         })?;
 
         Ok((string, info.data_type.clone()))
@@ -428,7 +660,8 @@ impl<'r> Responder<'r, 'static> for Template {
                 Status::InternalServerError
             })?.context();
 
-            self.finalize(&ctxt)?
+            let providers = req.rocket().state::<ContextProviders>();
+            self.finalize(req.rocket(), &ctxt, providers, Some(req))?
         };
 
         (content_type, render).respond_to(req)
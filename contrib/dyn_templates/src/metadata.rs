@@ -58,6 +58,39 @@ impl Metadata<'_> {
         self.0.context().templates.contains_key(name)
     }
 
+    /// Returns the names, in sorted order, of the blocks defined by the
+    /// template with the given `name`, including those inherited from its
+    /// parent templates. Returns `None` if the template is not loaded or its
+    /// engine cannot report blocks. Of the built-in engines, MiniJinja and
+    /// Tera report blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # extern crate rocket_dyn_templates;
+    /// #
+    /// use rocket_dyn_templates::{Template, Metadata, context};
+    ///
+    /// #[get("/")]
+    /// fn handler(metadata: Metadata) -> Template {
+    ///     // Render just the `rows` block, if `page` defines it.
+    ///     let blocks = metadata.blocks("page").unwrap_or_default();
+    ///     if blocks.iter().any(|block| block == "rows") {
+    ///         Template::render_block("page", "rows", context! {})
+    ///     } else {
+    ///         Template::render("page", context! {})
+    ///     }
+    /// }
+    /// ```
+    pub fn blocks(&self, name: &str) -> Option<Vec<String>> {
+        let ctxt = self.0.context();
+        let info = ctxt.templates.get(name)?;
+        let mut blocks = ctxt.engines.blocks(name, info.engine_ext)?;
+        blocks.sort();
+        Some(blocks)
+    }
+
     /// Returns `true` if template reloading is enabled.
     ///
     /// # Example
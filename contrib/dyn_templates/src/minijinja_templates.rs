@@ -0,0 +1,137 @@
+use std::io;
+use std::path::Path;
+use std::error::Error;
+use std::collections::{HashMap, BTreeSet};
+
+use rocket::figment::value::Value;
+
+use crate::engine::Engine;
+use crate::minijinja::{self, ErrorKind, value::{Kwargs, ValueKind}};
+use crate::minijinja::machinery::{self, Instruction, Instructions};
+pub use crate::minijinja::{Environment, default_auto_escape_callback};
+
+pub(crate) const EXT: &str = "j2";
+
+/// Creates the MiniJinja environment with the `uri_for(name, base="/",
+/// **params)` function registered.
+pub(crate) fn new() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.add_function("uri_for", move |name: &str, base: Option<&str>, kwargs: Kwargs| {
+        let mut params = vec![];
+        for key in kwargs.args() {
+            let value = kwargs.get::<minijinja::Value>(key)?;
+            let value = match value.kind() {
+                ValueKind::String | ValueKind::Number | ValueKind::Bool => value.to_string(),
+                _ => {
+                    let msg = format!("uri_for: invalid value for `{}`", key);
+                    return Err(minijinja::Error::new(ErrorKind::InvalidOperation, msg));
+                }
+            };
+
+            params.push((key.to_string(), value));
+        }
+
+        crate::routes::uri_for(name, base.unwrap_or("/"), params)
+            .map_err(|e| {
+                let msg = format!("uri_for: {}", e);
+                minijinja::Error::new(ErrorKind::InvalidOperation, msg)
+            })
+    });
+
+    env
+}
+
+impl Engine for Environment<'static> {
+    fn extensions(&self) -> &[&'static str] {
+        &[EXT]
+    }
+
+    fn load(&mut self, templates: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+        // Discovered templates are named without their extensions, so the
+        // escaping mode is determined from the path, as in `foo.html.j2`. The
+        // mode is fixed when a template is added, so this must come first.
+        let escapes = templates.iter()
+            .map(|(name, path, _)| {
+                let escape = default_auto_escape_callback(&path.to_string_lossy());
+                (name.to_string(), escape)
+            })
+            .collect::<HashMap<_, _>>();
+
+        // Templates registered later, from strings, are named as they are.
+        self.set_auto_escape_callback(move |name| match escapes.get(name) {
+            Some(escape) => *escape,
+            None => default_auto_escape_callback(name),
+        });
+
+        for &(name, path, source) in templates {
+            if let Err(e) = self.add_template_owned(name.to_string(), source.to_string()) {
+                error_!("MiniJinja template '{}' failed to load.", name);
+                info_!("Template path: '{}'.", path.to_string_lossy());
+                return Err(e.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn templates(&self) -> Vec<&str> {
+        self.templates().map(|(name, _)| name).collect()
+    }
+
+    fn render(&self, name: &str, context: &Value) -> Result<String, Box<dyn Error>> {
+        Ok(self.get_template(name)?.render(context)?)
+    }
+
+    fn render_block(
+        &self,
+        name: &str,
+        block: &str,
+        context: &Value
+    ) -> Result<String, Box<dyn Error>> {
+        // Blocks are rendered from the state of the evaluated template, whose
+        // own output is discarded.
+        let template = self.get_template(name)?;
+        let mut captured = template.render_captured_to(context, io::sink())?;
+        Ok(captured.with_state_mut(|state| state.render_block(block))?)
+    }
+
+    fn blocks(&self, name: &str) -> Option<Vec<String>> {
+        // The blocks of a template are those it compiles to instructions for,
+        // along with those of the templates it extends by literal name.
+        let mut blocks = BTreeSet::new();
+        let mut visited = vec![name.to_string()];
+        let mut template = self.get_template(name).ok()?;
+        loop {
+            let compiled = machinery::get_compiled_template(&template);
+            blocks.extend(compiled.blocks.keys().map(|name| name.to_string()));
+            let parent = match parent(&compiled.instructions) {
+                Some(parent) if !visited.iter().any(|name| name == parent) => {
+                    parent.to_string()
+                }
+                _ => break,
+            };
+
+            template = match self.get_template(&parent) {
+                Ok(template) => template,
+                Err(_) => break,
+            };
+
+            visited.push(parent);
+        }
+
+        Some(blocks.into_iter().collect())
+    }
+}
+
+/// Returns the name of the template extended by the template compiled to
+/// `instructions`, if it's a string literal.
+fn parent<'a>(instructions: &'a Instructions<'_>) -> Option<&'a str> {
+    // `{% extends name %}` compiles to the instructions for `name` followed by
+    // `LoadBlocks`; a literal `name` is a single `LoadConst`.
+    (1..instructions.len() as u32)
+        .filter(|&i| matches!(instructions.get(i), Some(Instruction::LoadBlocks)))
+        .find_map(|i| match instructions.get(i - 1) {
+            Some(Instruction::LoadConst(name)) => name.as_str(),
+            _ => None,
+        })
+}
@@ -0,0 +1,91 @@
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+/// Request guard for requests for partial page updates.
+///
+/// A request is a partial request if it carries the `HX-Request: true` header
+/// sent by [htmx], except for boosted requests (`HX-Boosted: true`) and history
+/// restoration requests (`HX-History-Restore-Request: true`), which expect a
+/// full page. The guard forwards when the request is not a partial request, so
+/// it is typically used as `Option<Partial>` to choose between rendering a
+/// block of a template, via [`Template::render_block()`], and the entire
+/// template.
+///
+/// [htmx]: https://htmx.org
+/// [`Template::render_block()`]: crate::Template::render_block()
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket_dyn_templates::{Template, Partial, context};
+///
+/// #[get("/rows")]
+/// fn rows(partial: Option<Partial<'_>>) -> Template {
+///     let context = context! { rows: ["a", "b", "c"] };
+///     match partial {
+///         Some(_) => Template::render_block("page", "rows", context),
+///         None => Template::render("page", context),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partial<'r> {
+    target: Option<&'r str>,
+    trigger: Option<&'r str>,
+    trigger_name: Option<&'r str>,
+}
+
+impl<'r> Partial<'r> {
+    /// The `id` of the element targeted for the update, from the `HX-Target`
+    /// header, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_dyn_templates::Partial;
+    ///
+    /// #[get("/")]
+    /// fn handler(partial: Partial<'_>) -> &'static str {
+    ///     match partial.target() {
+    ///         Some("rows") => "rows",
+    ///         _ => "other",
+    ///     }
+    /// }
+    /// ```
+    pub fn target(&self) -> Option<&'r str> {
+        self.target
+    }
+
+    /// The `id` of the element that triggered the request, from the
+    /// `HX-Trigger` header, if any.
+    pub fn trigger(&self) -> Option<&'r str> {
+        self.trigger
+    }
+
+    /// The `name` of the element that triggered the request, from the
+    /// `HX-Trigger-Name` header, if any.
+    pub fn trigger_name(&self) -> Option<&'r str> {
+        self.trigger_name
+    }
+}
+
+/// Succeeds if the request is a partial request and forwards otherwise.
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Partial<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let is_set = |name| req.headers().get_one(name).map_or(false, |v| v == "true");
+        if !is_set("HX-Request") || is_set("HX-Boosted") || is_set("HX-History-Restore-Request") {
+            return request::Outcome::Forward(());
+        }
+
+        request::Outcome::Success(Partial {
+            target: req.headers().get_one("HX-Target"),
+            trigger: req.headers().get_one("HX-Trigger"),
+            trigger_name: req.headers().get_one("HX-Trigger-Name"),
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use rocket::Request;
+use rocket::figment::{error::Error, value::{Dict, Value}};
+
+/// A provider of template context computed from the request being handled.
+type RequestProvider = Arc<dyn Fn(&Request<'_>) -> Result<Value, Error> + Send + Sync>;
+
+/// Context providers registered with a `TemplateFairing`, merged into the
+/// context of every template rendered.
+#[derive(Clone, Default)]
+pub(crate) struct Providers {
+    /// Global context values, serialized at registration. Errors are reported
+    /// at ignite.
+    globals: Vec<Result<Value, Error>>,
+    /// Request-derived context values, computed at render time.
+    request: Vec<RequestProvider>,
+}
+
+/// Context providers validated at ignite, managed by Rocket.
+pub(crate) struct ContextProviders {
+    /// All of the global context values, merged in order of registration.
+    globals: Dict,
+    request: Vec<RequestProvider>,
+}
+
+impl Providers {
+    pub fn add_global(&mut self, value: Result<Value, Error>) {
+        self.globals.push(value);
+    }
+
+    pub fn add_request<F>(&mut self, provider: F)
+        where F: Fn(&Request<'_>) -> Result<Value, Error> + Send + Sync + 'static
+    {
+        self.request.push(Arc::new(provider));
+    }
+
+    /// Validates the global context values, which must all serialize to maps.
+    /// Logs and returns `None` if any does not.
+    pub fn validate(&self) -> Option<ContextProviders> {
+        let mut globals = Dict::new();
+        for value in &self.globals {
+            match value {
+                Ok(Value::Dict(_, dict)) => globals.extend(dict.clone()),
+                Ok(value) => {
+                    error!("Global template context must be a map.");
+                    info_!("Found a value of type {}.", value.to_actual());
+                    return None;
+                }
+                Err(e) => {
+                    error!("Global template context failed to serialize.");
+                    error_!("{}", e);
+                    return None;
+                }
+            }
+        }
+
+        Some(ContextProviders { globals, request: self.request.clone() })
+    }
+}
+
+impl ContextProviders {
+    /// Merges the provided context into `value`, the user's context, if it is
+    /// a map or empty. Global values are merged first, then request-derived
+    /// values if `req` is `Some`, with `value` taking precedence over both.
+    /// Other values are returned as is.
+    pub fn merge(&self, req: Option<&Request<'_>>, value: Value) -> Result<Value, Error> {
+        let (tag, user) = match value {
+            Value::Dict(tag, dict) => (tag, dict),
+            Value::Empty(tag, _) => (tag, Dict::new()),
+            value => return Ok(value),
+        };
+
+        let mut dict = self.globals.clone();
+        for provider in req.into_iter().flat_map(|req| self.request.iter().map(move |p| p(req))) {
+            match provider? {
+                Value::Dict(_, provided) => dict.extend(provided),
+                Value::Empty(..) => continue,
+                value => {
+                    warn_!("Ignoring non-map request template context: {}.", value.to_actual());
+                }
+            }
+        }
+
+        dict.extend(user);
+        Ok(Value::Dict(tag, dict))
+    }
+}
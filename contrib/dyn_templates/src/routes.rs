@@ -0,0 +1,43 @@
+use std::cell::Cell;
+use std::ptr;
+
+use rocket::{Rocket, Orbit};
+
+thread_local! {
+    /// The instance of Rocket the template being rendered on this thread is
+    /// rendered for, if any. Only non-null while `with_rocket()` runs.
+    static ROCKET: Cell<*const Rocket<Orbit>> = Cell::new(ptr::null());
+}
+
+/// Calls `f`, which renders a template, with the `uri_for` helpers building
+/// URIs to the routes of `rocket`.
+pub(crate) fn with_rocket<T>(rocket: &Rocket<Orbit>, f: impl FnOnce() -> T) -> T {
+    // Restores the previous instance even if `f` panics.
+    struct Restore(*const Rocket<Orbit>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ROCKET.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(ROCKET.with(|current| current.replace(rocket)));
+    f()
+}
+
+/// Builds the URI to the route named `name` mounted at `base` with
+/// [`Rocket::uri_for()`] on the instance set by `with_rocket()`.
+pub(crate) fn uri_for<I>(name: &str, base: &str, params: I) -> Result<String, String>
+    where I: IntoIterator<Item = (String, String)>
+{
+    ROCKET.with(|current| {
+        // SAFETY: `current` is only non-null during a call to `with_rocket()`,
+        // which borrows the instance it points to for the call's duration.
+        let rocket = unsafe { current.get().as_ref() }
+            .ok_or_else(|| format!("no routes are known to link to route `{}`", name))?;
+
+        rocket.uri_for(name, base, params)
+            .map(|uri| uri.to_string())
+            .map_err(|e| e.to_string())
+    })
+}
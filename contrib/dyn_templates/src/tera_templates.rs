@@ -1,64 +1,161 @@
 use std::path::Path;
 use std::error::Error;
+use std::collections::{HashMap, BTreeSet};
 
-use rocket::serde::Serialize;
+use rocket::figment::value::Value;
 
 use crate::engine::Engine;
 
+use crate::tera;
 pub use crate::tera::{Context, Tera};
 
-impl Engine for Tera {
-    const EXT: &'static str = "tera";
-
-    fn init<'a>(templates: impl Iterator<Item = (&'a str, &'a Path)>) -> Option<Self> {
-        // Create the Tera instance.
-        let mut tera = Tera::default();
-        let ext = [".html.tera", ".htm.tera", ".xml.tera", ".html", ".htm", ".xml"];
-        tera.autoescape_on(ext.to_vec());
-
-        // Collect into a tuple of (name, path) for Tera. If we register one at
-        // a time, it will complain about unregistered base templates.
-        let files = templates.map(|(name, path)| (path, Some(name)));
-
-        // Finally try to tell Tera about all of the templates.
-        if let Err(e) = tera.add_template_files(files) {
-            error!("Failed to initialize Tera templating.");
-
-            let mut error = Some(&e as &dyn Error);
-            while let Some(err) = error {
-                info_!("{}", err);
-                error = err.source();
-            }
+pub(crate) const EXT: &str = "tera";
+
+/// The extensions of the template files that are autoescaped.
+const ESCAPED_EXTS: [&str; 3] = [".html.tera", ".htm.tera", ".xml.tera"];
+
+/// Suffix of the name of the template rendered in place of an autoescaped
+/// template. Template names can't contain `\0`, so these never collide.
+const ESCAPED: &str = "\0escaped";
+
+/// Separator in the names of the templates used to render a single block.
+const BLOCK: &str = "\0block:";
 
-            None
-        } else {
-            Some(tera)
+/// Creates the Tera instance with autoescaping enabled for HTML and XML and
+/// the `uri_for` function registered.
+pub(crate) fn new() -> Tera {
+    let mut tera = Tera::default();
+    let ext = [".html.tera", ".htm.tera", ".xml.tera", ".html", ".htm", ".xml", ESCAPED];
+    tera.autoescape_on(ext.to_vec());
+    tera.register_function("uri_for", uri_for());
+    tera
+}
+
+/// The `uri_for(route=name, base="/", ..params)` function. Tera functions only
+/// take named arguments, so `route` and `base` can't name route parameters.
+fn uri_for() -> impl tera::Function {
+    move |args: &HashMap<String, tera::Value>| {
+        let mut name = None;
+        let mut base = "/".to_string();
+        let mut params = vec![];
+        for (key, value) in args {
+            let value = match value {
+                tera::Value::String(s) => s.clone(),
+                tera::Value::Number(n) => n.to_string(),
+                tera::Value::Bool(b) => b.to_string(),
+                _ => return Err(format!("uri_for: invalid value for `{}`", key).into()),
+            };
+
+            match key.as_str() {
+                "route" => name = Some(value),
+                "base" => base = value,
+                _ => params.push((key.clone(), value)),
+            }
         }
+
+        // `args` is unordered; sort so trailing query parameters are stable.
+        params.sort();
+        let name = name.ok_or("uri_for: missing `route` argument")?;
+        let uri = crate::routes::uri_for(&name, &base, params)
+            .map_err(|e| format!("uri_for: {}", e))?;
+        Ok(tera::Value::String(uri))
+    }
+}
+
+impl Engine for Tera {
+    fn extensions(&self) -> &[&'static str] {
+        &[EXT]
+    }
+
+    fn load(&mut self, templates: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+        // Tera decides whether to autoescape a template it renders by its name
+        // when it's added from a string. Template names have no extension, so
+        // each template that should be escaped is rendered via a template
+        // that only extends it and whose name ends in `ESCAPED`.
+        let escaped = templates.iter()
+            .filter(|(_, path, _)| {
+                let path = path.to_string_lossy();
+                ESCAPED_EXTS.iter().any(|ext| path.ends_with(ext))
+            })
+            .map(|(name, ..)| (escaped_name(name), format!("{{% extends \"{}\" %}}", name)));
+
+        let sources = templates.iter().map(|&(name, _, source)| (name.into(), source.into()));
+        self.add_raw_templates(sources.chain(escaped).collect::<Vec<(String, String)>>())?;
+        Ok(())
     }
 
-    fn render<C: Serialize>(&self, name: &str, context: C) -> Option<String> {
+    fn templates(&self) -> Vec<&str> {
+        self.get_template_names()
+            .filter(|name| !name.ends_with(ESCAPED))
+            .collect()
+    }
+
+    fn render(&self, name: &str, context: &Value) -> Result<String, Box<dyn Error>> {
         if self.get_template(name).is_err() {
-            error_!("Tera template '{}' does not exist.", name);
-            return None;
+            return Err(format!("Tera template '{}' does not exist", name).into());
+        };
+
+        let escaped = escaped_name(name);
+        let name = match self.get_template(&escaped) {
+            Ok(_) => &escaped,
+            Err(_) => name,
         };
 
-        let tera_ctx = Context::from_serialize(context)
-            .map_err(|e| error_!("Tera context error: {}.", e))
-            .ok()?;
+        let tera_ctx = Context::from_serialize(context)?;
+        Ok(Tera::render(self, name, &tera_ctx)?)
+    }
+
+    fn render_block(
+        &self,
+        name: &str,
+        block: &str,
+        context: &Value
+    ) -> Result<String, Box<dyn Error>> {
+        let blocks = Engine::blocks(self, name)
+            .ok_or_else(|| format!("Tera template '{}' does not exist", name))?;
 
-        match Tera::render(self, name, &tera_ctx) {
-            Ok(string) => Some(string),
-            Err(e) => {
-                error_!("Error rendering Tera template '{}'.", name);
+        if !blocks.iter().any(|b| b == block) {
+            return Err(format!("Tera template '{}' has no block '{}'", name, block).into());
+        }
 
-                let mut error = Some(&e as &dyn Error);
-                while let Some(err) = error {
-                    error_!("{}", err);
-                    error = err.source();
-                }
+        // Tera renders the root of a template's inheritance chain. The block is
+        // rendered via a copy of the template whose chain ends in a root that
+        // contains only the block, so overrides and `super()` resolve as they
+        // do when rendering the entire template.
+        let root_name = format!("{}{}", BLOCK, block);
+        let root_src = format!("{{% block {0} %}}{{% endblock {0} %}}", block);
+        let root = tera::Template::new(&root_name, None, &root_src)?;
 
-                None
-            }
+        let mut copy = self.get_template(name)?.clone();
+        copy.name = format!("{}{}{}", name, BLOCK, block);
+        if self.get_template(&escaped_name(name)).is_ok() {
+            copy.name = escaped_name(&copy.name);
         }
+
+        copy.ast = vec![];
+        copy.parents.push(root_name.clone());
+
+        let mut tera = self.clone();
+        let copy_name = copy.name.clone();
+        tera.templates.insert(root_name, root);
+        tera.templates.insert(copy_name.clone(), copy);
+
+        let tera_ctx = Context::from_serialize(context)?;
+        Ok(Tera::render(&tera, &copy_name, &tera_ctx)?)
+    }
+
+    fn blocks(&self, name: &str) -> Option<Vec<String>> {
+        let template = self.get_template(name).ok()?;
+        let parents = template.parents.iter().filter_map(|name| self.get_template(name).ok());
+        let blocks = std::iter::once(template).chain(parents)
+            .flat_map(|template| template.blocks.keys().cloned())
+            .collect::<BTreeSet<_>>();
+
+        Some(blocks.into_iter().collect())
     }
 }
+
+/// The name of the template rendering the template `name` autoescaped.
+fn escaped_name(name: &str) -> String {
+    format!("{}{}", name, ESCAPED)
+}
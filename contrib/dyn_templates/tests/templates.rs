@@ -371,3 +371,628 @@ mod handlebars_tests {
         panic!("failed to reload modified template in 1.5s");
     }
 }
+
+#[cfg(feature = "minijinja")]
+mod minijinja_tests {
+    use super::*;
+    use std::collections::HashMap;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    const UNESCAPED_EXPECTED: &'static str
+        = "\nh_start\ntitle: _test_\nh_end\n\n\n<script />\n\nfoot";
+    const ESCAPED_EXPECTED: &'static str
+        = "\nh_start\ntitle: _test_\nh_end\n\n\n&lt;script &#x2f;&gt;\n\nfoot";
+
+    #[test]
+    fn test_minijinja_templates() {
+        let client = Client::debug(rocket()).unwrap();
+        let mut map = HashMap::new();
+        map.insert("title", "_test_");
+        map.insert("content", "<script />");
+
+        // Test with a txt file, which shouldn't escape.
+        let template = Template::show(client.rocket(), "j2/txt_test", &map);
+        assert_eq!(template, Some(UNESCAPED_EXPECTED.into()));
+
+        // Now with an HTML file, which should.
+        let template = Template::show(client.rocket(), "j2/html_test", &map);
+        assert_eq!(template, Some(ESCAPED_EXPECTED.into()));
+    }
+
+    #[test]
+    fn test_template_metadata_with_minijinja() {
+        let client = Client::debug(rocket()).unwrap();
+
+        let response = client.get("/j2/txt_test").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/j2/html_test").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/j2/not_existing").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.get("/hbs/txt_test").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_minijinja_customization() {
+        let rocket = rocket::custom(Config::figment().merge(("template_dir", template_root())))
+            .attach(Template::custom(|engines| {
+                engines.minijinja.add_filter("shout", |s: String| s.to_uppercase() + "!");
+                engines.minijinja.add_template("inline.html", "{{ name }}").unwrap();
+            }));
+
+        let client = Client::debug(rocket).unwrap();
+        let template = Template::show(client.rocket(), "j2/filter", context! { title: "hi" });
+        assert_eq!(template, Some("HI!".into()));
+
+        // Registered templates keep their names and escape by extension.
+        let template = Template::show(client.rocket(), "inline.html", context! { name: "<b>" });
+        assert_eq!(template, Some("&lt;b&gt;".into()));
+
+        // Without the filter, the template fails to render.
+        let client = Client::debug(super::rocket()).unwrap();
+        let template = Template::show(client.rocket(), "j2/filter", context! { title: "hi" });
+        assert_eq!(template, None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_minijinja_template_reload() {
+        use std::fs::File;
+        use std::io::Write;
+        use std::time::Duration;
+
+        const RELOAD_TEMPLATE: &str = "j2/reload";
+        const INITIAL_TEXT: &str = "initial";
+        const NEW_TEXT: &str = "reload";
+
+        fn write_file(path: &Path, text: &str) {
+            let mut file = File::create(path).expect("open file");
+            file.write_all(text.as_bytes()).expect("write file");
+            file.sync_all().expect("sync file");
+        }
+
+        let reload_path = template_root().join("j2").join("reload.txt.j2");
+        write_file(&reload_path, INITIAL_TEXT);
+
+        let client = Client::debug(rocket()).unwrap();
+        let res = client.get("/is_reloading").dispatch();
+        if res.status() != Status::Ok {
+            return;
+        }
+
+        let initial_rendered = Template::show(client.rocket(), RELOAD_TEMPLATE, ());
+        assert_eq!(initial_rendered, Some(INITIAL_TEXT.into()));
+
+        write_file(&reload_path, NEW_TEXT);
+        for _ in 0..6 {
+            client.get("/").dispatch();
+
+            let new_rendered = Template::show(client.rocket(), RELOAD_TEMPLATE, ());
+            if new_rendered == Some(NEW_TEXT.into()) {
+                write_file(&reload_path, INITIAL_TEXT);
+                return;
+            }
+
+            std::thread::sleep(Duration::from_millis(250));
+        }
+
+        panic!("failed to reload modified template in 1.5s");
+    }
+}
+
+mod custom_engine_tests {
+    use super::*;
+    use std::error::Error;
+    use std::collections::HashMap;
+
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+    use rocket_dyn_templates::Engine;
+
+    /// Replaces `$key` in templates with the string value of `key`.
+    #[derive(Default)]
+    struct Dollar(HashMap<String, String>);
+
+    impl Engine for Dollar {
+        fn extensions(&self) -> &[&'static str] {
+            &["dollar"]
+        }
+
+        fn load(&mut self, templates: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+            for (name, _, source) in templates {
+                self.0.insert(name.to_string(), source.to_string());
+            }
+
+            Ok(())
+        }
+
+        fn templates(&self) -> Vec<&str> {
+            self.0.keys().map(|name| name.as_str()).collect()
+        }
+
+        fn render(&self, name: &str, context: &Value) -> Result<String, Box<dyn Error>> {
+            let template = self.0.get(name).ok_or("missing template")?;
+            let dict = context.as_dict().ok_or("context must be a map")?;
+            Ok(dict.iter().fold(template.clone(), |string, (key, value)| {
+                let value = value.as_str().map(String::from).unwrap_or_default();
+                string.replace(&format!("${}", key), &value)
+            }))
+        }
+    }
+
+    /// Claims an extension already claimed by `Dollar`.
+    struct Imposter;
+
+    impl Engine for Imposter {
+        fn extensions(&self) -> &[&'static str] {
+            &["dollar", "imposter"]
+        }
+
+        fn load(&mut self, templates: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+            match templates.is_empty() {
+                true => Ok(()),
+                false => Err("imposter was handed templates".into()),
+            }
+        }
+
+        fn templates(&self) -> Vec<&str> {
+            vec![]
+        }
+
+        fn render(&self, _: &str, _: &Value) -> Result<String, Box<dyn Error>> {
+            Ok("imposter".into())
+        }
+    }
+
+    #[get("/greet/<name>")]
+    fn greet(name: &str) -> Template {
+        Template::render("dollar/greet", context! { name })
+    }
+
+    fn rocket() -> Rocket<Build> {
+        rocket::custom(Config::figment().merge(("template_dir", template_root())))
+            .attach(Template::custom(|engines| {
+                engines.register(Dollar::default()).register(Imposter);
+            }))
+            .mount("/", routes![template_check, greet])
+    }
+
+    #[test]
+    fn test_custom_engine() {
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.get("/dollar/greet").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let template = Template::show(client.rocket(), "dollar/greet", context! { name: "Bob" });
+        assert_eq!(template, Some("Hello, Bob!\n".into()));
+
+        let response = client.get("/greet/Alice").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::Plain));
+        assert_eq!(response.into_string().unwrap(), "Hello, Alice!\n");
+
+        // Render errors from custom engines surface as a `500`.
+        let template = Template::show(client.rocket(), "dollar/greet", 10);
+        assert_eq!(template, None);
+    }
+
+    #[test]
+    fn test_custom_engine_without_registration() {
+        let client = Client::debug(super::rocket()).unwrap();
+        let response = client.get("/dollar/greet").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_custom_engine_load_error() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        struct Failing;
+
+        impl Engine for Failing {
+            fn extensions(&self) -> &[&'static str] { &["dollar"] }
+            fn load(&mut self, _: &[(&str, &Path, &str)]) -> Result<(), Box<dyn Error>> {
+                Err("failed to load".into())
+            }
+            fn templates(&self) -> Vec<&str> { vec![] }
+            fn render(&self, _: &str, _: &Value) -> Result<String, Box<dyn Error>> {
+                Ok(String::new())
+            }
+        }
+
+        let rocket = rocket::custom(Config::figment().merge(("template_dir", template_root())))
+            .attach(Template::custom(|engines| { engines.register(Failing); }));
+
+        let error = Client::debug(rocket).expect_err("client failure");
+        match error.kind() {
+            FailedFairings(failures) => assert_eq!(failures[0].name, "Templating"),
+            _ => panic!("Wrong kind of launch error"),
+        }
+    }
+}
+
+#[cfg(all(feature = "embed", feature = "tera"))]
+mod embed_tests {
+    use super::*;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+    use rocket_dyn_templates::{Embedded, embed};
+
+    static TEMPLATES: Embedded = embed!("$CARGO_MANIFEST_DIR/tests/templates");
+
+    fn rocket() -> Rocket<Build> {
+        rocket::build()
+            .attach(Template::embedded(&TEMPLATES, |engines| {
+                engines.tera.add_raw_template("hello", "Hello, {{ name }}!").unwrap();
+            }))
+            .mount("/", routes![template_check, is_reloading])
+    }
+
+    #[test]
+    fn test_embedded_templates() {
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.get("/tera/txt_test").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let context = context! { title: "_test_", content: "<script />" };
+        let template = Template::show(client.rocket(), "tera/html_test", &context).unwrap();
+        assert!(template.contains("&lt;script &#x2F;&gt;"));
+
+        let template = Template::show(client.rocket(), "tera/txt_test", &context).unwrap();
+        assert!(template.contains("<script />"));
+
+        let template = Template::show(client.rocket(), "hello", context! { name: "Bob" });
+        assert_eq!(template, Some("Hello, Bob!".into()));
+    }
+
+    #[test]
+    fn test_embedded_templates_ignore_template_dir() {
+        let figment = Config::figment()
+            .merge(("template_dir", "/missing"))
+            .merge(("template_embedded", true));
+
+        let client = Client::debug(rocket().configure(figment)).unwrap();
+        let response = client.get("/is_reloading").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.get("/tera/txt_test").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_embedded_templates_use_template_dir_in_debug() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        let rocket = rocket().configure(Config::figment().merge(("template_dir", "/missing")));
+        let error = Client::debug(rocket).expect_err("client failure");
+        assert!(matches!(error.kind(), FailedFairings(_)));
+    }
+
+    #[test]
+    fn test_embedded_templates_can_be_disabled() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        let figment = Config::figment()
+            .merge(("template_dir", "/missing"))
+            .merge(("template_embedded", false));
+
+        let error = Client::debug(rocket().configure(figment)).expect_err("client failure");
+        assert!(matches!(error.kind(), FailedFairings(_)));
+    }
+}
+
+#[cfg(feature = "tera")]
+mod provider_tests {
+    use super::*;
+    use rocket::http::{Cookie, Header};
+    use rocket::local::blocking::Client;
+
+    #[get("/<name>")]
+    fn hello(name: &str) -> Template {
+        Template::render("tera/providers", context! { name })
+    }
+
+    #[get("/override")]
+    fn override_version() -> Template {
+        Template::render("tera/providers", context! { name: "Bob", version: "override" })
+    }
+
+    fn rocket() -> Rocket<Build> {
+        let fairing = Template::fairing()
+            .context(context! { version: "1.0", name: "global" })
+            .request_context(|req| {
+                let user = req.cookies().get("user").map(|c| c.value().to_string());
+                context! { user }
+            })
+            .request_context(|req| {
+                let version = req.headers().get_one("X-Version").map(String::from);
+                version.map(|version| context! { version })
+            });
+
+        rocket::custom(Config::figment().merge(("template_dir", template_root())))
+            .attach(fairing)
+            .mount("/", routes![hello, override_version])
+    }
+
+    #[test]
+    fn test_context_providers() {
+        let client = Client::debug(rocket()).unwrap();
+
+        let response = client.get("/Alice").dispatch();
+        assert_eq!(response.into_string().unwrap(), "1.0 anonymous Alice\n");
+
+        let response = client.get("/Alice").cookie(Cookie::new("user", "bob")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "1.0 bob Alice\n");
+
+        // Request-derived values take precedence over global ones...
+        let response = client.get("/Alice").header(Header::new("X-Version", "2.0")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "2.0 anonymous Alice\n");
+
+        // ...and the template's own context takes precedence over both.
+        let response = client.get("/override").header(Header::new("X-Version", "2.0")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "override anonymous Bob\n");
+
+        // Without a request, only global values are merged.
+        let template = Template::show(client.rocket(), "tera/providers", ());
+        assert_eq!(template, Some("1.0 anonymous global\n".into()));
+
+        // Contexts that aren't maps are left untouched.
+        let template = Template::show(client.rocket(), "tera/providers", 10);
+        assert_eq!(template, None);
+    }
+
+    #[test]
+    fn test_invalid_global_context() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        let rocket = rocket::custom(Config::figment().merge(("template_dir", template_root())))
+            .attach(Template::fairing().context("not a map"));
+
+        let error = Client::debug(rocket).expect_err("client failure");
+        match error.kind() {
+            FailedFairings(failures) => assert_eq!(failures[0].name, "Templating"),
+            _ => panic!("Wrong kind of launch error"),
+        }
+    }
+}
+
+#[cfg(feature = "minijinja")]
+mod fragment_tests {
+    use super::*;
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::local::blocking::Client;
+    use rocket_dyn_templates::Partial;
+
+    #[get("/page")]
+    fn page(partial: Option<Partial<'_>>) -> Template {
+        let context = context! { title: "_test_", content: "<script />" };
+        match partial {
+            Some(partial) if partial.target() == Some("head") => {
+                Template::render_block("j2/html_test", "head", context)
+            }
+            Some(_) => Template::render_block("j2/html_test", "content", context),
+            None => Template::render("j2/html_test", context),
+        }
+    }
+
+    #[get("/block/<engine>/<name>/<block>")]
+    fn block(engine: &str, name: &str, block: String) -> Template {
+        let context = context! { title: "_test_", content: "<script />", x: "<x>" };
+        let name = match engine {
+            "raw" => name.to_string(),
+            _ => format!("{}/{}", engine, name),
+        };
+
+        Template::render_block(name, block, context)
+    }
+
+    #[get("/blocks/<engine>/<name>")]
+    fn blocks(md: Metadata<'_>, engine: &str, name: &str) -> Option<String> {
+        let name = match engine {
+            "raw" => name.to_string(),
+            _ => format!("{}/{}", engine, name),
+        };
+
+        md.blocks(&name).map(|blocks| blocks.join(","))
+    }
+
+    fn rocket() -> Rocket<Build> {
+        // Templates added in the callback can have their blocks rendered too.
+        let fairing = Template::custom(|engines| {
+            let source = "{# {% block no %} #}{% raw %}{% block no %}{% endraw %}\
+                {%- block a -%}{{ x }}{%- endblock a -%}";
+
+            engines.minijinja.add_template("raw.html", source).expect("valid template");
+        });
+
+        rocket::custom(Config::figment().merge(("template_dir", template_root())))
+            .attach(fairing)
+            .mount("/", routes![page, block, blocks])
+    }
+
+    fn get(client: &Client, uri: &str) -> Option<String> {
+        let response = client.get(uri.to_string()).dispatch();
+        if response.status() != Status::Ok {
+            return None;
+        }
+
+        response.into_string()
+    }
+
+    #[test]
+    fn test_render_block() {
+        let client = Client::debug(rocket()).unwrap();
+
+        // Blocks are escaped like the template and resolve child overrides,
+        // including of nested blocks.
+        let content = get(&client, "/block/j2/html_test/content");
+        assert_eq!(content.unwrap(), "\n&lt;script &#x2f;&gt;\n");
+
+        let head = get(&client, "/block/j2/html_test/head");
+        assert_eq!(head.unwrap(), "\nh_start\ntitle: _test_\nh_end\n");
+
+        let title = get(&client, "/block/j2/txt_test/title");
+        assert_eq!(title.unwrap(), "_test_");
+
+        // Blocks only defined by a parent, and `super()`, work as usual.
+        let footer = get(&client, "/block/j2/html_test/footer");
+        assert_eq!(footer.unwrap(), "foot");
+
+        let footer = get(&client, "/block/j2/fragment/footer");
+        assert_eq!(footer.unwrap(), "foot & &lt;script &#x2f;&gt;");
+
+        let raw = get(&client, "/block/raw/raw.html/a");
+        assert_eq!(raw.unwrap(), "&lt;x&gt;");
+
+        // The response has the template's content type.
+        let response = client.get("/block/j2/html_test/title").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+        assert!(get(&client, "/block/j2/html_test/unknown").is_none());
+        assert!(get(&client, "/block/j2/unknown/content").is_none());
+
+        // Tera blocks render the same way.
+        #[cfg(feature = "tera")] {
+            let content = get(&client, "/block/tera/html_test/content");
+            assert_eq!(content.unwrap(), "\n&lt;script &#x2F;&gt;\n");
+
+            let head = get(&client, "/block/tera/txt_test/head");
+            assert_eq!(head.unwrap(), "\nh_start\ntitle: _test_\nh_end\n");
+
+            let footer = get(&client, "/block/tera/fragment/footer");
+            assert_eq!(footer.unwrap(), "foot & &lt;script &#x2F;&gt;");
+
+            assert!(get(&client, "/block/tera/html_test/unknown").is_none());
+            assert!(get(&client, "/block/tera/unknown/content").is_none());
+        }
+
+        // Handlebars templates have no blocks.
+        #[cfg(feature = "handlebars")]
+        assert!(get(&client, "/block/hbs/test/content").is_none());
+    }
+
+    #[test]
+    fn test_template_blocks_metadata() {
+        let client = Client::debug(rocket()).unwrap();
+
+        let blocks = get(&client, "/blocks/j2/html_test");
+        assert_eq!(blocks.unwrap(), "content,footer,head,title");
+
+        let blocks = get(&client, "/blocks/j2/base");
+        assert_eq!(blocks.unwrap(), "content,footer,head,title");
+
+        let blocks = get(&client, "/blocks/raw/raw.html");
+        assert_eq!(blocks.unwrap(), "a");
+
+        assert!(get(&client, "/blocks/j2/unknown").is_none());
+
+        #[cfg(feature = "tera")] {
+            let blocks = get(&client, "/blocks/tera/html_test");
+            assert_eq!(blocks.unwrap(), "content,footer,head,title");
+
+            let blocks = get(&client, "/blocks/tera/links");
+            assert_eq!(blocks.unwrap(), "");
+
+            assert!(get(&client, "/blocks/tera/unknown").is_none());
+        }
+    }
+
+    #[test]
+    fn test_partial_request_guard() {
+        const FULL: &str = "\nh_start\ntitle: _test_\nh_end\n\n\n&lt;script &#x2f;&gt;\n\nfoot";
+
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.get("/page").dispatch();
+        assert_eq!(response.into_string().unwrap(), FULL);
+
+        let response = client.get("/page").header(Header::new("HX-Request", "true")).dispatch();
+        assert_eq!(response.into_string().unwrap(), "\n&lt;script &#x2f;&gt;\n");
+
+        let response = client.get("/page")
+            .header(Header::new("HX-Request", "true"))
+            .header(Header::new("HX-Target", "head"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "\nh_start\ntitle: _test_\nh_end\n");
+
+        // Boosted and history restoration requests expect the full page.
+        let response = client.get("/page")
+            .header(Header::new("HX-Request", "true"))
+            .header(Header::new("HX-Boosted", "true"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), FULL);
+
+        let response = client.get("/page")
+            .header(Header::new("HX-Request", "true"))
+            .header(Header::new("HX-History-Restore-Request", "true"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), FULL);
+    }
+}
+
+mod uri_for_tests {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[get("/post/<id:u64>/<slug>?<page>")]
+    fn post(id: u64, slug: &str, page: Option<usize>) -> String {
+        format!("{} {} {:?}", id, slug, page)
+    }
+
+    #[get("/links/<id>")]
+    fn links_page(id: &str) -> Template {
+        Template::render("j2/links", context! { id })
+    }
+
+    fn client() -> Client {
+        Client::debug(rocket().mount("/blog", routes![post]).mount("/", routes![links_page]))
+            .unwrap()
+    }
+
+    fn links(client: &Client, engine: &str, id: &str) -> Option<String> {
+        let template = Template::show(client.rocket(), format!("{}/links", engine), context! { id });
+        template.map(|links| links.trim_end().to_string())
+    }
+
+    const LINKS: &str = "/blog/post/7/a%20b\n/blog/post/7/x?page=2";
+
+    #[test]
+    #[cfg(feature = "tera")]
+    fn test_tera_uri_for() {
+        let client = client();
+        assert_eq!(links(&client, "tera", "7").unwrap(), LINKS);
+        assert!(links(&client, "tera", "seven").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "handlebars")]
+    fn test_handlebars_uri_for() {
+        let client = client();
+        // Handlebars escapes all output, including `=`, as HTML.
+        assert_eq!(links(&client, "hbs", "7").unwrap(), LINKS.replace('=', "&#x3D;"));
+        assert!(links(&client, "hbs", "seven").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "minijinja")]
+    fn test_minijinja_uri_for() {
+        let client = client();
+        assert_eq!(links(&client, "j2", "7").unwrap(), LINKS);
+        assert!(links(&client, "j2", "seven").is_none());
+
+        let response = client.get(LINKS.lines().last().unwrap()).dispatch();
+        assert_eq!(response.into_string().unwrap(), "7 x Some(2)");
+
+        // Templates rendered as responses link to the routes of their instance.
+        let response = client.get("/links/7").dispatch();
+        assert_eq!(response.into_string().unwrap().trim_end(), LINKS);
+    }
+}
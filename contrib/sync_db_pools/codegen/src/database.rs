@@ -84,7 +84,7 @@ pub fn database_attr(attr: TokenStream, input: TokenStream) -> Result<TokenStrea
         impl #guard_type {
             /// Returns a fairing that initializes the associated database
             /// connection pool.
-            pub fn fairing() -> impl #rocket::fairing::Fairing {
+            pub fn fairing() -> #root::PoolFairing<Self, #conn_type> {
                 <#pool>::fairing(#fairing_name, #name)
             }
 
@@ -97,6 +97,15 @@ pub fn database_attr(attr: TokenStream, input: TokenStream) -> Result<TokenStrea
                 <#pool>::get_one(&__rocket).await.map(Self)
             }
 
+            /// Returns the current statistics of the connection pool in the
+            /// `rocket` instance. Returns `Some` as long as `Self::fairing()`
+            /// has been attached and the pool has been initialized.
+            pub fn pool_stats<P>(__rocket: &#rocket::Rocket<P>) -> Option<#root::PoolStats>
+                where P: #rocket::Phase,
+            {
+                <#pool>::pool_stats(&__rocket)
+            }
+
             /// Runs the provided closure on a thread from a threadpool. The
             /// closure will be passed an `&mut r2d2::PooledConnection`.
             /// `.await`ing the return value of this function yields the value
@@ -121,6 +130,12 @@ pub fn database_attr(attr: TokenStream, input: TokenStream) -> Result<TokenStrea
             }
         }
 
+        impl #root::Database for #guard_type {
+            type Connection = #conn_type;
+
+            const NAME: &'static str = #name;
+        }
+
         impl #rocket::Sentinel for #guard_type {
             fn abort(__r: &#rocket::Rocket<#rocket::Ignite>) -> bool {
                 <#conn>::abort(__r)
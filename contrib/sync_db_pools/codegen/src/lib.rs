@@ -30,18 +30,23 @@ use proc_macro::TokenStream;
 /// retrieves a connection from the database pool or fails with a
 /// `Status::ServiceUnavailable` if connecting to the database times out.
 ///
-/// The macro also generates three inherent methods on the decorated type:
+/// The macro also generates four inherent methods on the decorated type:
 ///
-///   * `fn fairing() -> impl Fairing`
+///   * `fn fairing() -> PoolFairing<Self, Db>`
 ///
 ///      Returns a fairing that initializes the associated database connection
-///      pool.
+///      pool. Connection hooks are registered with `PoolFairing::on_connect()`.
 ///
 ///   * `async fn get_one<P: Phase>(&Rocket<P>) -> Option<Self>`
 ///
 ///     Retrieves a connection wrapper from the configured pool. Returns `Some`
 ///     as long as `Self::fairing()` has been attached.
 ///
+///   * `fn pool_stats<P: Phase>(&Rocket<P>) -> Option<PoolStats>`
+///
+///     Returns the current statistics of the configured pool. Returns `Some`
+///     as long as `Self::fairing()` has been attached and has run.
+///
 ///   * `async fn run<R: Send + 'static>(&self, impl FnOnce(&mut Db) -> R + Send + 'static) -> R`
 ///
 ///     Runs the specified function or closure, providing it access to the
@@ -1,5 +1,5 @@
 use rocket::{Rocket, Build};
-use rocket::figment::{self, Figment, providers::Serialized};
+use rocket::figment::{self, Figment, providers::Serialized, value::Dict};
 
 use serde::{Serialize, Deserialize};
 
@@ -27,8 +27,9 @@ use serde::{Serialize, Deserialize};
 ///
 /// If you want to implement your own custom database adapter (or other
 /// database-like struct that can be pooled by `r2d2`) and need some more
-/// configurations options, you may need to define a custom `Config` struct.
-/// Note, however, that the configuration values in `Config` are required.
+/// configurations options, you may need to define a custom `Config` struct or
+/// read them from [`Config::extras()`]. Note, however, that the configuration
+/// values in `Config` are required.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Config {
     /// Connection URL specified in the Rocket configuration.
@@ -116,4 +117,46 @@ impl Config {
             None => figment
         }
     }
+
+    /// Retrieves the configuration keys for the database named `db_name` that
+    /// aren't read into `Config`, such as `min_idle`. These are passed through
+    /// to [`Poolable::pool_with()`](crate::Poolable::pool_with()), which
+    /// applies the keys it supports.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::figment::{Figment, providers::{Format, Toml}};
+    /// // Assume that these are the contents of `Rocket.toml`:
+    /// # let toml = Toml::string(r#"
+    /// [default.databases]
+    /// my_db = { url = "db/db.sqlite", min_idle = 2 }
+    /// # "#).nested();
+    ///
+    /// use rocket::{Rocket, Build};
+    /// use rocket_sync_db_pools::Config;
+    ///
+    /// fn pool(rocket: &Rocket<Build>) {
+    ///     let extras = Config::extras("my_db", rocket).unwrap();
+    ///     assert_eq!(extras["min_idle"].to_i128(), Some(2));
+    ///     assert!(!extras.contains_key("url"));
+    /// }
+    /// #
+    /// # let config = Figment::from(rocket::Config::default()).merge(toml);
+    /// # let rocket = rocket::custom(config);
+    /// # pool(&rocket);
+    /// ```
+    pub fn extras(db_name: &str, rocket: &Rocket<Build>) -> Result<Dict, figment::Error> {
+        #[derive(Deserialize)]
+        struct Extras {
+            #[serde(flatten)]
+            _config: Config,
+            #[serde(flatten)]
+            extras: Dict,
+        }
+
+        let mut extras = Config::figment(db_name, rocket).extract::<Extras>()?.extras;
+        extras.remove("migrations");
+        Ok(extras)
+    }
 }
@@ -1,16 +1,22 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use rocket::{Phase, Rocket, Ignite, Sentinel};
-use rocket::fairing::{AdHoc, Fairing};
+use rocket::{Phase, Rocket, Build, Ignite, Sentinel};
+use rocket::fairing::{self, Fairing, Info, Kind};
 use rocket::request::{Request, Outcome, FromRequest};
+use rocket::response::Response;
 use rocket::outcome::IntoOutcome;
 use rocket::http::Status;
 
 use rocket::tokio::sync::{OwnedSemaphorePermit, Semaphore, Mutex};
 use rocket::tokio::time::timeout;
 
-use crate::{Config, Poolable, Error};
+use r2d2::ManageConnection;
+
+use crate::{Config, Poolable, Error, OnConnect, PoolStats};
+use crate::health::{Check, Registry};
+use crate::stats::Metrics;
 
 /// Unstable internal details of generated code for the #[database] attribute.
 ///
@@ -18,19 +24,23 @@ use crate::{Config, Poolable, Error};
 /// types are properly checked.
 #[doc(hidden)]
 pub struct ConnectionPool<K, C: Poolable> {
+    name: &'static str,
     config: Config,
     // This is an 'Option' so that we can drop the pool in a 'spawn_blocking'.
     pool: Option<r2d2::Pool<C::Manager>>,
     semaphore: Arc<Semaphore>,
+    metrics: Arc<Metrics>,
     _marker: PhantomData<fn() -> K>,
 }
 
 impl<K, C: Poolable> Clone for ConnectionPool<K, C> {
     fn clone(&self) -> Self {
         ConnectionPool {
+            name: self.name,
             config: self.config.clone(),
             pool: self.pool.clone(),
             semaphore: self.semaphore.clone(),
+            metrics: self.metrics.clone(),
             _marker: PhantomData
         }
     }
@@ -68,37 +78,135 @@ macro_rules! dberr {
     });
 }
 
-impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
-    pub fn fairing(fairing_name: &'static str, db: &'static str) -> impl Fairing {
-        AdHoc::try_on_ignite(fairing_name, move |rocket| async move {
-            run_blocking(move || {
-                let config = match Config::from(db, &rocket) {
-                    Ok(config) => config,
-                    Err(e) => dberr!("config", db, "{}", e, rocket),
-                };
-
-                let pool_size = config.pool_size;
-                match C::pool(db, &rocket) {
-                    Ok(pool) => Ok(rocket.manage(ConnectionPool::<K, C> {
-                        config,
-                        pool: Some(pool),
-                        semaphore: Arc::new(Semaphore::new(pool_size as usize)),
-                        _marker: PhantomData,
-                    })),
-                    Err(Error::Config(e)) => dberr!("config", db, "{}", e, rocket),
-                    Err(Error::Pool(e)) => dberr!("pool init", db, "{}", e, rocket),
-                    Err(Error::Custom(e)) => dberr!("pool manager", db, "{:?}", e, rocket),
+/// The fairing returned by the `fairing()` method generated by
+/// `#[database]`, which initializes the database's connection pool at ignite.
+///
+/// Hooks registered with [`PoolFairing::on_connect()`] are run on every new
+/// connection in the pool. The fairing also finishes the request's
+/// [`Transaction`](crate::Transaction), if any, when the response is sent.
+pub struct PoolFairing<K, C: Poolable> {
+    name: &'static str,
+    db: &'static str,
+    on_connect: OnConnect<C>,
+    _marker: PhantomData<fn() -> K>,
+}
+
+impl<K: 'static, C: Poolable> PoolFairing<K, C> {
+    /// Registers `hook` to be run on every new connection in the pool, after
+    /// any previously registered hooks. Hooks apply per-connection settings
+    /// such as SQLite pragmas or Postgres session parameters. A connection for
+    /// which a hook fails is discarded; if no connection can be established
+    /// when the pool is initialized, launch is aborted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// # #[macro_use] extern crate rocket_sync_db_pools;
+    /// # #[cfg(feature = "diesel_sqlite_pool")] {
+    /// use rocket_sync_db_pools::diesel::{self, connection::SimpleConnection};
+    ///
+    /// #[database("my_db")]
+    /// struct MyDatabase(diesel::SqliteConnection);
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build().attach(MyDatabase::fairing().on_connect(|conn| {
+    ///         conn.batch_execute("PRAGMA synchronous = NORMAL;")
+    ///             .map_err(diesel::r2d2::Error::QueryError)
+    ///     }))
+    /// }
+    /// # }
+    /// ```
+    pub fn on_connect<F>(mut self, hook: F) -> Self
+        where F: Fn(&mut C) -> Result<(), <C::Manager as ManageConnection>::Error>,
+              F: Send + Sync + 'static
+    {
+        self.on_connect.push(hook);
+        self
+    }
+}
+
+#[rocket::async_trait]
+impl<K: 'static, C: Poolable> Fairing for PoolFairing<K, C> {
+    fn info(&self) -> Info {
+        Info { name: self.name, kind: Kind::Ignite | Kind::Response }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let (db, on_connect) = (self.db, self.on_connect.clone());
+        run_blocking(move || {
+            let config = match Config::from(db, &rocket) {
+                Ok(config) => config,
+                Err(e) => dberr!("config", db, "{}", e, rocket),
+            };
+
+            let extras = match Config::extras(db, &rocket) {
+                Ok(extras) => extras,
+                Err(e) => dberr!("config", db, "{}", e, rocket),
+            };
+
+            let migrations = match crate::migrations::directory(db, &rocket) {
+                Ok(migrations) => migrations,
+                Err(e) => dberr!("config", db, "{}", e, rocket),
+            };
+
+            let pool = match C::pool_with(db, &rocket, &extras, on_connect) {
+                Ok(pool) => pool,
+                Err(Error::Config(e)) => dberr!("config", db, "{}", e, rocket),
+                Err(Error::Pool(e)) => dberr!("pool init", db, "{}", e, rocket),
+                Err(Error::Custom(e)) => dberr!("pool manager", db, "{:?}", e, rocket),
+            };
+
+            if let Some(dir) = migrations {
+                info!("Running migrations for `{}` in '{}'.", db, dir.display());
+                let result = pool.get()
+                    .map_err(|e| e.into())
+                    .and_then(|mut conn| conn.migrate(&dir));
+
+                if let Err(e) = result {
+                    dberr!("migration", db, "{}", e, rocket);
                 }
-            }).await
-        })
+            }
+
+            let pool_size = config.pool_size;
+            let pool = ConnectionPool::<K, C> {
+                name: db,
+                config,
+                pool: Some(pool),
+                semaphore: Arc::new(Semaphore::new(pool_size as usize)),
+                metrics: Arc::new(Metrics::default()),
+                _marker: PhantomData,
+            };
+
+            let rocket = Registry::register(rocket, Arc::new(pool.clone()));
+            Ok(rocket.manage(pool))
+        }).await
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        crate::transaction::finish::<K, C>(req, res).await
+    }
+}
+
+impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
+    pub fn fairing(fairing_name: &'static str, db: &'static str) -> PoolFairing<K, C> {
+        PoolFairing {
+            name: fairing_name,
+            db,
+            on_connect: OnConnect::default(),
+            _marker: PhantomData,
+        }
     }
 
-    async fn get(&self) -> Result<Connection<K, C>, ()> {
-        let duration = std::time::Duration::from_secs(self.config.timeout as u64);
+    pub(crate) async fn get(&self) -> Result<Connection<K, C>, ()> {
+        let start = Instant::now();
+        let duration = Duration::from_secs(self.config.timeout as u64);
         let permit = match timeout(duration, self.semaphore.clone().acquire_owned()).await {
             Ok(p) => p.expect("internal invariant broken: semaphore should not be closed"),
             Err(_) => {
                 error_!("database connection retrieval timed out");
+                self.metrics.timed_out(start.elapsed());
                 return Err(());
             }
         };
@@ -106,19 +214,31 @@ impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
         let pool = self.pool.as_ref().cloned()
             .expect("internal invariant broken: self.pool is Some");
 
+        // With a permit, a connection is idle or can be opened, so `r2d2` only
+        // fails to provide one if connecting fails: that's not a timeout.
         match run_blocking(move || pool.get_timeout(duration)).await {
-            Ok(c) => Ok(Connection {
-                connection: Arc::new(Mutex::new(Some(c))),
-                permit: Some(permit),
-                _marker: PhantomData,
-            }),
+            Ok(c) => {
+                self.metrics.acquired(start.elapsed());
+                Ok(Connection {
+                    connection: Arc::new(Mutex::new(Some(c))),
+                    permit: Some(permit),
+                    _marker: PhantomData,
+                })
+            }
             Err(e) => {
                 error_!("failed to get a database connection: {}", e);
+                self.metrics.failed(start.elapsed());
                 Err(())
             }
         }
     }
 
+    /// Returns the current statistics of the pool.
+    pub fn stats(&self) -> PoolStats {
+        let pool = self.pool.as_ref().expect("internal invariant broken: self.pool is Some");
+        self.metrics.stats(self.config.pool_size, pool.state())
+    }
+
     #[inline]
     pub async fn get_one<P: Phase>(rocket: &Rocket<P>) -> Option<Connection<K, C>> {
         match rocket.state::<Self>() {
@@ -140,6 +260,42 @@ impl<K: 'static, C: Poolable> ConnectionPool<K, C> {
     pub async fn get_pool<P: Phase>(rocket: &Rocket<P>) -> Option<Self> {
         rocket.state::<Self>().cloned()
     }
+
+    #[inline]
+    pub fn pool_stats<P: Phase>(rocket: &Rocket<P>) -> Option<PoolStats> {
+        rocket.state::<Self>().map(|pool| pool.stats())
+    }
+}
+
+#[rocket::async_trait]
+impl<K: 'static, C: Poolable> Check for ConnectionPool<K, C> {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn stats(&self) -> PoolStats {
+        ConnectionPool::stats(self)
+    }
+
+    async fn ping(&self) -> Result<Duration, String> {
+        let start = Instant::now();
+        let duration = Duration::from_secs(self.config.timeout as u64);
+        let permit = match timeout(duration, self.semaphore.clone().acquire_owned()).await {
+            Ok(p) => p.expect("internal invariant broken: semaphore should not be closed"),
+            Err(_) => return Err("timed out waiting for a connection".into()),
+        };
+
+        let pool = self.pool.as_ref().cloned()
+            .expect("internal invariant broken: self.pool is Some");
+
+        // The pool validates connections before handing them out. As in `get`,
+        // the permit is only released after the connection is.
+        run_blocking(move || {
+            let result = pool.get_timeout(duration).map(drop);
+            drop(permit);
+            result
+        }).await.map(|_| start.elapsed()).map_err(|e| e.to_string())
+    }
 }
 
 impl<K: 'static, C: Poolable> Connection<K, C> {
@@ -169,15 +325,33 @@ impl<K: 'static, C: Poolable> Connection<K, C> {
             f(conn)
         }).await
     }
+
+    /// Runs `f` on the connection on the current thread, blocking it. Used
+    /// where there is no runtime to run `f` on. Does nothing if the connection
+    /// is in use.
+    pub(crate) fn run_now<F: FnOnce(&mut C)>(&self, f: F) {
+        if let Ok(mut connection) = self.connection.try_lock() {
+            if let Some(conn) = connection.as_mut() {
+                f(conn);
+            }
+        }
+    }
 }
 
 impl<K, C: Poolable> Drop for Connection<K, C> {
     fn drop(&mut self) {
+        // Without a runtime, there's no async context to avoid blocking, so the
+        // connection and permit are simply dropped in place.
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            Err(_) => return,
+        };
+
         let connection = self.connection.clone();
         let permit = self.permit.take();
 
         // See same motivation above for this arrangement of spawn_blocking/block_on
-        tokio::task::spawn_blocking(move || {
+        handle.spawn_blocking(move || {
             let mut connection = tokio::runtime::Handle::current().block_on(async {
                 connection.lock_owned().await
             });
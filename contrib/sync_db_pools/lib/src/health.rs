@@ -0,0 +1,215 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rocket::{Rocket, Build, Phase, Request, Data, Route};
+use rocket::route::{Handler, Outcome};
+use rocket::http::{Method, Status};
+
+use crate::PoolStats;
+
+/// A database connection pool whose health can be checked.
+#[rocket::async_trait]
+pub(crate) trait Check: Send + Sync + 'static {
+    /// The name of the database.
+    fn name(&self) -> &'static str;
+
+    /// The current statistics of the pool.
+    fn stats(&self) -> PoolStats;
+
+    /// Retrieves and validates a connection from the pool, subject to the same
+    /// limit on connections in use as request guards, returning how long doing
+    /// so took.
+    async fn ping(&self) -> Result<Duration, String>;
+}
+
+/// The pools of the database fairings attached to a `Rocket`, in the order in
+/// which they were initialized.
+#[derive(Default)]
+pub(crate) struct Registry(Mutex<Vec<Arc<dyn Check>>>);
+
+impl Registry {
+    /// Registers `check` with the registry managed by `rocket`, managing one
+    /// if there is none.
+    pub(crate) fn register(rocket: Rocket<Build>, check: Arc<dyn Check>) -> Rocket<Build> {
+        let rocket = match rocket.state::<Registry>() {
+            Some(_) => rocket,
+            None => rocket.manage(Registry::default()),
+        };
+
+        let registry = rocket.state::<Registry>().expect("managed registry");
+        registry.0.lock().expect("unpoisoned registry").push(check);
+        rocket
+    }
+}
+
+/// The health of the databases of a `Rocket` instance.
+///
+/// [`Health::check()`] pings every database whose fairing has been attached by
+/// retrieving a connection from its pool, which the pool validates before
+/// handing it out. Like a request guard, a ping waits for a connection to be
+/// available, so a pool that remains exhausted for `timeout` seconds is
+/// reported as unhealthy. To serve the result, mount a [`HealthCheck`].
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::{Rocket, Orbit};
+/// use rocket_sync_db_pools::Health;
+///
+/// async fn report(rocket: &Rocket<Orbit>) {
+///     let health = Health::check(rocket).await;
+///     for db in &health.databases {
+///         if let Some(error) = &db.error {
+///             eprintln!("database `{}` is unhealthy: {}", db.name, error);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Health {
+    /// The health of each database, in the order in which their pools were
+    /// initialized.
+    pub databases: Vec<DatabaseHealth>,
+}
+
+/// The health of one database. See [`Health`].
+#[derive(Debug, Clone)]
+pub struct DatabaseHealth {
+    /// The name of the database, as passed to `#[database]`.
+    pub name: &'static str,
+    /// The reason the database is unhealthy, or `None` if it is healthy.
+    pub error: Option<String>,
+    /// How long retrieving a connection took, if it was retrieved.
+    pub latency: Option<Duration>,
+    /// The statistics of the database's pool after the check.
+    pub stats: PoolStats,
+}
+
+impl Health {
+    /// Pings every database whose fairing has been attached to `rocket`,
+    /// concurrently. Pools are initialized when `rocket` is ignited, so before
+    /// then, there are no databases to check.
+    pub async fn check<P: Phase>(rocket: &Rocket<P>) -> Health {
+        let checks = match rocket.state::<Registry>() {
+            Some(registry) => registry.0.lock().expect("unpoisoned registry").clone(),
+            None => vec![],
+        };
+
+        let pings = checks.iter().map(|check| async move {
+            let result = check.ping().await;
+            DatabaseHealth {
+                name: check.name(),
+                error: result.as_ref().err().cloned(),
+                latency: result.ok(),
+                stats: check.stats(),
+            }
+        });
+
+        Health { databases: rocket::futures::future::join_all(pings).await }
+    }
+
+    /// Returns `true` if every database is healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.databases.iter().all(|db| db.is_healthy())
+    }
+}
+
+impl DatabaseHealth {
+    /// Returns `true` if the database is healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl fmt::Display for Health {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for db in &self.databases {
+            match (&db.error, db.latency) {
+                (Some(e), _) => write!(f, "{}: error: {}", db.name, e)?,
+                (None, latency) => {
+                    write!(f, "{}: ok in {:?}", db.name, latency.unwrap_or_default())?
+                }
+            }
+
+            writeln!(f, " ({}/{} connections in use)", db.stats.in_use, db.stats.size)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A handler that reports the [`Health`] of the application's databases.
+///
+/// `HealthCheck` can be mounted as a readiness route. It handles `GET`
+/// requests to the mount point, checking every database and responding with
+/// `200 OK` if all are healthy and `503 Service Unavailable` otherwise. The
+/// body is a plain-text line per database.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[macro_use] extern crate rocket_sync_db_pools;
+/// # #[cfg(feature = "diesel_sqlite_pool")]
+/// # mod test {
+/// use rocket_sync_db_pools::{diesel, HealthCheck};
+///
+/// #[database("my_db")]
+/// struct MyDatabase(diesel::SqliteConnection);
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build()
+///         .attach(MyDatabase::fairing())
+///         .mount("/ready", HealthCheck::new())
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HealthCheck {
+    rank: isize,
+}
+
+impl HealthCheck {
+    /// The default rank used by `HealthCheck` routes.
+    const DEFAULT_RANK: isize = 10;
+
+    /// Creates a `HealthCheck` with the default rank of `10`.
+    pub fn new() -> Self {
+        HealthCheck { rank: Self::DEFAULT_RANK }
+    }
+
+    /// Sets the rank of the route emitted by `self` to `rank`.
+    pub fn rank(mut self, rank: isize) -> Self {
+        self.rank = rank;
+        self
+    }
+}
+
+impl Default for HealthCheck {
+    fn default() -> Self {
+        HealthCheck::new()
+    }
+}
+
+impl From<HealthCheck> for Vec<Route> {
+    fn from(check: HealthCheck) -> Self {
+        let mut route = Route::ranked(check.rank, Method::Get, "/", check);
+        route.name = Some("HealthCheck".into());
+        vec![route]
+    }
+}
+
+#[rocket::async_trait]
+impl Handler for HealthCheck {
+    async fn handle<'r>(&self, req: &'r Request<'_>, _: Data<'r>) -> Outcome<'r> {
+        let health = Health::check(req.rocket()).await;
+        let status = match health.is_healthy() {
+            true => Status::Ok,
+            false => Status::ServiceUnavailable,
+        };
+
+        Outcome::from(req, (status, health.to_string()))
+    }
+}
@@ -127,8 +127,84 @@
 //!
 //!   * `pool_size` - the size of the pool, i.e., the number of connections to
 //!     pool (defaults to the configured number of workers * 4)
+//!   * `timeout` - how long, in seconds, to wait for a connection before
+//!     timing out (defaults to `5`)
+//!   * `min_idle` - the minimum number of idle connections to maintain
+//!     (defaults to `pool_size`)
+//!   * `max_lifetime` - how long, in seconds, to keep a connection before
+//!     replacing it (defaults to 30 minutes)
+//!   * `idle_timeout` - how long, in seconds, to keep an idle connection
+//!     before closing it (defaults to 10 minutes)
+//!   * `migrations` - the directory of schema migrations to run at ignite,
+//!     `true` for `migrations`, or `false` to not run migrations (defaults to
+//!     `false`; see [Migrations](#migrations))
+//!
+//! Additional options may be required or supported by other adapters. The
+//! `rusqlite` adapter, for instance, reads `open_flags`, and the Postgres
+//! adapters set the `search_path` of every new connection to the value of
+//! `search_path`, if set, a comma-separated list of schema names. Each name is
+//! quoted as an identifier, so it's used exactly as written. Keys in the
+//! database's table other than those above are passed through to the adapter;
+//! see [`Config::extras()`].
+//!
+//! ### Migrations
+//!
+//! When `migrations` is configured, the database fairing runs any pending
+//! schema migrations in the directory before the application launches. A
+//! relative path is relative to the configuration file that sets it. If any
+//! migration fails, launch is aborted. Because `migrations` is regular
+//! configuration, migrations can be enabled for some profiles only:
 //!
-//! Additional options may be required or supported by other adapters.
+//! ```toml
+//! [default.databases.my_db]
+//! url = "db.sqlite"
+//! migrations = "db/migrations"
+//!
+//! [release.databases.my_db]
+//! migrations = false
+//! ```
+//!
+//! Migrations are laid out as [Diesel's]: each is a directory named
+//! `{version}_{name}`, such as `2021-06-01-000000_create_posts`, containing an
+//! `up.sql` file. The Diesel connection types run migrations with
+//! `diesel_migrations`. `rusqlite::Connection` and `postgres::Client` run the
+//! `up.sql` file of each pending migration, in order of version, within a
+//! transaction. Both record run migrations in Diesel's
+//! `__diesel_schema_migrations` table. Other [`Poolable`] types support
+//! migrations by implementing [`Poolable::migrate()`].
+//!
+//! [Diesel's]: https://diesel.rs/guides/getting-started
+//!
+//! ### Connection Hooks
+//!
+//! Settings that apply to a connection rather than to the database, such as
+//! SQLite pragmas or Postgres session parameters, are applied by hooks that
+//! run on every new connection. Hooks are registered on the fairing with
+//! [`PoolFairing::on_connect()`] and return the error type of the adapter's
+//! connection manager:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[macro_use] extern crate rocket_sync_db_pools;
+//! # #[cfg(feature = "diesel_sqlite_pool")] {
+//! use rocket_sync_db_pools::diesel::{self, connection::SimpleConnection};
+//!
+//! #[database("sqlite_db")]
+//! struct Db(diesel::SqliteConnection);
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build().attach(Db::fairing().on_connect(|conn| {
+//!         conn.batch_execute("PRAGMA cache_size = -16000;")
+//!             .map_err(diesel::r2d2::Error::QueryError)
+//!     }))
+//! }
+//! # }
+//! ```
+//!
+//! Connections of `diesel::SqliteConnection` pools are configured with
+//! `journal_mode = WAL`, `busy_timeout = 1000`, and `foreign_keys = ON` before
+//! hooks run, so hooks can override these pragmas.
 //!
 //! ### Procedurally
 //!
@@ -187,18 +263,24 @@
 //! retrieves a connection from the database pool or fails with a
 //! `Status::ServiceUnavailable` if connecting to the database times out.
 //!
-//! The macro also generates three inherent methods on the decorated type:
+//! The macro also generates four inherent methods on the decorated type:
 //!
-//!   * `fn fairing() -> impl Fairing`
+//!   * `fn fairing() -> PoolFairing<Self, Db>`
 //!
 //!      Returns a fairing that initializes the associated database connection
-//!      pool.
+//!      pool. Connection hooks are registered with
+//!      [`PoolFairing::on_connect()`].
 //!
 //!   * `async fn get_one<P: Phase>(&Rocket<P>) -> Option<Self>`
 //!
 //!     Retrieves a connection wrapper from the configured pool. Returns `Some`
 //!     as long as `Self::fairing()` has been attached.
 //!
+//!   * `fn pool_stats<P: Phase>(&Rocket<P>) -> Option<PoolStats>`
+//!
+//!     Returns the current statistics of the configured pool. Returns `Some`
+//!     as long as `Self::fairing()` has been attached and has run.
+//!
 //!   * `async fn run<R: Send + 'static>(&self, impl FnOnce(&mut Db) -> R + Send + 'static) -> R`
 //!
 //!     Runs the specified function or closure, providing it access to the
@@ -306,6 +388,82 @@
 //! # }
 //! ```
 //!
+//! ## Transactions
+//!
+//! To run all of a request's queries in one transaction, use the
+//! [`Transaction`] guard instead. The first `Transaction` guard of a request
+//! begins a transaction, which every other `Transaction` guard for the same
+//! database in the request shares, including those retrieved by custom guards.
+//! When the response is sent, the transaction is committed if the response
+//! status is below `400` and rolled back otherwise, including when the handler
+//! panics:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[macro_use] extern crate rocket_sync_db_pools;
+//! #
+//! # #[cfg(feature = "diesel_sqlite_pool")]
+//! # mod test {
+//! # use rocket_sync_db_pools::diesel;
+//! use rocket::http::Status;
+//! use rocket_sync_db_pools::Transaction;
+//!
+//! #[database("my_db")]
+//! struct MyDatabase(diesel::SqliteConnection);
+//!
+//! fn archive(conn: &diesel::SqliteConnection) -> diesel::QueryResult<()> {
+//!     // Move rows between several tables.
+//!     # Ok(())
+//! }
+//!
+//! #[post("/archive")]
+//! async fn my_handler(tx: Transaction<MyDatabase>) -> Status {
+//!     match tx.run(|c| archive(c)).await {
+//!         Ok(()) => Status::Ok,
+//!         Err(_) => Status::InternalServerError,
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! ## Testing
+//!
+//! Tests that share the configured database cannot run in parallel. Instead,
+//! [`isolate()`] configures a `Rocket` instance to use a fresh database for a
+//! `#[database]` type: a temporary SQLite file or a Postgres schema, migrated
+//! as configured and torn down when the instance, or the `local::Client` that
+//! owns it, is dropped.
+//!
+//! ## Monitoring
+//!
+//! The generated `pool_stats()` method returns the [`PoolStats`] of a database
+//! pool: its size, open, idle, and in-use connections, and counts of retrieved
+//! connections, timeouts, failures, and time spent waiting.
+//! [`Health::check()`] pings every attached database, and [`HealthCheck`]
+//! serves the result as a readiness route, responding with `503 Service
+//! Unavailable` when any database is unreachable:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! # #[macro_use] extern crate rocket_sync_db_pools;
+//! #
+//! # #[cfg(feature = "diesel_sqlite_pool")]
+//! # mod test {
+//! # use rocket_sync_db_pools::diesel;
+//! use rocket_sync_db_pools::HealthCheck;
+//!
+//! #[database("my_db")]
+//! struct MyDatabase(diesel::SqliteConnection);
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     rocket::build()
+//!         .attach(MyDatabase::fairing())
+//!         .mount("/ready", HealthCheck::new())
+//! }
+//! # }
+//! ```
+//!
 //! # Database Support
 //!
 //! Built-in support is provided for many popular databases and drivers. Support
@@ -385,10 +543,19 @@ mod poolable;
 mod config;
 mod error;
 mod connection;
+mod migrations;
+mod transaction;
+mod testing;
+mod stats;
+mod health;
 
-pub use self::poolable::{Poolable, PoolResult};
+pub use self::poolable::{Poolable, PoolResult, OnConnect};
 pub use self::config::Config;
 pub use self::error::Error;
+pub use self::transaction::{Database, Transaction};
+pub use self::testing::{isolate, Scratch};
+pub use self::stats::PoolStats;
+pub use self::health::{Health, DatabaseHealth, HealthCheck};
 
 pub use rocket_sync_db_pools_codegen::*;
 pub use self::connection::*;
@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+#[cfg(any(
+    feature = "diesel_sqlite_pool",
+    feature = "diesel_postgres_pool",
+    feature = "diesel_mysql_pool",
+    feature = "sqlite_pool",
+    feature = "postgres_pool"
+))]
+use std::{path::Path, error::Error};
+
+use rocket::{Rocket, Build};
+use rocket::figment::{self, providers::Serialized};
+use rocket::figment::value::magic::{Either, RelativePathBuf};
+
+use crate::Config;
+
+/// The directory migrations are run from when `migrations = true`.
+const DEFAULT_DIRECTORY: &str = "migrations";
+
+/// Returns the migrations directory configured for the database named
+/// `db_name` via `databases.<db_name>.migrations`, or `None` if migrations are
+/// disabled, as they are by default.
+pub(crate) fn directory(
+    db_name: &str,
+    rocket: &Rocket<Build>
+) -> Result<Option<PathBuf>, figment::Error> {
+    let migrations = Config::figment(db_name, rocket)
+        .join(Serialized::default("migrations", false))
+        .extract_inner::<Either<RelativePathBuf, bool>>("migrations")?;
+
+    Ok(match migrations {
+        Either::Left(path) => Some(path.relative()),
+        Either::Right(true) => Some(DEFAULT_DIRECTORY.into()),
+        Either::Right(false) => None,
+    })
+}
+
+/// Runs the pending Diesel migrations in `dir`, logging Diesel's output.
+#[cfg(any(
+    feature = "diesel_sqlite_pool",
+    feature = "diesel_postgres_pool",
+    feature = "diesel_mysql_pool"
+))]
+pub(crate) fn run_diesel_migrations<C>(conn: &C, dir: &Path) -> Result<(), Box<dyn Error>>
+    where C: diesel_migrations::MigrationConnection
+{
+    let mut output = vec![];
+    let result = diesel_migrations::run_pending_migrations_in_directory(conn, dir, &mut output);
+    for line in String::from_utf8_lossy(&output).lines() {
+        info_!("{}", line);
+    }
+
+    Ok(result?)
+}
+
+/// The table recording the versions of the migrations that have been run. It
+/// is shared with Diesel so that either can run the same migrations.
+#[cfg(any(feature = "sqlite_pool", feature = "postgres_pool"))]
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS __diesel_schema_migrations (
+    version VARCHAR(50) PRIMARY KEY NOT NULL,
+    run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);";
+
+/// A connection that can run plain SQL migrations via [`run_sql_migrations`].
+#[cfg(any(feature = "sqlite_pool", feature = "postgres_pool"))]
+pub(crate) trait SqlConnection {
+    type Error: Error + 'static;
+
+    /// Executes `sql`, which may contain several statements.
+    fn batch(&mut self, sql: &str) -> Result<(), Self::Error>;
+
+    /// Returns the versions of the migrations that have been run.
+    fn run_versions(&mut self) -> Result<Vec<String>, Self::Error>;
+
+    /// Runs the migration `sql` and records `version` as run, atomically.
+    fn run_migration(&mut self, version: &str, sql: &str) -> Result<(), Self::Error>;
+}
+
+/// Runs the pending migrations in `dir`, in order of version. Migrations are
+/// laid out as Diesel's: each is a directory named `{version}_{name}`
+/// containing an `up.sql` file. Only the `up.sql` files are used.
+#[cfg(any(feature = "sqlite_pool", feature = "postgres_pool"))]
+pub(crate) fn run_sql_migrations<C>(conn: &mut C, dir: &Path) -> Result<(), Box<dyn Error>>
+    where C: SqlConnection
+{
+    let migrations = sql_migrations(dir)
+        .map_err(|e| format!("failed to read migrations in '{}': {}", dir.display(), e))?;
+
+    conn.batch(CREATE_TABLE)?;
+    let run_versions = conn.run_versions()?;
+    for (version, name, path) in migrations {
+        if run_versions.contains(&version) {
+            continue;
+        }
+
+        info_!("Running migration {}", name);
+        let sql = std::fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read '{}': {}", path.display(), e))?;
+
+        conn.run_migration(&version, &sql)
+            .map_err(|e| format!("migration {} failed: {}", name, e))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the (version, name, path to `up.sql`) of every migration in `dir`,
+/// ordered by version. Hidden entries and files are ignored.
+#[cfg(any(feature = "sqlite_pool", feature = "postgres_pool"))]
+fn sql_migrations(dir: &Path) -> std::io::Result<Vec<(String, String, PathBuf)>> {
+    let mut migrations = vec![];
+    for entry in dir.read_dir()? {
+        let path = entry?.path();
+        let name = match path.file_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => continue,
+        };
+
+        if name.starts_with('.') || !path.is_dir() {
+            continue;
+        }
+
+        let up = path.join("up.sql");
+        if !up.is_file() {
+            let kind = std::io::ErrorKind::NotFound;
+            let msg = format!("migration {} is missing an `up.sql` file", name);
+            return Err(std::io::Error::new(kind, msg));
+        }
+
+        let version = name.split('_').next().unwrap_or_default().replace('-', "");
+        migrations.push((version, name, up));
+    }
+
+    migrations.sort();
+    Ok(migrations)
+}
+
+#[cfg(feature = "sqlite_pool")]
+impl SqlConnection for rusqlite::Connection {
+    type Error = rusqlite::Error;
+
+    fn batch(&mut self, sql: &str) -> Result<(), Self::Error> {
+        self.execute_batch(sql)
+    }
+
+    fn run_versions(&mut self) -> Result<Vec<String>, Self::Error> {
+        let mut stmt = self.prepare("SELECT version FROM __diesel_schema_migrations")?;
+        let versions = stmt.query_map(rusqlite::params![], |row| row.get(0))?.collect();
+        versions
+    }
+
+    fn run_migration(&mut self, version: &str, sql: &str) -> Result<(), Self::Error> {
+        let tx = self.transaction()?;
+        tx.execute_batch(sql)?;
+        tx.execute(
+            "INSERT INTO __diesel_schema_migrations (version) VALUES (?1)",
+            rusqlite::params![version]
+        )?;
+
+        tx.commit()
+    }
+}
+
+#[cfg(feature = "postgres_pool")]
+impl SqlConnection for postgres::Client {
+    type Error = postgres::Error;
+
+    fn batch(&mut self, sql: &str) -> Result<(), Self::Error> {
+        self.batch_execute(sql)
+    }
+
+    fn run_versions(&mut self) -> Result<Vec<String>, Self::Error> {
+        let rows = self.query("SELECT version FROM __diesel_schema_migrations", &[])?;
+        Ok(rows.iter().map(|row| row.get(0)).collect())
+    }
+
+    fn run_migration(&mut self, version: &str, sql: &str) -> Result<(), Self::Error> {
+        let mut tx = self.transaction()?;
+        tx.batch_execute(sql)?;
+        tx.execute(
+            "INSERT INTO __diesel_schema_migrations (version) VALUES ($1)",
+            &[&version]
+        )?;
+
+        tx.commit()
+    }
+}
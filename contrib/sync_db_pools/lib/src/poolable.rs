@@ -1,10 +1,14 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
-use r2d2::ManageConnection;
+use r2d2::{ManageConnection, CustomizeConnection};
 use rocket::{Rocket, Build};
+use rocket::figment::{self, value::Dict};
 
 #[allow(unused_imports)]
-use crate::{Config, Error};
+use crate::{Config, Error, Scratch};
 
 /// Trait implemented by `r2d2`-based database adapters.
 ///
@@ -67,18 +71,30 @@ use crate::{Config, Error};
 /// # }
 /// use std::time::Duration;
 /// use rocket::{Rocket, Build};
-/// use rocket_sync_db_pools::{r2d2, Error, Config, Poolable, PoolResult};
+/// use rocket::figment::value::Dict;
+/// use rocket_sync_db_pools::{r2d2, Error, Config, Poolable, PoolResult, OnConnect};
 ///
 /// impl Poolable for foo::Connection {
 ///     type Manager = foo::ConnectionManager;
 ///     type Error = foo::Error;
 ///
 ///     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
+///         let extras = Config::extras(db_name, rocket)?;
+///         Self::pool_with(db_name, rocket, &extras, OnConnect::default())
+///     }
+///
+///     fn pool_with(
+///         db_name: &str,
+///         rocket: &Rocket<Build>,
+///         _extras: &Dict,
+///         on_connect: OnConnect<Self>
+///     ) -> PoolResult<Self> {
 ///         let config = Config::from(db_name, rocket)?;
 ///         let manager = foo::ConnectionManager::new(&config.url).map_err(Error::Custom)?;
 ///         Ok(r2d2::Pool::builder()
 ///             .max_size(config.pool_size)
 ///             .connection_timeout(Duration::from_secs(config.timeout as u64))
+///             .connection_customizer(Box::new(on_connect))
 ///             .build(manager)?)
 ///     }
 /// }
@@ -88,7 +104,8 @@ use crate::{Config, Error};
 /// failure. The [`Error`] enum consolidates this type, the `r2d2::Error` type
 /// that can result from `r2d2::Pool::builder()`, and the
 /// [`figment::Error`](rocket::figment::Error) type from
-/// `database::Config::from()`.
+/// `database::Config::from()`. Implementing [`Poolable::pool_with()`] enables
+/// `on_connect` hooks and configuration keys beyond those in [`Config`].
 ///
 /// In the event that a connection manager isn't fallible (as is the case with
 /// Diesel's r2d2 connection manager, for instance), the associated error type
@@ -107,23 +124,286 @@ pub trait Poolable: Send + Sized + 'static {
     /// Creates an `r2d2` connection pool for `Manager::Connection`, returning
     /// the pool on success.
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self>;
+
+    /// Creates an `r2d2` connection pool like [`Poolable::pool()`] that
+    /// additionally runs the hooks in `on_connect` on every new connection.
+    /// `extras` are the configuration keys for the database that aren't in
+    /// [`Config`], as returned by [`Config::extras()`]. This is the method
+    /// called by the database fairing.
+    ///
+    /// The default implementation calls [`Poolable::pool()`] if there are no
+    /// hooks and otherwise returns an error: hooks are not supported. All of
+    /// the provided implementations support hooks and apply the `min_idle`,
+    /// `max_lifetime`, and `idle_timeout` pool options in `extras`. The
+    /// Postgres implementations also apply `search_path`.
+    fn pool_with(
+        db_name: &str,
+        rocket: &Rocket<Build>,
+        extras: &Dict,
+        on_connect: OnConnect<Self>
+    ) -> PoolResult<Self> {
+        let _ = extras;
+        if !on_connect.is_empty() {
+            let msg = "`on_connect` hooks are not supported by this database type";
+            return Err(Error::Config(msg.into()));
+        }
+
+        Self::pool(db_name, rocket)
+    }
+
+    /// Runs the pending schema migrations in the directory `dir` using this
+    /// connection. Called once at ignite, after the pool is created, when
+    /// migrations are enabled via `databases.<name>.migrations`. An error
+    /// aborts launch.
+    ///
+    /// The default implementation returns an error: migrations are not
+    /// supported. The Diesel connections run Diesel migrations via
+    /// `diesel_migrations`. `rusqlite::Connection` and `postgres::Client` run
+    /// the `up.sql` files of migrations in the same layout.
+    fn migrate(&mut self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let _ = dir;
+        Err("migrations are not supported by this database type".into())
+    }
+
+    /// Begins a transaction on this connection. Called by the first
+    /// [`Transaction`](crate::Transaction) guard of a request.
+    ///
+    /// The default implementation returns an error: transactions are not
+    /// supported. The provided implementations, except for
+    /// `memcache::Client`, support transactions.
+    fn begin_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Err("transactions are not supported by this database type".into())
+    }
+
+    /// Commits the transaction begun by [`Poolable::begin_transaction()`].
+    ///
+    /// The default implementation returns an error: transactions are not
+    /// supported.
+    fn commit_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Err("transactions are not supported by this database type".into())
+    }
+
+    /// Rolls back the transaction begun by [`Poolable::begin_transaction()`].
+    ///
+    /// The default implementation returns an error: transactions are not
+    /// supported.
+    fn rollback_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Err("transactions are not supported by this database type".into())
+    }
+
+    /// Provisions a fresh database, isolated from the one configured for the
+    /// database named `db_name` and from other scratch databases, for
+    /// [`isolate()`](crate::isolate()). The returned [`Scratch`] tears the
+    /// database down when dropped.
+    ///
+    /// The default implementation returns an error: scratch databases are not
+    /// supported. The SQLite and Postgres connection types support them.
+    fn scratch(
+        db_name: &str,
+        rocket: &Rocket<Build>
+    ) -> Result<Scratch, Box<dyn std::error::Error>> {
+        let _ = (db_name, rocket);
+        Err("scratch databases are not supported by this database type".into())
+    }
 }
 
 /// A type alias for the return type of [`Poolable::pool()`].
 #[allow(type_alias_bounds)]
 pub type PoolResult<P: Poolable> = Result<r2d2::Pool<P::Manager>, Error<P::Error>>;
 
+/// Creates a pool via [`Poolable::pool_with()`] without any hooks. Used by the
+/// provided implementations of [`Poolable::pool()`].
+#[allow(dead_code)]
+fn pool<C: Poolable>(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<C> {
+    let extras = Config::extras(db_name, rocket)?;
+    C::pool_with(db_name, rocket, &extras, OnConnect::default())
+}
+
+/// Builds a pool of connections from `manager`, sized by `config` and
+/// configured by the pool options in `extras`, that runs `customizer` on every
+/// new connection. Used by the provided implementations of
+/// [`Poolable::pool_with()`].
+#[allow(dead_code)]
+fn build_pool<C: Poolable>(
+    config: &Config,
+    extras: &Dict,
+    manager: C::Manager,
+    customizer: Box<dyn CustomizeConnection<C, ManagerError<C>>>,
+) -> PoolResult<C> {
+    let secs = |key| Ok::<_, figment::Error>(extra::<u64>(extras, key)?.map(Duration::from_secs));
+
+    let mut builder = r2d2::Pool::builder()
+        .max_size(config.pool_size)
+        .connection_timeout(Duration::from_secs(config.timeout as u64))
+        .connection_customizer(customizer);
+
+    if let Some(min_idle) = extra::<u32>(extras, "min_idle")? {
+        builder = builder.min_idle(Some(min_idle));
+    }
+
+    if let Some(max_lifetime) = secs("max_lifetime")? {
+        builder = builder.max_lifetime(Some(max_lifetime));
+    }
+
+    if let Some(idle_timeout) = secs("idle_timeout")? {
+        builder = builder.idle_timeout(Some(idle_timeout));
+    }
+
+    Ok(builder.build(manager)?)
+}
+
+/// Returns `on_connect` with a hook, run first, that sets the `search_path` of
+/// new connections to the value of the `search_path` option in `extras`, if
+/// there is one: a comma-separated list of schema names, each quoted as an
+/// identifier. `execute` executes SQL on a connection. Used by the Postgres
+/// implementations of [`Poolable::pool_with()`].
+#[cfg(any(feature = "diesel_postgres_pool", feature = "postgres_pool"))]
+fn search_path<C: Poolable>(
+    extras: &Dict,
+    on_connect: OnConnect<C>,
+    execute: fn(&mut C, &str) -> Result<(), ManagerError<C>>,
+) -> Result<OnConnect<C>, figment::Error> {
+    Ok(match extra::<String>(extras, "search_path")? {
+        Some(path) => {
+            let schemas = path.split(',')
+                .map(|schema| format!("\"{}\"", schema.trim().replace('"', "\"\"")))
+                .collect::<Vec<_>>();
+
+            let sql = format!("SET search_path TO {}", schemas.join(", "));
+            on_connect.before(move |conn| execute(conn, &sql))
+        }
+        None => on_connect,
+    })
+}
+
+/// Deserializes the value of `key` in `extras`, if there is one.
+#[allow(dead_code)]
+fn extra<T>(extras: &Dict, key: &str) -> Result<Option<T>, figment::Error>
+    where T: for<'de> serde::Deserialize<'de>
+{
+    extras.get(key)
+        .map(|value| value.deserialize().map_err(|e| e.with_path(key)))
+        .transpose()
+}
+
+/// The error type of the connection manager for `C`, returned by hooks.
+#[allow(type_alias_bounds)]
+type ManagerError<C: Poolable> = <C::Manager as ManageConnection>::Error;
+
+type Hook<C> = Arc<dyn Fn(&mut C) -> Result<(), ManagerError<C>> + Send + Sync>;
+
+/// Hooks run, in order, on every new connection of type `C`.
+///
+/// Hooks are registered with
+/// [`PoolFairing::on_connect()`](crate::PoolFairing::on_connect()) and passed
+/// to [`Poolable::pool_with()`]. `OnConnect` implements
+/// [`r2d2::CustomizeConnection`], so a pool runs the hooks when it is built
+/// with `OnConnect` as its [connection customizer]. A connection for which a
+/// hook fails is discarded.
+///
+/// [connection customizer]: r2d2::Builder::connection_customizer()
+pub struct OnConnect<C: Poolable> {
+    hooks: Vec<Hook<C>>,
+}
+
+impl<C: Poolable> OnConnect<C> {
+    pub(crate) fn push<F>(&mut self, hook: F)
+        where F: Fn(&mut C) -> Result<(), ManagerError<C>> + Send + Sync + 'static
+    {
+        self.hooks.push(Arc::new(hook));
+    }
+
+    /// Returns `self` with `hook` run before the other hooks.
+    #[cfg(any(feature = "diesel_postgres_pool", feature = "postgres_pool"))]
+    pub(crate) fn before<F>(mut self, hook: F) -> Self
+        where F: Fn(&mut C) -> Result<(), ManagerError<C>> + Send + Sync + 'static
+    {
+        self.hooks.insert(0, Arc::new(hook));
+        self
+    }
+
+    /// Returns `true` if there are no hooks.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Runs the hooks on `conn` in order, stopping at the first error.
+    pub fn run(&self, conn: &mut C) -> Result<(), ManagerError<C>> {
+        self.hooks.iter().try_for_each(|hook| hook(conn))
+    }
+}
+
+impl<C: Poolable> Default for OnConnect<C> {
+    fn default() -> Self {
+        OnConnect { hooks: vec![] }
+    }
+}
+
+impl<C: Poolable> Clone for OnConnect<C> {
+    fn clone(&self) -> Self {
+        OnConnect { hooks: self.hooks.clone() }
+    }
+}
+
+impl<C: Poolable> fmt::Debug for OnConnect<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnConnect").field("hooks", &self.hooks.len()).finish()
+    }
+}
+
+impl<C: Poolable> CustomizeConnection<C, ManagerError<C>> for OnConnect<C> {
+    fn on_acquire(&self, conn: &mut C) -> Result<(), ManagerError<C>> {
+        self.run(conn)
+    }
+}
+
+/// Implements the transaction methods of `Poolable` for a Diesel connection
+/// via its `TransactionManager`.
+#[cfg(any(
+    feature = "diesel_sqlite_pool",
+    feature = "diesel_postgres_pool",
+    feature = "diesel_mysql_pool"
+))]
+macro_rules! diesel_transactions {
+    () => {
+        fn begin_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+            use diesel::connection::{Connection, TransactionManager};
+            Ok(self.transaction_manager().begin_transaction(self)?)
+        }
+
+        fn commit_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+            use diesel::connection::{Connection, TransactionManager};
+            Ok(self.transaction_manager().commit_transaction(self)?)
+        }
+
+        fn rollback_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+            use diesel::connection::{Connection, TransactionManager};
+            Ok(self.transaction_manager().rollback_transaction(self)?)
+        }
+    };
+}
+
 #[cfg(feature = "diesel_sqlite_pool")]
 impl Poolable for diesel::SqliteConnection {
     type Manager = diesel::r2d2::ConnectionManager<diesel::SqliteConnection>;
     type Error = std::convert::Infallible;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
+        pool::<Self>(db_name, rocket)
+    }
+
+    fn pool_with(
+        db_name: &str,
+        rocket: &Rocket<Build>,
+        extras: &Dict,
+        on_connect: OnConnect<Self>
+    ) -> PoolResult<Self> {
         use diesel::{SqliteConnection, connection::SimpleConnection};
-        use diesel::r2d2::{CustomizeConnection, ConnectionManager, Error, Pool};
+        use diesel::r2d2::{CustomizeConnection, ConnectionManager, Error};
 
+        // Runs after the default pragmas so that hooks can override them.
         #[derive(Debug)]
-        struct Customizer;
+        struct Customizer(OnConnect<SqliteConnection>);
 
         impl CustomizeConnection<SqliteConnection, Error> for Customizer {
             fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), Error> {
@@ -133,19 +413,23 @@ impl Poolable for diesel::SqliteConnection {
                     PRAGMA foreign_keys = ON;\
                 ").map_err(Error::QueryError)?;
 
-                Ok(())
+                self.0.run(conn)
             }
         }
 
         let config = Config::from(db_name, rocket)?;
         let manager = ConnectionManager::new(&config.url);
-        let pool = Pool::builder()
-            .connection_customizer(Box::new(Customizer))
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
+        build_pool(&config, extras, manager, Box::new(Customizer(on_connect)))
+    }
+
+    fn migrate(&mut self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        crate::migrations::run_diesel_migrations(self, dir)
+    }
+
+    diesel_transactions!();
 
-        Ok(pool)
+    fn scratch(db_name: &str, _: &Rocket<Build>) -> Result<Scratch, Box<dyn std::error::Error>> {
+        crate::testing::sqlite_scratch(db_name)
     }
 }
 
@@ -155,14 +439,42 @@ impl Poolable for diesel::PgConnection {
     type Error = std::convert::Infallible;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
+        pool::<Self>(db_name, rocket)
+    }
+
+    fn pool_with(
+        db_name: &str,
+        rocket: &Rocket<Build>,
+        extras: &Dict,
+        on_connect: OnConnect<Self>
+    ) -> PoolResult<Self> {
+        use diesel::{PgConnection, connection::SimpleConnection, r2d2::Error};
+
         let config = Config::from(db_name, rocket)?;
         let manager = diesel::r2d2::ConnectionManager::new(&config.url);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
+        let on_connect = search_path(extras, on_connect, |conn: &mut PgConnection, sql| {
+            conn.batch_execute(sql).map_err(Error::QueryError)
+        })?;
+
+        build_pool(&config, extras, manager, Box::new(on_connect))
+    }
 
-        Ok(pool)
+    fn migrate(&mut self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        crate::migrations::run_diesel_migrations(self, dir)
+    }
+
+    diesel_transactions!();
+
+    fn scratch(
+        db_name: &str,
+        rocket: &Rocket<Build>
+    ) -> Result<Scratch, Box<dyn std::error::Error>> {
+        use diesel::{Connection, connection::SimpleConnection};
+
+        crate::testing::postgres_scratch(db_name, rocket, |url, sql| {
+            let conn = diesel::PgConnection::establish(url).map_err(|e| e.to_string())?;
+            conn.batch_execute(sql).map_err(|e| e.to_string())
+        })
     }
 }
 
@@ -172,15 +484,25 @@ impl Poolable for diesel::MysqlConnection {
     type Error = std::convert::Infallible;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
+        pool::<Self>(db_name, rocket)
+    }
+
+    fn pool_with(
+        db_name: &str,
+        rocket: &Rocket<Build>,
+        extras: &Dict,
+        on_connect: OnConnect<Self>
+    ) -> PoolResult<Self> {
         let config = Config::from(db_name, rocket)?;
         let manager = diesel::r2d2::ConnectionManager::new(&config.url);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
+        build_pool(&config, extras, manager, Box::new(on_connect))
+    }
 
-        Ok(pool)
+    fn migrate(&mut self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        crate::migrations::run_diesel_migrations(self, dir)
     }
+
+    diesel_transactions!();
 }
 
 // TODO: Add a feature to enable TLS in `postgres`; parse a suitable `config`.
@@ -190,15 +512,51 @@ impl Poolable for postgres::Client {
     type Error = postgres::Error;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
+        pool::<Self>(db_name, rocket)
+    }
+
+    fn pool_with(
+        db_name: &str,
+        rocket: &Rocket<Build>,
+        extras: &Dict,
+        on_connect: OnConnect<Self>
+    ) -> PoolResult<Self> {
         let config = Config::from(db_name, rocket)?;
         let url = config.url.parse().map_err(Error::Custom)?;
         let manager = r2d2_postgres::PostgresConnectionManager::new(url, postgres::tls::NoTls);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
+        let on_connect = search_path(extras, on_connect, |conn: &mut Self, sql| {
+            conn.batch_execute(sql)
+        })?;
+
+        build_pool(&config, extras, manager, Box::new(on_connect))
+    }
+
+    fn migrate(&mut self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        crate::migrations::run_sql_migrations(self, dir)
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.batch_execute("BEGIN")?)
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.batch_execute("COMMIT")?)
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.batch_execute("ROLLBACK")?)
+    }
+
+    fn scratch(
+        db_name: &str,
+        rocket: &Rocket<Build>
+    ) -> Result<Scratch, Box<dyn std::error::Error>> {
+        crate::testing::postgres_scratch(db_name, rocket, |url, sql| {
+            let mut client = postgres::Client::connect(url, postgres::tls::NoTls)
+                .map_err(|e| e.to_string())?;
 
-        Ok(pool)
+            client.batch_execute(sql).map_err(|e| e.to_string())
+        })
     }
 }
 
@@ -208,8 +566,15 @@ impl Poolable for rusqlite::Connection {
     type Error = std::convert::Infallible;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
-        use rocket::figment::providers::Serialized;
+        pool::<Self>(db_name, rocket)
+    }
 
+    fn pool_with(
+        db_name: &str,
+        rocket: &Rocket<Build>,
+        extras: &Dict,
+        on_connect: OnConnect<Self>
+    ) -> PoolResult<Self> {
         #[derive(Debug, serde::Deserialize, serde::Serialize)]
         #[serde(rename_all = "snake_case")]
         enum OpenFlag {
@@ -225,11 +590,8 @@ impl Poolable for rusqlite::Connection {
             Nofollow,
         }
 
-        let figment = Config::figment(db_name, rocket);
-        let config: Config = figment.extract()?;
-        let open_flags: Vec<OpenFlag> = figment
-            .join(Serialized::default("open_flags", <Vec<OpenFlag>>::new()))
-            .extract_inner("open_flags")?;
+        let config = Config::from(db_name, rocket)?;
+        let open_flags: Vec<OpenFlag> = extra(extras, "open_flags")?.unwrap_or_default();
 
         let mut flags = rusqlite::OpenFlags::default();
         for flag in open_flags {
@@ -252,12 +614,27 @@ impl Poolable for rusqlite::Connection {
         let manager = r2d2_sqlite::SqliteConnectionManager::file(&*config.url)
             .with_flags(flags);
 
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
+        build_pool(&config, extras, manager, Box::new(on_connect))
+    }
+
+    fn migrate(&mut self, dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        crate::migrations::run_sql_migrations(self, dir)
+    }
+
+    fn begin_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.execute_batch("BEGIN")?)
+    }
+
+    fn commit_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.execute_batch("COMMIT")?)
+    }
+
+    fn rollback_transaction(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(self.execute_batch("ROLLBACK")?)
+    }
 
-        Ok(pool)
+    fn scratch(db_name: &str, _: &Rocket<Build>) -> Result<Scratch, Box<dyn std::error::Error>> {
+        crate::testing::sqlite_scratch(db_name)
     }
 }
 
@@ -268,13 +645,17 @@ impl Poolable for memcache::Client {
     type Error = memcache::MemcacheError;
 
     fn pool(db_name: &str, rocket: &Rocket<Build>) -> PoolResult<Self> {
+        pool::<Self>(db_name, rocket)
+    }
+
+    fn pool_with(
+        db_name: &str,
+        rocket: &Rocket<Build>,
+        extras: &Dict,
+        on_connect: OnConnect<Self>
+    ) -> PoolResult<Self> {
         let config = Config::from(db_name, rocket)?;
         let manager = r2d2_memcache::MemcacheConnectionManager::new(&*config.url);
-        let pool = r2d2::Pool::builder()
-            .max_size(config.pool_size)
-            .connection_timeout(Duration::from_secs(config.timeout as u64))
-            .build(manager)?;
-
-        Ok(pool)
+        build_pool(&config, extras, manager, Box::new(on_connect))
     }
 }
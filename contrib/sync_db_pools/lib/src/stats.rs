@@ -0,0 +1,101 @@
+use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Statistics about a database connection pool.
+///
+/// Retrieved via the `pool_stats()` method generated by `#[database]`. The
+/// counters accumulate from the time the pool is initialized and only account
+/// for connections retrieved by request guards and `get_one()`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket_sync_db_pools;
+/// # #[cfg(feature = "diesel_sqlite_pool")]
+/// # mod test {
+/// use rocket::{Rocket, Orbit};
+/// use rocket_sync_db_pools::diesel;
+///
+/// #[database("my_db")]
+/// struct MyDatabase(diesel::SqliteConnection);
+///
+/// fn log_saturation(rocket: &Rocket<Orbit>) {
+///     if let Some(stats) = MyDatabase::pool_stats(rocket) {
+///         println!("{}/{} connections in use", stats.in_use, stats.size);
+///         println!("average wait: {:?}", stats.average_wait());
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The maximum number of connections in the pool, `pool_size`.
+    pub size: u32,
+    /// The number of connections currently open, in use or idle.
+    pub connections: u32,
+    /// The number of open connections that are idle.
+    pub idle: u32,
+    /// The number of open connections that are in use.
+    pub in_use: u32,
+    /// The number of connections retrieved from the pool.
+    pub acquired: u64,
+    /// The number of attempts to retrieve a connection that timed out waiting
+    /// for a connection to be returned to the pool.
+    pub timeouts: u64,
+    /// The number of attempts to retrieve a connection that failed because a
+    /// new connection could not be established.
+    pub failures: u64,
+    /// The total time spent waiting to retrieve connections, including waits
+    /// that timed out or failed.
+    pub wait_time: Duration,
+}
+
+impl PoolStats {
+    /// The average time spent waiting to retrieve a connection, including
+    /// waits that timed out or failed, or zero if there were no attempts.
+    pub fn average_wait(&self) -> Duration {
+        match self.acquired + self.timeouts + self.failures {
+            0 => Duration::from_secs(0),
+            attempts => Duration::from_nanos((self.wait_time.as_nanos() / attempts as u128) as u64),
+        }
+    }
+}
+
+/// The counters of a pool, shared by its clones.
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    acquired: AtomicU64,
+    timeouts: AtomicU64,
+    failures: AtomicU64,
+    wait_nanos: AtomicU64,
+}
+
+impl Metrics {
+    pub(crate) fn acquired(&self, waited: Duration) {
+        self.acquired.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos.fetch_add(waited.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn timed_out(&self, waited: Duration) {
+        self.timeouts.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos.fetch_add(waited.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn failed(&self, waited: Duration) {
+        self.failures.fetch_add(1, Ordering::Relaxed);
+        self.wait_nanos.fetch_add(waited.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn stats(&self, size: u32, state: r2d2::State) -> PoolStats {
+        PoolStats {
+            size,
+            connections: state.connections,
+            idle: state.idle_connections,
+            in_use: state.connections.saturating_sub(state.idle_connections),
+            acquired: self.acquired.load(Ordering::Relaxed),
+            timeouts: self.timeouts.load(Ordering::Relaxed),
+            failures: self.failures.load(Ordering::Relaxed),
+            wait_time: Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+use rocket::{Rocket, Build};
+use rocket::figment::value::{Dict, Value};
+
+use crate::{Database, Poolable};
+
+type Teardown = Box<dyn FnOnce() + Send>;
+
+/// A throwaway database, torn down when dropped.
+///
+/// A `Scratch` is created by [`Poolable::scratch()`] for [`isolate()`]. It
+/// records the URL used to connect to the database, any other configuration
+/// options needed to use it, and, optionally, a function that tears the
+/// database down. The function may block: when the `Scratch` is dropped within
+/// a runtime, it is called on the runtime's blocking thread pool.
+pub struct Scratch {
+    url: String,
+    options: Dict,
+    teardown: Mutex<Option<Teardown>>,
+}
+
+impl Scratch {
+    /// Creates a `Scratch` for the database at `url` with no other options and
+    /// no teardown.
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Scratch { url: url.into(), options: Dict::new(), teardown: Mutex::new(None) }
+    }
+
+    /// Sets the configuration option `key` to `value` for connections to the
+    /// database, in addition to `url`.
+    pub fn option<V: Into<Value>>(mut self, key: &str, value: V) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the function called to tear down the database when `self` is
+    /// dropped.
+    pub fn on_drop<F: FnOnce() + Send + 'static>(self, teardown: F) -> Self {
+        *self.teardown.lock().expect("unpoisoned") = Some(Box::new(teardown));
+        self
+    }
+
+    /// The URL used to connect to the database.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let teardown = match self.teardown.get_mut() {
+            Ok(teardown) => teardown.take(),
+            Err(poisoned) => poisoned.into_inner().take(),
+        };
+
+        // Teardowns may block, and drops may happen in async contexts, where
+        // some drivers refuse to block. Without a runtime, there's no async
+        // context, so the teardown runs on this thread.
+        if let Some(teardown) = teardown {
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn_blocking(teardown);
+                }
+                Err(_) => teardown(),
+            }
+        }
+    }
+}
+
+/// The scratch database of `D`, managed so that it is dropped with `Rocket`.
+struct Isolated<D> {
+    _scratch: Scratch,
+    _marker: PhantomData<fn() -> D>,
+}
+
+/// Configures `rocket` to connect the `#[database]` type `D` to a fresh,
+/// isolated database, torn down when `rocket`, or the `local::Client` it is
+/// moved into, is dropped.
+///
+/// Only `databases.<name>.url` and any options the database needs are
+/// replaced: other options, including `migrations`, still apply, so the
+/// database is migrated when `D::fairing()` initializes its pool. Because
+/// each call provisions a new database, tests using `isolate()` can run in
+/// parallel.
+///
+/// The database is provisioned by [`Poolable::scratch()`]:
+///
+///   * The SQLite connection types create a database in a new temporary file,
+///     deleted on teardown. An in-memory database is not used as each pooled
+///     connection would see a different one.
+///   * The Postgres connection types create a new schema in the configured
+///     database and set it as the `search_path` option, so that each new
+///     connection runs `SET search_path`. The schema and everything in it is
+///     dropped on teardown.
+///
+/// Other types return an error.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[macro_use] extern crate rocket_sync_db_pools;
+/// # #[cfg(feature = "diesel_sqlite_pool")]
+/// # mod test {
+/// use rocket::{Rocket, Build};
+/// use rocket::local::blocking::Client;
+/// use rocket_sync_db_pools::{diesel, isolate};
+///
+/// #[database("my_db")]
+/// struct MyDatabase(diesel::SqliteConnection);
+///
+/// fn rocket() -> Rocket<Build> {
+///     rocket::build().attach(MyDatabase::fairing())
+/// }
+///
+/// // Called by each test that needs a database.
+/// fn client() -> Client {
+///     let rocket = isolate::<MyDatabase>(rocket()).expect("scratch database");
+///     Client::tracked(rocket).expect("valid rocket")
+/// }
+/// # }
+/// ```
+pub fn isolate<D: Database>(rocket: Rocket<Build>) -> Result<Rocket<Build>, Box<dyn Error>> {
+    let scratch = D::Connection::scratch(D::NAME, &rocket)?;
+    let mut options = scratch.options.clone();
+    options.insert("url".into(), scratch.url().into());
+
+    let figment = rocket.figment().clone().merge((format!("databases.{}", D::NAME), options));
+    Ok(rocket.configure(figment).manage(Isolated::<D> { _scratch: scratch, _marker: PhantomData }))
+}
+
+/// Returns a name for a scratch database for the database named `db_name`,
+/// unique to this process and call.
+#[cfg(any(
+    feature = "diesel_sqlite_pool",
+    feature = "sqlite_pool",
+    feature = "diesel_postgres_pool",
+    feature = "postgres_pool"
+))]
+fn scratch_name(db_name: &str) -> String {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let db_name: String = db_name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("rocket_test_{}_{}_{}", db_name, std::process::id(), count)
+}
+
+/// Creates a scratch SQLite database in a new temporary file.
+#[cfg(any(feature = "diesel_sqlite_pool", feature = "sqlite_pool"))]
+pub(crate) fn sqlite_scratch(db_name: &str) -> Result<Scratch, Box<dyn Error>> {
+    let path = std::env::temp_dir().join(format!("{}.sqlite", scratch_name(db_name)));
+    let url = path.to_str().ok_or("temporary directory path is not UTF-8")?.to_string();
+    let _ = std::fs::remove_file(&path);
+
+    Ok(Scratch::new(url.clone()).on_drop(move || {
+        for suffix in &["", "-wal", "-shm", "-journal"] {
+            let _ = std::fs::remove_file(format!("{}{}", url, suffix));
+        }
+    }))
+}
+
+/// Creates a scratch schema in the configured Postgres database. `execute`
+/// connects to the database at the given URL and executes the given SQL.
+#[cfg(any(feature = "diesel_postgres_pool", feature = "postgres_pool"))]
+pub(crate) fn postgres_scratch<F>(
+    db_name: &str,
+    rocket: &Rocket<Build>,
+    execute: F
+) -> Result<Scratch, Box<dyn Error>>
+    where F: Fn(&str, &str) -> Result<(), String> + Send + Clone + 'static
+{
+    // Drivers may refuse to block in async contexts, so run on a new thread.
+    fn blocking<F>(execute: F, url: String, sql: String) -> Result<(), String>
+        where F: Fn(&str, &str) -> Result<(), String> + Send + 'static
+    {
+        std::thread::spawn(move || execute(&url, &sql))
+            .join()
+            .unwrap_or_else(|_| Err("scratch database thread panicked".into()))
+    }
+
+    let config = crate::Config::from(db_name, rocket)?;
+    let schema = scratch_name(db_name);
+    let create = format!("CREATE SCHEMA {}", schema);
+    blocking(execute.clone(), config.url.clone(), create)?;
+
+    let scratch = Scratch::new(config.url.clone()).option("search_path", schema.clone());
+    Ok(scratch.on_drop(move || {
+        let drop = format!("DROP SCHEMA {} CASCADE", schema);
+        if let Err(e) = execute(&config.url, &drop) {
+            warn_!("failed to drop scratch schema '{}': {}", schema, e);
+        }
+    }))
+}
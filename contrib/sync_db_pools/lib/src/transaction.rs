@@ -0,0 +1,211 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rocket::{Rocket, Ignite, Sentinel};
+use rocket::http::Status;
+use rocket::request::{Request, Outcome, FromRequest};
+use rocket::response::Response;
+
+use crate::{Poolable, Connection, ConnectionPool};
+
+/// Trait implemented by types decorated with `#[database]`, naming the type of
+/// their connections.
+pub trait Database: 'static {
+    /// The connection type, the type inside the decorated structure.
+    type Connection: Poolable;
+
+    /// The name of the database, as passed to `#[database]`.
+    const NAME: &'static str;
+}
+
+/// Request guard for a database transaction spanning a request.
+///
+/// The first `Transaction<D>` guard of a request retrieves a connection from
+/// the pool of the `#[database]` type `D` and begins a transaction on it. Every
+/// other `Transaction<D>` guard in the same request, including those retrieved
+/// by other guards, shares the connection and the transaction. Requests that
+/// do not use the guard do not begin a transaction.
+///
+/// The transaction is finished by `D::fairing()` when the response is sent: it
+/// is committed if the response status is not an error, that is, it is below
+/// `400`, and rolled back otherwise. Since Rocket responds with a `500` to a
+/// request whose handler panics, the transaction is also rolled back on
+/// panics. If the commit fails, the response is replaced with an empty `500`.
+///
+/// The guard fails with `Status::ServiceUnavailable` if connecting to the
+/// database times out or the transaction cannot be begun. Transactions are
+/// supported by the Diesel, `rusqlite`, and `postgres` connection types and by
+/// [`Poolable`] types that implement [`Poolable::begin_transaction()`].
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// # #[macro_use] extern crate rocket_sync_db_pools;
+/// # #[cfg(feature = "diesel_sqlite_pool")]
+/// # mod test {
+/// use rocket::http::Status;
+/// use rocket_sync_db_pools::{diesel, Transaction};
+/// use diesel::RunQueryDsl;
+///
+/// #[database("my_db")]
+/// struct MyDatabase(diesel::SqliteConnection);
+///
+/// #[post("/transfer")]
+/// async fn transfer(tx: Transaction<MyDatabase>) -> Status {
+///     let debited = tx.run(|c| {
+///         diesel::sql_query("UPDATE accounts SET balance = balance - 10 WHERE id = 1")
+///             .execute(c)
+///     }).await;
+///
+///     if debited != Ok(1) {
+///         // The update above is rolled back.
+///         return Status::BadRequest;
+///     }
+///
+///     // ...
+///     Status::Ok
+/// }
+/// # }
+/// ```
+pub struct Transaction<D: Database> {
+    inner: Arc<TransactionInner<D, D::Connection>>,
+}
+
+struct TransactionInner<K: 'static, C: Poolable> {
+    // This is an 'Option' so that it can be moved out in 'Drop'.
+    connection: Option<Connection<K, C>>,
+    active: AtomicBool,
+}
+
+/// The transaction of a request, cached in request-local state.
+struct TransactionSlot<K: 'static, C: Poolable>(Option<Arc<TransactionInner<K, C>>>);
+
+impl<D: Database> Transaction<D> {
+    /// Runs the provided closure on a thread from a threadpool, passing it the
+    /// connection on which the transaction was begun. `.await`ing the return
+    /// value of this function yields the value returned by the closure.
+    #[inline]
+    pub async fn run<F, R>(&self, f: F) -> R
+        where F: FnOnce(&mut D::Connection) -> R + Send + 'static,
+              R: Send + 'static,
+    {
+        self.inner.connection().run(f).await
+    }
+}
+
+impl<K: 'static, C: Poolable> TransactionInner<K, C> {
+    async fn begin(pool: &ConnectionPool<K, C>) -> Option<Self> {
+        let connection = pool.get().await.ok()?;
+        let begun = connection.run(|c| {
+            c.begin_transaction().map_err(|e| e.to_string())
+        }).await;
+
+        match begun {
+            Ok(()) => Some(TransactionInner {
+                connection: Some(connection),
+                active: AtomicBool::new(true),
+            }),
+            Err(e) => {
+                error_!("failed to begin database transaction: {}", e);
+                None
+            }
+        }
+    }
+
+    fn connection(&self) -> &Connection<K, C> {
+        self.connection.as_ref().expect("internal invariant broken: self.connection is Some")
+    }
+}
+
+impl<K: 'static, C: Poolable> Drop for TransactionInner<K, C> {
+    fn drop(&mut self) {
+        // The transaction is finished in 'on_response', so it is only active
+        // here if the response was never sent. Roll it back before the
+        // connection is returned to the pool.
+        if !self.active.swap(false, Ordering::AcqRel) {
+            return;
+        }
+
+        let connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => return,
+        };
+
+        let rollback = |c: &mut C| {
+            if let Err(e) = c.rollback_transaction() {
+                warn_!("failed to roll back database transaction: {}", e);
+            }
+        };
+
+        // Without a runtime, the connection can't be in use by a blocking task,
+        // so the rollback runs on this thread.
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move { connection.run(rollback).await });
+            }
+            Err(_) => connection.run_now(rollback),
+        }
+    }
+}
+
+/// Commits or rolls back the transaction, if any, begun for `req` on a
+/// connection of type `C` based on the status of `res`.
+pub(crate) async fn finish<K: 'static, C: Poolable>(req: &Request<'_>, res: &mut Response<'_>) {
+    let inner = match &req.local_cache(|| TransactionSlot::<K, C>(None)).0 {
+        Some(inner) if inner.active.swap(false, Ordering::AcqRel) => inner,
+        _ => return,
+    };
+
+    if res.status().code < 400 {
+        let result = inner.connection().run(|c| {
+            c.commit_transaction().map_err(|e| {
+                let _ = c.rollback_transaction();
+                e.to_string()
+            })
+        }).await;
+
+        if let Err(e) = result {
+            error_!("failed to commit database transaction: {}", e);
+            *res = Response::build().status(Status::InternalServerError).finalize();
+        }
+    } else {
+        let result = inner.connection().run(|c| {
+            c.rollback_transaction().map_err(|e| e.to_string())
+        }).await;
+
+        if let Err(e) = result {
+            warn_!("failed to roll back database transaction: {}", e);
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, D: Database> FromRequest<'r> for Transaction<D> {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, ()> {
+        let pool = match request.rocket().state::<ConnectionPool<D, D::Connection>>() {
+            Some(pool) => pool,
+            None => {
+                error_!("Missing database fairing for `{}`", std::any::type_name::<D>());
+                return Outcome::Failure((Status::InternalServerError, ()));
+            }
+        };
+
+        let slot = request.local_cache_async(async {
+            TransactionSlot(TransactionInner::begin(pool).await.map(Arc::new))
+        });
+
+        match slot.await {
+            TransactionSlot(Some(inner)) => Outcome::Success(Transaction { inner: inner.clone() }),
+            TransactionSlot(None) => Outcome::Failure((Status::ServiceUnavailable, ())),
+        }
+    }
+}
+
+impl<D: Database> Sentinel for Transaction<D> {
+    fn abort(rocket: &Rocket<Ignite>) -> bool {
+        <Connection<D, D::Connection>>::abort(rocket)
+    }
+}
@@ -118,3 +118,499 @@ mod sentinel_and_runtime_test {
         assert!(matches!(err.kind(), SentinelAborts(vec) if vec.len() == 1));
     }
 }
+
+#[cfg(feature = "diesel_sqlite_pool")]
+mod diesel_migrations_test {
+    use std::path::{Path, PathBuf};
+
+    use rocket::{Rocket, Build};
+    use rocket::figment::{Figment, util::map};
+    use rocket_sync_db_pools::{database, diesel};
+
+    use diesel::RunQueryDsl;
+
+    #[database("test_db")]
+    struct SqliteDb(diesel::SqliteConnection);
+
+    fn migrating_rocket(url: &Path, migrations: Option<&str>) -> Rocket<Build> {
+        let url = url.to_str().expect("UTF-8 path");
+        let mut options = map!["url" => url.to_string()];
+        if let Some(dir) = migrations {
+            let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join(dir);
+            options.insert("migrations", dir.to_str().expect("UTF-8 path").to_string());
+        }
+
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases", map!["test_db" => options]));
+
+        rocket::custom(config).attach(SqliteDb::fairing())
+    }
+
+    fn temp_db(name: &str) -> PathBuf {
+        let file = format!("rocket-{}-{}.sqlite", name, std::process::id());
+        let path = std::env::temp_dir().join(file);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    async fn insert_item(rocket: &Rocket<rocket::Ignite>) -> diesel::QueryResult<usize> {
+        let conn = SqliteDb::get_one(rocket).await.expect("database connection");
+        conn.run(|c| diesel::sql_query("INSERT INTO items (name) VALUES ('rocket')").execute(c))
+            .await
+    }
+
+    #[rocket::async_test]
+    async fn test_migrations() {
+        let db = temp_db("migrations");
+        let rocket = migrating_rocket(&db, Some("migrations")).ignite().await.expect("migrated");
+        assert_eq!(insert_item(&rocket).await, Ok(1));
+        drop(rocket);
+
+        // Migrations that have been run are not run again.
+        let rocket = migrating_rocket(&db, Some("migrations")).ignite().await.expect("migrated");
+        assert_eq!(insert_item(&rocket).await, Ok(1));
+        drop(rocket);
+
+        let _ = std::fs::remove_file(&db);
+    }
+
+    #[rocket::async_test]
+    async fn test_migrations_disabled() {
+        let db = temp_db("no-migrations");
+        let rocket = migrating_rocket(&db, None).ignite().await.expect("ignited");
+        assert!(insert_item(&rocket).await.is_err());
+        drop(rocket);
+
+        let _ = std::fs::remove_file(&db);
+    }
+
+    #[rocket::async_test]
+    async fn test_failed_migrations_abort_launch() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        let db = temp_db("bad-migrations");
+        let rocket = migrating_rocket(&db, Some("bad_migrations"));
+        let error = rocket.ignite().await.expect_err("failed migrations");
+        let fairing = "'test_db' Database Pool";
+        assert!(matches!(error.kind(), FailedFairings(f) if f[0].name == fairing));
+
+        let rocket = migrating_rocket(&db, Some("missing_migrations"));
+        let error = rocket.ignite().await.expect_err("missing migrations");
+        assert!(matches!(error.kind(), FailedFairings(..)));
+
+        let _ = std::fs::remove_file(&db);
+    }
+}
+
+#[cfg(feature = "sqlite_pool")]
+mod rusqlite_migrations_test {
+    use rocket::figment::{Figment, util::map};
+    use rocket_sync_db_pools::{database, rusqlite};
+
+    #[database("test_db")]
+    struct SqliteDb(rusqlite::Connection);
+
+    #[rocket::async_test]
+    async fn test_sql_migrations() {
+        let file = format!("rocket-sql-migrations-{}.sqlite", std::process::id());
+        let db = std::env::temp_dir().join(file);
+        let _ = std::fs::remove_file(&db);
+
+        let migrations = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/migrations");
+        let options = map![
+            "url" => db.to_str().expect("UTF-8 path").to_string(),
+            "migrations" => migrations.to_string()
+        ];
+
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases", map!["test_db" => options]));
+
+        // Running twice checks that migrations are only run once.
+        for _ in 0..2 {
+            let rocket = rocket::custom(config.clone())
+                .attach(SqliteDb::fairing())
+                .ignite()
+                .await
+                .expect("migrated");
+
+            let conn = SqliteDb::get_one(&rocket).await.expect("database connection");
+            let versions: i64 = conn.run(|c| {
+                c.execute("INSERT INTO items (name) VALUES ('rocket')", rusqlite::params![])?;
+                c.query_row("SELECT COUNT(*) FROM __diesel_schema_migrations",
+                    rusqlite::params![], |row| row.get(0))
+            }).await.expect("migrated tables");
+
+            assert_eq!(versions, 1);
+        }
+
+        let _ = std::fs::remove_file(&db);
+    }
+}
+
+#[cfg(feature = "diesel_sqlite_pool")]
+mod diesel_on_connect_test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use rocket::{Rocket, Build};
+    use rocket::figment::{Figment, util::map, value::{Map, Value}};
+    use rocket_sync_db_pools::{database, diesel, Config, Poolable};
+
+    use diesel::{RunQueryDsl, connection::SimpleConnection, r2d2::Error};
+
+    #[database("test_db")]
+    struct SqliteDb(diesel::SqliteConnection);
+
+    fn rocket() -> Rocket<Build> {
+        let options: Map<_, Value> = map![
+            "url" => ":memory:".into(),
+            "pool_size" => 2.into(),
+            "timeout" => 1.into(),
+            "min_idle" => 1.into(),
+            "max_lifetime" => 3600.into(),
+            "idle_timeout" => 600.into(),
+        ];
+
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases", map!["test_db" => options]));
+
+        rocket::custom(config)
+    }
+
+    #[test]
+    fn test_pool_options() {
+        let extras = Config::extras("test_db", &rocket()).expect("extras");
+        assert_eq!(extras["min_idle"].to_i128(), Some(1));
+        assert_eq!(extras["max_lifetime"].to_i128(), Some(3600));
+        assert_eq!(extras["idle_timeout"].to_i128(), Some(600));
+        assert!(!extras.contains_key("url") && !extras.contains_key("pool_size"));
+
+        let pool = diesel::SqliteConnection::pool("test_db", &rocket()).expect("pool");
+        assert_eq!(pool.min_idle(), Some(1));
+        assert_eq!(pool.max_lifetime(), Some(Duration::from_secs(3600)));
+        assert_eq!(pool.idle_timeout(), Some(Duration::from_secs(600)));
+
+        let figment = Figment::from(rocket::Config::debug_default())
+            .merge(("databases", map!["test_db" => map!["url" => ":memory:"]]));
+
+        let extras = Config::extras("test_db", &rocket::custom(figment)).expect("extras");
+        assert!(extras.is_empty());
+    }
+
+    #[rocket::async_test]
+    async fn test_on_connect_hooks() {
+        static CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+        let fairing = SqliteDb::fairing()
+            .on_connect(|conn| {
+                conn.batch_execute("CREATE TEMP TABLE hooked (id INTEGER);")
+                    .map_err(Error::QueryError)
+            })
+            .on_connect(|conn| {
+                // Hooks run in order: the table created above must exist.
+                conn.batch_execute("INSERT INTO hooked (id) VALUES (1);")
+                    .map_err(Error::QueryError)?;
+
+                CONNECTIONS.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            });
+
+        let rocket = rocket().attach(fairing).ignite().await.expect("ignited");
+        assert!(CONNECTIONS.load(Ordering::SeqCst) >= 1);
+
+        let conn = SqliteDb::get_one(&rocket).await.expect("database connection");
+        let rows = conn.run(|c| diesel::sql_query("DELETE FROM hooked").execute(c)).await;
+        assert_eq!(rows, Ok(1));
+    }
+
+    #[rocket::async_test]
+    async fn test_failing_hook_aborts_launch() {
+        use rocket::error::ErrorKind::FailedFairings;
+
+        let fairing = SqliteDb::fairing()
+            .on_connect(|_| Err(Error::QueryError(diesel::result::Error::NotFound)));
+
+        let error = rocket().attach(fairing).ignite().await.expect_err("failing hook");
+        let fairing = "'test_db' Database Pool";
+        assert!(matches!(error.kind(), FailedFairings(f) if f[0].name == fairing));
+    }
+}
+
+#[cfg(feature = "diesel_sqlite_pool")]
+mod diesel_transaction_test {
+    use std::path::{Path, PathBuf};
+
+    use rocket::{Rocket, Build, Orbit, post, routes};
+    use rocket::http::Status;
+    use rocket::figment::{Figment, util::map};
+    use rocket::local::asynchronous::Client;
+    use rocket_sync_db_pools::{database, diesel, Transaction};
+
+    use diesel::RunQueryDsl;
+
+    #[database("test_db")]
+    struct SqliteDb(diesel::SqliteConnection);
+
+    fn insert(c: &mut diesel::SqliteConnection) -> diesel::QueryResult<usize> {
+        diesel::sql_query("INSERT INTO items (name) VALUES ('rocket')").execute(c)
+    }
+
+    // Touches every row, returning the number of rows visible to `c`.
+    fn count(c: &mut diesel::SqliteConnection) -> diesel::QueryResult<usize> {
+        diesel::sql_query("UPDATE items SET name = name").execute(c)
+    }
+
+    #[post("/<status>")]
+    async fn insert_then(tx: Transaction<SqliteDb>, status: u16) -> (Status, &'static str) {
+        assert_eq!(tx.run(insert).await, Ok(1));
+        (Status::from_code(status).unwrap(), "inserted")
+    }
+
+    #[post("/panic")]
+    async fn insert_then_panic(tx: Transaction<SqliteDb>) {
+        assert_eq!(tx.run(insert).await, Ok(1));
+        panic!("rolled back");
+    }
+
+    #[post("/shared/<status>")]
+    async fn shared(a: Transaction<SqliteDb>, b: Transaction<SqliteDb>, status: u16) -> Status {
+        assert_eq!(a.run(insert).await, Ok(1));
+        assert_eq!(b.run(count).await, Ok(1));
+        Status::from_code(status).unwrap()
+    }
+
+    fn temp_db(name: &str) -> PathBuf {
+        let file = format!("rocket-{}-{}.sqlite", name, std::process::id());
+        let path = std::env::temp_dir().join(file);
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn rocket(db: &Path) -> Rocket<Build> {
+        let migrations = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/migrations");
+        let options = map![
+            "url" => db.to_str().expect("UTF-8 path").to_string(),
+            "migrations" => migrations.to_str().expect("UTF-8 path").to_string(),
+        ];
+
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases", map!["test_db" => options]));
+
+        rocket::custom(config)
+            .attach(SqliteDb::fairing())
+            .mount("/", routes![insert_then, insert_then_panic, shared])
+    }
+
+    async fn committed(rocket: &Rocket<Orbit>) -> usize {
+        let conn = SqliteDb::get_one(rocket).await.expect("database connection");
+        conn.run(count).await.expect("count")
+    }
+
+    #[rocket::async_test]
+    async fn test_transactions() {
+        let db = temp_db("transactions");
+        let client = Client::tracked(rocket(&db)).await.expect("valid rocket");
+
+        let status = client.post("/200").dispatch().await.status();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(committed(client.rocket()).await, 1);
+
+        let status = client.post("/303").dispatch().await.status();
+        assert_eq!(status, Status::SeeOther);
+        assert_eq!(committed(client.rocket()).await, 2);
+
+        let status = client.post("/400").dispatch().await.status();
+        assert_eq!(status, Status::BadRequest);
+        assert_eq!(committed(client.rocket()).await, 2);
+
+        let status = client.post("/panic").dispatch().await.status();
+        assert_eq!(status, Status::InternalServerError);
+        assert_eq!(committed(client.rocket()).await, 2);
+
+        let _ = std::fs::remove_file(&db);
+    }
+
+    #[rocket::async_test]
+    async fn test_shared_transaction() {
+        let db = temp_db("shared-transaction");
+        let client = Client::tracked(rocket(&db)).await.expect("valid rocket");
+
+        let status = client.post("/shared/500").dispatch().await.status();
+        assert_eq!(status, Status::InternalServerError);
+        assert_eq!(committed(client.rocket()).await, 0);
+
+        let status = client.post("/shared/200").dispatch().await.status();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(committed(client.rocket()).await, 1);
+
+        let _ = std::fs::remove_file(&db);
+    }
+}
+
+#[cfg(feature = "diesel_sqlite_pool")]
+mod diesel_isolation_test {
+    use std::path::Path;
+
+    use rocket::{Rocket, Build, Orbit};
+    use rocket::figment::{Figment, util::map};
+    use rocket::local::asynchronous::Client;
+    use rocket_sync_db_pools::{database, diesel, isolate, Config};
+
+    use diesel::RunQueryDsl;
+
+    #[database("test_db")]
+    struct SqliteDb(diesel::SqliteConnection);
+
+    fn rocket() -> Rocket<Build> {
+        let migrations = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/migrations");
+        let options = map![
+            "url" => "/nonexistent/shared.sqlite".to_string(),
+            "migrations" => migrations.to_str().expect("UTF-8 path").to_string(),
+        ];
+
+        let config = Figment::from(rocket::Config::debug_default())
+            .merge(("databases", map!["test_db" => options]));
+
+        rocket::custom(config).attach(SqliteDb::fairing())
+    }
+
+    async fn insert_item(rocket: &Rocket<Orbit>) -> diesel::QueryResult<usize> {
+        let conn = SqliteDb::get_one(rocket).await.expect("database connection");
+        conn.run(|c| diesel::sql_query("INSERT INTO items (name) VALUES ('rocket')").execute(c))
+            .await
+    }
+
+    async fn count_items(rocket: &Rocket<Orbit>) -> diesel::QueryResult<usize> {
+        let conn = SqliteDb::get_one(rocket).await.expect("database connection");
+        conn.run(|c| diesel::sql_query("UPDATE items SET name = name").execute(c)).await
+    }
+
+    #[rocket::async_test]
+    async fn test_isolated_databases() {
+        let (a, b) = (isolate::<SqliteDb>(rocket()), isolate::<SqliteDb>(rocket()));
+        let (a, b) = (a.expect("scratch database"), b.expect("scratch database"));
+
+        let url_a = Config::from("test_db", &a).expect("config").url;
+        let url_b = Config::from("test_db", &b).expect("config").url;
+        assert_ne!(url_a, url_b);
+
+        let a = Client::tracked(a).await.expect("migrated scratch database");
+        let b = Client::tracked(b).await.expect("migrated scratch database");
+        assert!(Path::new(&url_a).exists());
+
+        assert_eq!(insert_item(a.rocket()).await, Ok(1));
+        assert_eq!(insert_item(a.rocket()).await, Ok(1));
+        assert_eq!(insert_item(b.rocket()).await, Ok(1));
+        assert_eq!(count_items(a.rocket()).await, Ok(2));
+        assert_eq!(count_items(b.rocket()).await, Ok(1));
+
+        drop(a);
+        assert!(!Path::new(&url_a).exists());
+        assert!(Path::new(&url_b).exists());
+
+        drop(b);
+        assert!(!Path::new(&url_b).exists());
+    }
+}
+
+#[cfg(feature = "diesel_sqlite_pool")]
+mod diesel_health_test {
+    use rocket::{Rocket, Build};
+    use rocket::figment::{Figment, util::map, value::{Map, Value}};
+    use rocket::http::Status;
+    use rocket::local::asynchronous::Client;
+    use rocket_sync_db_pools::{database, diesel, Health, HealthCheck};
+
+    #[database("healthy_db")]
+    struct HealthyDb(diesel::SqliteConnection);
+
+    #[database("busy_db")]
+    struct BusyDb(diesel::SqliteConnection);
+
+    fn rocket(databases: Figment) -> Rocket<Build> {
+        let config = Figment::from(rocket::Config::debug_default()).merge(databases);
+        rocket::custom(config)
+            .attach(HealthyDb::fairing())
+            .mount("/ready", HealthCheck::new())
+    }
+
+    #[rocket::async_test]
+    async fn test_pool_stats() {
+        let options: Map<_, Value> = map!["url" => ":memory:".into(), "pool_size" => 2.into()];
+        let databases = Figment::from(("databases", map!["healthy_db" => options]));
+        let client = Client::tracked(rocket(databases)).await.unwrap();
+
+        let stats = HealthyDb::pool_stats(client.rocket()).expect("pool stats");
+        assert_eq!((stats.size, stats.in_use, stats.acquired, stats.timeouts), (2, 0, 0, 0));
+
+        let conn = HealthyDb::get_one(client.rocket()).await.expect("connection");
+        let stats = HealthyDb::pool_stats(client.rocket()).expect("pool stats");
+        assert_eq!((stats.in_use, stats.acquired), (1, 1));
+        assert!(stats.connections >= 1);
+
+        // Connections are returned to the pool asynchronously.
+        drop(conn);
+        let mut attempts = 0;
+        while HealthyDb::pool_stats(client.rocket()).expect("pool stats").in_use != 0 {
+            assert!(attempts < 100, "connection was never released");
+            rocket::tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            attempts += 1;
+        }
+
+        let _conn = HealthyDb::get_one(client.rocket()).await.expect("connection");
+        let stats = HealthyDb::pool_stats(client.rocket()).expect("pool stats");
+        assert_eq!((stats.in_use, stats.acquired, stats.timeouts, stats.failures), (1, 2, 0, 0));
+        assert!(stats.average_wait() <= stats.wait_time);
+    }
+
+    #[rocket::async_test]
+    async fn test_health_check() {
+        let options = map!["url" => ":memory:"];
+        let databases = Figment::from(("databases", map!["healthy_db" => options]));
+        let client = Client::tracked(rocket(databases)).await.unwrap();
+
+        let health = Health::check(client.rocket()).await;
+        assert!(health.is_healthy());
+        assert_eq!(health.databases.len(), 1);
+        assert_eq!(health.databases[0].name, "healthy_db");
+        assert!(health.databases[0].latency.is_some());
+
+        let response = client.get("/ready").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().await.unwrap().starts_with("healthy_db: ok"));
+    }
+
+    #[rocket::async_test]
+    async fn test_unhealthy_database() {
+        let busy: Map<_, Value> = map![
+            "url" => ":memory:".into(),
+            "pool_size" => 1.into(),
+            "timeout" => 1.into(),
+        ];
+
+        let databases = Figment::from(("databases", map![
+            "healthy_db" => map!["url" => ":memory:".into()],
+            "busy_db" => busy,
+        ]));
+
+        let rocket = rocket(databases).attach(BusyDb::fairing());
+        let client = Client::tracked(rocket).await.unwrap();
+        assert!(Health::check(client.rocket()).await.is_healthy());
+
+        // Exhaust the pool so that no connection can be retrieved.
+        let conn = BusyDb::get_one(client.rocket()).await.expect("connection");
+        let health = Health::check(client.rocket()).await;
+        assert!(!health.is_healthy());
+        assert!(health.databases[0].is_healthy());
+        assert_eq!(health.databases[1].name, "busy_db");
+        assert!(health.databases[1].error.is_some());
+        assert_eq!(health.databases[1].stats.in_use, 1);
+
+        let response = client.get("/ready").dispatch().await;
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        assert!(response.into_string().await.unwrap().contains("busy_db: error"));
+
+        drop(conn);
+        assert!(Health::check(client.rocket()).await.is_healthy());
+    }
+}
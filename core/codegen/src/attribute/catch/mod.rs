@@ -1,11 +1,13 @@
 mod parse;
 
-use devise::ext::SpanDiagnosticExt;
+use devise::ext::{SpanDiagnosticExt, TypeExt};
 use devise::{Spanned, Result};
 use proc_macro2::{TokenStream, Span};
+use quote::ToTokens;
 
 use crate::http_codegen::Optional;
-use crate::syn_ext::ReturnTypeExt;
+use crate::syn_ext::{ReturnTypeExt, FnArgExt};
+use crate::name::Name;
 use crate::exports::*;
 
 pub fn _catch(
@@ -20,12 +22,40 @@ pub fn _catch(
     let user_catcher_fn_name = &catch.function.sig.ident;
     let vis = &catch.function.vis;
     let status_code = Optional(catch.status.map(|s| s.code));
+    let format = Optional(catch.format.as_ref());
+    let method = Optional(catch.method);
+
+    // Find the argument named by `error`, if any.
+    let error_arg = match catch.error {
+        Some(ref error) => {
+            let arg = catch.function.sig.inputs.iter()
+                .filter_map(|arg| arg.typed())
+                .find(|(ident, _)| Name::from(*ident).as_str() == error.name.as_str());
+
+            match arg {
+                Some((ident, ty)) => Some((error, ident, ty)),
+                None => {
+                    let msg = format!("expected argument named `{}` here", error.name);
+                    return Err(error.span().error("unused parameter")
+                        .span_note(catch.function.sig.paren_token.span, msg));
+                }
+            }
+        }
+        None => None,
+    };
 
     // Determine the number of parameters that will be passed in.
-    if catch.function.sig.inputs.len() > 2 {
+    let is_error_arg = |arg: &syn::FnArg| match (arg.typed(), error_arg) {
+        (Some((ident, _)), Some((_, error_ident, _))) => ident == error_ident,
+        _ => false,
+    };
+
+    let num_args = catch.function.sig.inputs.iter().filter(|a| !is_error_arg(a)).count();
+    if num_args > 2 {
         return Err(catch.function.sig.paren_token.span
             .error("invalid number of arguments: must be zero, one, or two")
-            .help("catchers optionally take `&Request` or `Status, &Request`"));
+            .help("catchers optionally take `&Request` or `Status, &Request`")
+            .help("typed catchers additionally take the argument named by `error`"));
     }
 
     // This ensures that "Responder not implemented" points to the return type.
@@ -33,15 +63,58 @@ pub fn _catch(
         .map(|ty| ty.span())
         .unwrap_or_else(Span::call_site);
 
+    // Take the guard's error value out of the request. The stashed value's
+    // type is given by `FromTyped` for the argument's type without a reference
+    // and with `'static` lifetimes. The argument may borrow from the value.
+    let error_ty = error_arg.map(|(_, _, ty)| match ty {
+        syn::Type::Reference(r) => (&*r.elem, true),
+        ty => (ty, false),
+    });
+
+    let stashed_ty = error_ty.map(|(ty, _)| {
+        let static_ty = ty.with_replaced_lifetimes(lifetime("'static", ty));
+        quote_spanned!(ty.span() => <#static_ty as #_catcher::resolution::FromTyped<'static>>::Typed)
+    });
+
+    let error_type = Optional(stashed_ty.as_ref().map(|stashed_ty| {
+        quote!(#_catcher::ErrorType::of::<#stashed_ty>())
+    }));
+
+    let error_decl = error_ty.map(|(ty, _)| {
+        let elided_ty = ty.with_replaced_lifetimes(lifetime("'_", ty));
+        quote_spanned! { ty.span() =>
+            let mut __stashed = #__req.take_error::<#stashed_ty>();
+            let __error: #elided_ty = match #_catcher::resolution::FromTyped::from_typed(&mut __stashed) {
+                #_Some(__e) => __e,
+                #_None => {
+                    #_log::error_!("Catcher `{}` expected an error value of type `{}`.",
+                        stringify!(#user_catcher_fn_name), stringify!(#elided_ty));
+
+                    return #_Err(#Status::InternalServerError);
+                }
+            };
+        }
+    });
+
     // Set the `req` and `status` spans to that of their respective function
-    // arguments for a more correct `wrong type` error span. `rev` to be cute.
-    let codegen_args = &[__req, __status];
-    let inputs = catch.function.sig.inputs.iter().rev()
-        .zip(codegen_args.iter())
-        .map(|(fn_arg, codegen_arg)| match fn_arg {
-            syn::FnArg::Receiver(_) => codegen_arg.respanned(fn_arg.span()),
-            syn::FnArg::Typed(a) => codegen_arg.respanned(a.ty.span())
-        }).rev();
+    // arguments for a more correct `wrong type` error span.
+    let codegen_args = [__status, __req];
+    let mut codegen_args = codegen_args[2 - num_args..].iter();
+    let inputs = catch.function.sig.inputs.iter().map(|fn_arg| {
+        if is_error_arg(fn_arg) {
+            let (span, by_ref) = (fn_arg.span(), matches!(error_ty, Some((_, true))));
+            return match by_ref {
+                true => quote_spanned!(span => &__error),
+                false => quote_spanned!(span => __error),
+            };
+        }
+
+        let codegen_arg = codegen_args.next().expect("at most two non-error arguments");
+        match fn_arg {
+            syn::FnArg::Receiver(_) => codegen_arg.respanned(fn_arg.span()).into_token_stream(),
+            syn::FnArg::Typed(a) => codegen_arg.respanned(a.ty.span()).into_token_stream(),
+        }
+    }).collect::<Vec<_>>();
 
     // We append `.await` to the function call if this is `async`.
     let dot_await = catch.function.sig.asyncness
@@ -69,6 +142,7 @@ pub fn _catch(
                     #__req: &'__r #Request<'_>
                 ) -> #_catcher::BoxFuture<'__r> {
                     #_Box::pin(async move {
+                        #error_decl
                         let __response = #catcher_response;
                         #Response::build()
                             .status(#__status)
@@ -81,6 +155,9 @@ pub fn _catch(
                     name: stringify!(#user_catcher_fn_name),
                     code: #status_code,
                     handler: monomorphized_function,
+                    error: #error_type,
+                    format: #format,
+                    method: #method,
                 }
             }
 
@@ -92,6 +169,10 @@ pub fn _catch(
     })
 }
 
+fn lifetime(name: &str, ty: &syn::Type) -> syn::Lifetime {
+    syn::Lifetime::new(name, ty.span())
+}
+
 pub fn catch_attribute(
     args: proc_macro::TokenStream,
     input: proc_macro::TokenStream
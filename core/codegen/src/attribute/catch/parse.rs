@@ -1,13 +1,20 @@
 use devise::ext::SpanDiagnosticExt;
-use devise::{MetaItem, Spanned, Result, FromMeta, Diagnostic};
+use devise::{MetaItem, Spanned, SpanWrapped, Result, FromMeta, Diagnostic};
 use proc_macro2::TokenStream;
 
 use crate::{http, http_codegen};
+use crate::attribute::param::Dynamic;
 
 /// This structure represents the parsed `catch` attribute and associated items.
 pub struct Attribute {
     /// The status associated with the code in the `#[catch(code)]` attribute.
     pub status: Option<http::Status>,
+    /// The parameter named in `error = "<param>"`, if any.
+    pub error: Option<SpanWrapped<Dynamic>>,
+    /// The media type in `format = "<media-type>"`, if any.
+    pub format: Option<http_codegen::MediaType>,
+    /// The method in `method = METHOD`, if any.
+    pub method: Option<http_codegen::Method>,
     /// The function that was decorated with the `catch` attribute.
     pub function: syn::ItemFn,
 }
@@ -17,6 +24,9 @@ pub struct Attribute {
 struct Meta {
     #[meta(naked)]
     code: Code,
+    error: Option<SpanWrapped<Dynamic>>,
+    format: Option<http_codegen::MediaType>,
+    method: Option<http_codegen::Method>,
 }
 
 /// `Some` if there's a code, `None` if it's `default`.
@@ -48,11 +58,16 @@ impl Attribute {
             .map_err(|diag| diag.help("`#[catch]` can only be used on functions"))?;
 
         let attr: MetaItem = syn::parse2(quote!(catch(#args)))?;
-        let status = Meta::from_meta(&attr)
-            .map(|meta| meta.code.0)
+        let meta = Meta::from_meta(&attr)
             .map_err(|diag| diag.help("`#[catch]` expects a status code int or `default`: \
                         `#[catch(404)]` or `#[catch(default)]`"))?;
 
-        Ok(Attribute { status, function })
+        Ok(Attribute {
+            status: meta.code.0,
+            error: meta.error,
+            format: meta.format,
+            method: meta.method,
+            function,
+        })
     }
 }
@@ -3,6 +3,8 @@ mod parse;
 use std::ops::Deref;
 use std::hash::Hash;
 
+use proc_macro2::Span;
+
 use crate::name::Name;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -18,6 +20,14 @@ pub struct Dynamic {
     pub name: Name,
     pub index: usize,
     pub trailing: bool,
+    pub constraint: Option<Constraint>,
+}
+
+/// The constraint in a path parameter `<name:constraint>`.
+#[derive(Debug, Clone)]
+pub struct Constraint {
+    pub source: String,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
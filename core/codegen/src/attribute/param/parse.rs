@@ -4,7 +4,7 @@ use proc_macro2::Span;
 
 use crate::name::Name;
 use crate::proc_macro_ext::StringLit;
-use crate::attribute::param::{Parameter, Dynamic};
+use crate::attribute::param::{Parameter, Dynamic, Constraint};
 use crate::http::uri::fmt::{Part, Kind, Path};
 
 #[derive(Debug)]
@@ -24,6 +24,7 @@ pub enum ErrorKind {
     EarlyTrailing,
     NoTrailing,
     Static,
+    Constrained,
 }
 
 impl Dynamic {
@@ -31,7 +32,7 @@ impl Dynamic {
         segment: &str,
         span: Span,
     ) -> Result<Self, Error<'_>>  {
-        match Parameter::parse::<P>(&segment, span)? {
+        match Parameter::parse::<P>(segment, span)? {
             Parameter::Dynamic(d) | Parameter::Ignored(d) => Ok(d),
             Parameter::Guard(g) => Ok(g.source),
             Parameter::Static(_) => Err(Error::new(segment, span, ErrorKind::Static)),
@@ -54,6 +55,19 @@ impl Parameter {
                 name = &name[..(name.len() - 2)];
             }
 
+            let mut constraint = None;
+            if let Some((ident, source)) = name.split_once(':') {
+                let span = subspan(source, segment, source_span);
+                if source.is_empty() {
+                    return Err(Error::new(source, span, ErrorKind::Empty));
+                } else if trailing || P::KIND != Kind::Path {
+                    return Err(Error::new(segment, source_span, ErrorKind::Constrained));
+                }
+
+                name = ident;
+                constraint = Some(Constraint { source: source.into(), span });
+            }
+
             let span = subspan(name, segment, source_span);
             if name.is_empty() {
                 return Err(Error::new(name, source_span, ErrorKind::Empty));
@@ -61,7 +75,7 @@ impl Parameter {
                 return Err(Error::new(name, span, ErrorKind::BadIdent));
             }
 
-            let dynamic = Dynamic { name: Name::new(name, span), trailing, index: 0 };
+            let dynamic = Dynamic { name: Name::new(name, span), trailing, index: 0, constraint };
             if dynamic.is_wild() && P::KIND != Kind::Path {
                 return Err(Error::new(name, span, ErrorKind::Ignored));
             } else if dynamic.is_wild() {
@@ -128,6 +142,7 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::NoTrailing => "parameter cannot be trailing".fmt(f),
             ErrorKind::EarlyTrailing => "unexpected text after trailing parameter".fmt(f),
             ErrorKind::Static => "unexpected static parameter".fmt(f),
+            ErrorKind::Constrained => "parameter cannot be constrained".fmt(f),
         }
     }
 }
@@ -174,6 +189,10 @@ impl From<Error<'_>> for Diagnostic {
                 error.span.error(error.kind.to_string())
                     .help(format!("parameter must be dynamic: `<{}>`", candidate))
             }
+            ErrorKind::Constrained => {
+                error.span.error(error.kind.to_string())
+                    .help("only single, non-trailing path parameters can be constrained")
+            }
         }
     }
 }
@@ -185,9 +204,11 @@ impl devise::FromMeta for Dynamic {
         let param = Dynamic::parse::<Path>(&string, span)?;
 
         if param.is_wild() {
-            return Err(Error::new(&string, span, ErrorKind::Ignored).into());
+            Err(Error::new(&string, span, ErrorKind::Ignored).into())
         } else if param.trailing {
-            return Err(Error::new(&string, span, ErrorKind::NoTrailing).into());
+            Err(Error::new(&string, span, ErrorKind::NoTrailing).into())
+        } else if param.constraint.is_some() {
+            Err(Error::new(&string, span, ErrorKind::Constrained).into())
         } else {
             Ok(param)
         }
@@ -112,7 +112,7 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
 fn request_guard_decl(guard: &Guard) -> TokenStream {
     let (ident, ty) = (guard.fn_ident.rocketized(), &guard.ty);
     define_spanned_export!(ty.span() =>
-        __req, __data, _request, _log, FromRequest, Outcome
+        __req, __data, _request, _log, _catcher, FromRequest, Outcome
     );
 
     quote_spanned! { ty.span() =>
@@ -124,6 +124,9 @@ fn request_guard_decl(guard: &Guard) -> TokenStream {
             },
             #Outcome::Failure((__c, __e)) => {
                 #_log::warn_!("Request guard `{}` failed: {:?}.", stringify!(#ty), __e);
+                #[allow(unused_imports)]
+                use #_catcher::resolution::DefaultStash as _;
+                #_catcher::resolution::Resolve(__e).stash(#__req);
                 return #Outcome::Failure(__c);
             }
         };
@@ -176,7 +179,7 @@ fn param_guard_decl(guard: &Guard) -> TokenStream {
 
 fn data_guard_decl(guard: &Guard) -> TokenStream {
     let (ident, ty) = (guard.fn_ident.rocketized(), &guard.ty);
-    define_spanned_export!(ty.span() => _log, __req, __data, FromData, Outcome);
+    define_spanned_export!(ty.span() => _log, _catcher, __req, __data, FromData, Outcome);
 
     quote_spanned! { ty.span() =>
         let #ident: #ty = match <#ty as #FromData>::from_data(#__req, #__data).await {
@@ -187,6 +190,9 @@ fn data_guard_decl(guard: &Guard) -> TokenStream {
             }
             #Outcome::Failure((__c, __e)) => {
                 #_log::warn_!("Data guard `{}` failed: {:?}.", stringify!(#ty), __e);
+                #[allow(unused_imports)]
+                use #_catcher::resolution::DefaultStash as _;
+                #_catcher::resolution::Resolve(__e).stash(#__req);
                 return #Outcome::Failure(__c);
             }
         };
@@ -209,6 +215,10 @@ fn internal_uri_macro_decl(route: &Route) -> TokenStream {
     });
 
     let route_uri = route.attr.uri.to_string();
+    let route_host = match route.attr.host {
+        Some(ref host) => quote!(#host),
+        None => quote!(_),
+    };
 
     quote_spanned! { Span::call_site() =>
         #[doc(hidden)]
@@ -216,7 +226,7 @@ fn internal_uri_macro_decl(route: &Route) -> TokenStream {
         /// Rocket generated URI macro.
         macro_rules! #inner_macro_name {
             ($($token:tt)*) => {{
-                rocket::rocket_internal_uri!(#route_uri, (#(#uri_args),*), $($token)*)
+                rocket::rocket_internal_uri!(#route_uri, #route_host, (#(#uri_args),*), $($token)*)
             }};
         }
 
@@ -319,6 +329,16 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     // Extract the sentinels from the route.
     let sentinels = sentinels_expr(&route);
 
+    // `uuid` constraints fail to compile without Rocket's `uuid` feature.
+    let uuid_constraints = route.path_params.iter()
+        .filter_map(|p| p.dynamic().or_else(|| p.ignored()))
+        .filter_map(|d| d.constraint.as_ref())
+        .filter(|c| c.source == "uuid")
+        .map(|c| {
+            let route = _route.respanned(c.span);
+            quote_spanned!(c.span => #route::uuid_constraint!();)
+        });
+
     // Gather info about the function.
     let (vis, handler_fn) = (&route.handler.vis, &route.handler);
     let handler_fn_name = &handler_fn.sig.ident;
@@ -329,6 +349,13 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let uri = route.attr.uri.to_string();
     let rank = Optional(route.attr.rank);
     let format = Optional(route.attr.format.as_ref());
+    let host = Optional(route.attr.host.as_ref());
+    let deprecated = route.attr.deprecated.unwrap_or(false);
+    let sunset = Optional(route.attr.sunset.as_ref().map(|s| &s.0));
+    let tags = route.attr.tags.as_ref().map_or(&[][..], |t| &t.0);
+    let summary = Optional(route.attr.summary.as_ref());
+    let docs = Optional(route.docs.as_ref());
+    let may_forward = !route.arguments.map.is_empty();
 
     Ok(quote! {
         #handler_fn
@@ -342,6 +369,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
         impl #handler_fn_name {
             #[allow(non_snake_case, unreachable_patterns, unreachable_code)]
             fn into_info(self) -> #_route::StaticInfo {
+                #(#uuid_constraints)*
+
                 fn monomorphized_function<'__r>(
                     #__req: &'__r #Request<'_>,
                     #__data: #Data<'__r>
@@ -363,6 +392,13 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     handler: monomorphized_function,
                     format: #format,
                     rank: #rank,
+                    host: #host,
+                    deprecated: #deprecated,
+                    sunset: #sunset,
+                    tags: &[#(#tags),*],
+                    summary: #summary,
+                    docs: #docs,
+                    may_forward: #may_forward,
                     sentinels: #sentinels,
                 }
             }
@@ -415,6 +451,11 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        host: method_attribute.host,
+        deprecated: method_attribute.deprecated,
+        sunset: method_attribute.sunset,
+        tags: method_attribute.tags,
+        summary: method_attribute.summary,
     };
 
     codegen_route(Route::from(attribute, function)?)
@@ -4,8 +4,8 @@ use indexmap::{IndexSet, IndexMap};
 use proc_macro2::Span;
 
 use crate::proc_macro_ext::Diagnostics;
-use crate::http_codegen::{Method, MediaType};
-use crate::attribute::param::{Parameter, Dynamic, Guard};
+use crate::http_codegen::{Method, MediaType, HostPattern};
+use crate::attribute::param::{Parameter, Dynamic, Guard, Constraint};
 use crate::syn_ext::FnArgExt;
 use crate::name::Name;
 use crate::http::ext::IntoOwned;
@@ -28,6 +28,8 @@ pub struct Route {
     pub handler: syn::ItemFn,
     /// The parsed arguments to the user's function.
     pub arguments: Arguments,
+    /// The handler's doc comment, if any.
+    pub docs: Option<String>,
 }
 
 type ArgumentMap = IndexMap<Name, (syn::Ident, syn::Type)>;
@@ -47,6 +49,11 @@ pub struct Attribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub host: Option<HostPattern>,
+    pub deprecated: Option<bool>,
+    pub sunset: Option<SpanWrapped<Sunset>>,
+    pub tags: Option<Tags>,
+    pub summary: Option<String>,
 }
 
 /// The parsed `#[method(..)]` (e.g, `get`, `put`, etc.) attribute.
@@ -57,6 +64,11 @@ pub struct MethodAttribute {
     pub data: Option<SpanWrapped<Dynamic>>,
     pub format: Option<MediaType>,
     pub rank: Option<isize>,
+    pub host: Option<HostPattern>,
+    pub deprecated: Option<bool>,
+    pub sunset: Option<SpanWrapped<Sunset>>,
+    pub tags: Option<Tags>,
+    pub summary: Option<String>,
 }
 
 #[derive(Debug)]
@@ -101,6 +113,67 @@ impl FromMeta for RouteUri {
     }
 }
 
+/// The `YYYY-MM-DD` date of a `sunset = "..."` route attribute parameter.
+#[derive(Debug)]
+pub struct Sunset(pub String);
+
+impl FromMeta for Sunset {
+    fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
+        fn is_valid(date: &str) -> bool {
+            let parts = date.split('-')
+                .map(|p| (p.len(), p.bytes().all(|b| b.is_ascii_digit()), p.parse::<u32>()))
+                .collect::<Vec<_>>();
+
+            let (y, m, d) = match parts.as_slice() {
+                [(4, true, Ok(y)), (2, true, Ok(m)), (2, true, Ok(d))] => (*y, *m, *d),
+                _ => return false,
+            };
+
+            let leap = (y % 4 == 0 && y % 100 != 0) || y % 400 == 0;
+            let days = match m {
+                1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+                4 | 6 | 9 | 11 => 30,
+                2 if leap => 29,
+                2 => 28,
+                _ => return false,
+            };
+
+            (1..=days).contains(&d)
+        }
+
+        let date = String::from_meta(meta)?;
+        if !is_valid(&date) {
+            return Err(meta.value_span().error("invalid sunset date")
+                .help("expected a date in the form \"YYYY-MM-DD\""));
+        }
+
+        Ok(Sunset(date))
+    }
+}
+
+/// The string literals of a `tags = [...]` route attribute parameter.
+#[derive(Debug)]
+pub struct Tags(pub Vec<String>);
+
+impl FromMeta for Tags {
+    fn from_meta(meta: &devise::MetaItem) -> Result<Self> {
+        let array = match meta.expr()? {
+            syn::Expr::Array(array) => array,
+            expr => return Err(expr.span().error("invalid value: expected array of strings")
+                .help("tags are declared as `tags = [\"a\", \"b\"]`")),
+        };
+
+        let tags = array.elems.iter()
+            .map(|elem| match elem {
+                syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => Ok(s.value()),
+                _ => Err(elem.span().error("invalid tag: expected string literal")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Tags(tags))
+    }
+}
+
 impl Route {
     pub fn upgrade_param(param: Parameter, args: &Arguments) -> Result<Parameter> {
         if param.dynamic().is_none() {
@@ -121,6 +194,60 @@ impl Route {
         }
     }
 
+    /// Checks that the regular expression constraint `c` is valid or that the
+    /// type of `guard`, if any, is that of the integer or `uuid` constraint `c`,
+    /// possibly wrapped in an `Option` or `Result`.
+    pub fn check_constraint(c: &Constraint, guard: Option<&Guard>) -> Result<()> {
+        const INTEGERS: &[&str] = &[
+            "u8", "u16", "u32", "u64", "u128", "usize",
+            "i8", "i16", "i32", "i64", "i128", "isize",
+        ];
+
+        let expected = match c.source.as_str() {
+            ty if INTEGERS.contains(&ty) => ty,
+            "uuid" => "Uuid",
+            regex => return match regex_syntax::Parser::new().parse(regex) {
+                Ok(_) => Ok(()),
+                Err(regex_syntax::Error::Parse(e)) => Err(c.span.error(e.kind().to_string())),
+                Err(regex_syntax::Error::Translate(e)) => Err(c.span.error(e.kind().to_string())),
+                Err(e) => Err(c.span.error(e.to_string())),
+            }.map_err(|d| d.help("constraints other than integer types and `uuid` are \
+                regular expressions that must match the entire segment")),
+        };
+
+        let guard = match guard {
+            Some(guard) => guard,
+            None => return Ok(()),
+        };
+
+        fn last_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+            match ty {
+                syn::Type::Path(p) if p.qself.is_none() => p.path.segments.last(),
+                _ => None
+            }
+        }
+
+        // Look through `Option<T>` and `Result<T, E>` to `T`.
+        let mut ty = &guard.ty;
+        if let Some(segment) = last_segment(ty) {
+            if segment.ident == "Option" || segment.ident == "Result" {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                        ty = inner;
+                    }
+                }
+            }
+        }
+
+        match last_segment(ty) {
+            Some(segment) if segment.ident == expected && segment.arguments.is_empty() => Ok(()),
+            _ => Err(guard.ty.span()
+                .error(format!("type of `{}` does not match its constraint", guard.name))
+                .span_note(c.span, format!("`{}` constraint here", c.source))
+                .help(format!("expected `{0}`, `Option<{0}>`, or `Result<{0}, _>`", expected))),
+        }
+    }
+
     pub fn from(attr: Attribute, handler: syn::ItemFn) -> Result<Route> {
         // Collect diagnostics as we proceed.
         let mut diags = Diagnostics::new();
@@ -163,6 +290,13 @@ impl Route {
             .filter_map(|p| p.map_err(|e| diags.push(e)).ok())
             .collect::<Vec<_>>();
 
+        // Check path parameter constraints, and that they match argument types.
+        path_params.iter()
+            .filter_map(|p| p.dynamic().or_else(|| p.ignored()).map(|d| (d, p.guard())))
+            .filter_map(|(d, guard)| d.constraint.as_ref().map(|c| (c, guard)))
+            .filter_map(|(c, guard)| Route::check_constraint(c, guard).err())
+            .for_each(|e| diags.push(e));
+
         // Parse and collect the query parameters.
         let query_params = match (attr.uri.query(), attr.uri.query_span) {
             (Some(q), Some(span)) => Parameter::parse_many::<fmt::Query>(q.as_str(), span)
@@ -202,15 +336,37 @@ impl Route {
             })
             .enumerate()
             .map(|(index, (name, (ident, ty)))| Guard {
-                source: Dynamic { index, name: name.clone(), trailing: false },
+                source: Dynamic { index, name: name.clone(), trailing: false, constraint: None },
                 fn_ident: ident.clone(),
                 ty: ty.clone(),
             })
             .collect();
 
+        // A sunset date only makes sense for a deprecated route.
+        if let Some(ref sunset) = attr.sunset {
+            if !attr.deprecated.unwrap_or(false) {
+                diags.push(sunset.full_span.error("`sunset` requires `deprecated`")
+                    .help("mark the route as deprecated with `deprecated`"));
+            }
+        }
+
+        // Collect the handler's doc comment, one line per `doc` attribute.
+        let doc_lines = handler.attrs.iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .filter_map(|attr| match attr.parse_meta() {
+                Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(s), .. })) => {
+                    Some(s.value())
+                }
+                _ => None,
+            })
+            .map(|line| line.strip_prefix(' ').map(|l| l.to_string()).unwrap_or(line))
+            .collect::<Vec<_>>();
+
+        let docs = Some(doc_lines.join("\n").trim().to_string()).filter(|d| !d.is_empty());
+
         diags.head_err_or(Route {
             attr, path_params, query_params, data_guard, request_guards,
-            handler, arguments,
+            handler, arguments, docs,
         })
     }
 }
@@ -116,6 +116,24 @@ fn extract_exprs(internal: &InternalUriParams) -> Result<(
     }
 }
 
+fn check_prefix_host(internal: &InternalUriParams) -> Result<()> {
+    let (pattern, (host, span)) = match (&internal.host, internal.literal_prefix_host()) {
+        (Some(pattern), Some(prefix)) => (pattern, prefix),
+        _ => return Ok(()),
+    };
+
+    if !pattern.matches_domain(host) {
+        let route_name = &internal.uri_mac.route.path;
+        let mut route_name = quote!(#route_name).to_string();
+        route_name.retain(|c| !c.is_whitespace());
+
+        return Err(span.error(format!("URI prefix host `{}` does not match route host", host))
+            .note(format!("route `{}` only matches hosts matching `{}`", route_name, pattern)));
+    }
+
+    Ok(())
+}
+
 fn add_binding<P: fmt::Part>(to: &mut Vec<TokenStream>, ident: &Ident, ty: &Type, expr: &Expr) {
     let span = expr.span();
     let part = match P::KIND {
@@ -224,6 +242,7 @@ fn explode_query<'a>(
 pub fn _uri_internal_macro(input: TokenStream) -> Result<TokenStream> {
     // Parse the internal invocation and the user's URI param expressions.
     let internal = syn::parse2::<InternalUriParams>(input)?;
+    check_prefix_host(&internal)?;
     let (path_exprs, query_exprs, mut fn_args) = extract_exprs(&internal)?;
 
     let mut bindings = vec![];
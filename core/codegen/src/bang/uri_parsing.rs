@@ -9,7 +9,7 @@ use syn::punctuated::Punctuated;
 use proc_macro2::{TokenStream, TokenTree, Span};
 use rocket_http::uri::{Error, Reference};
 
-use crate::http::uri::{Uri, Origin, Absolute, fmt};
+use crate::http::uri::{Uri, Origin, Absolute, HostPattern, fmt};
 use crate::http::ext::IntoOwned;
 use crate::proc_macro_ext::StringLit;
 use crate::attribute::param::{Parameter, Dynamic};
@@ -103,17 +103,19 @@ pub enum Validation<'a> {
 //
 // `fn_args` are the URI arguments (excluding request guards) from the original
 // handler in the order they were declared in the URI (`<first>/<second>`).
-// `route_uri` is the full route URI itself.
+// `route_uri` is the full route URI itself. `host` is the route's host
+// pattern, if any, or `_` otherwise.
 //
 // The syntax of `uri_mac` is that of `UriMacro`.
 //
-//  internal_uri!("/<one>/<_>?lang=en&<two>", (one: ty, two: ty), $($tt)*);
-//                ^----/----^ ^-----\-----^    ^-------/------^   ^-----|
-//               path_params    query_params       fn_args          uri_mac
+//  internal_uri!("/<one>/<_>?lang=en&<two>", "api.*", (one: ty, two: ty), $($tt)*);
+//                ^----/----^ ^-----\-----^    ^-----^  ^-------/------^   ^-----|
+//               path_params    query_params     host      fn_args          uri_mac
 //                ^------ route_uri ------^
 #[derive(Debug)]
 pub struct InternalUriParams {
     pub route_uri: Origin<'static>,
+    pub host: Option<HostPattern>,
     pub path_params: Vec<Parameter>,
     pub query_params: Vec<Parameter>,
     pub fn_args: Vec<FnArg>,
@@ -312,6 +314,18 @@ impl Parse for InternalUriParams {
             .map(|o| o.into_normalized().into_owned())
             .map_err(|_| input.error("internal error: invalid route URI"))?;
 
+        let host = match input.parse::<Token![_]>() {
+            Ok(_) => None,
+            Err(_) => {
+                let host = input.parse::<StringLit>()?;
+                let pattern = HostPattern::parse(&host)
+                    .map_err(|_| input.error("internal error: invalid route host"))?;
+
+                Some(pattern)
+            }
+        };
+
+        input.parse::<Token![,]>()?;
         let content;
         syn::parenthesized!(content in input);
         let fn_args: Punctuated<FnArg, Token![,]> = content.parse_terminated(FnArg::parse)?;
@@ -336,6 +350,7 @@ impl Parse for InternalUriParams {
 
         Ok(InternalUriParams {
             route_uri,
+            host,
             path_params,
             query_params,
             fn_args,
@@ -345,6 +360,17 @@ impl Parse for InternalUriParams {
 }
 
 impl InternalUriParams {
+    /// Returns the host and span of the prefix if the prefix is a string
+    /// literal absolute URI with an authority.
+    pub fn literal_prefix_host(&self) -> Option<(&str, Span)> {
+        match self.uri_mac.prefix.as_ref()? {
+            UriExpr::Uri(UriLit(Uri::Absolute(uri), span)) => {
+                uri.authority().map(|auth| (auth.host(), *span))
+            }
+            _ => None,
+        }
+    }
+
     pub fn fn_args_str(&self) -> String {
         self.fn_args.iter()
             .map(|FnArg { ident, ty }| {
@@ -0,0 +1,167 @@
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+use crate::derive::form_field::{VariantExt, first_duplicate};
+use crate::derive::uri_display::derive_uri_display_path_param;
+use crate::syn_ext::{TypeExt as _, GenericsExt as _};
+
+const EXACTLY_ONE_FIELD: &str = "struct must have exactly one field";
+
+/// The field's type with its lifetimes replaced with `lifetime`.
+fn field_ty(field: &Field<'_>, lifetime: &str) -> syn::Type {
+    field.ty.with_replaced_lifetimes(syn::Lifetime::new(lifetime, field.ty.span()))
+}
+
+fn newtype_bounds(bound: TokenStream, lifetime: &'static str) -> MapperBuild {
+    MapperBuild::new()
+        .try_enum_map(|m, e| mapper::enum_null(m, e))
+        .try_fields_map(move |_, fields| {
+            let generic_idents = fields.parent.input().generics().type_idents();
+            let bounds = fields.iter()
+                .map(|f| field_ty(&f, lifetime))
+                .filter(|ty| !ty.is_concrete(&generic_idents))
+                .map(|ty| quote_spanned!(ty.span() => #ty: #bound));
+
+            Ok(quote!(#(#bounds,)*))
+        })
+}
+
+/// Emits `tokens` followed by a `UriDisplay<Path>` implementation and its
+/// `FromUriParam` companions, unless generating `tokens` failed.
+fn with_uri_display(input: proc_macro::TokenStream, tokens: Result<TokenStream>) -> TokenStream {
+    match tokens {
+        Ok(mut tokens) => {
+            tokens.extend(derive_uri_display_path_param(input));
+            tokens
+        }
+        Err(diag) => diag.emit_as_item_tokens()
+    }
+}
+
+pub fn derive_from_param(input: proc_macro::TokenStream) -> TokenStream {
+    let tokens = DeriveGenerator::build_for(input.clone(), quote!(impl<'a> #FromParam<'a>))
+        .support(Support::TupleStruct | Support::Enum | Support::Lifetime | Support::Type)
+        .replace_generic(0, 0)
+        .type_bound_mapper(newtype_bounds(quote!(#FromParam<'a>), "'a"))
+        .validator(ValidatorBuild::new()
+            .input_validate(|_, i| match i.generics().lifetimes().enumerate().last() {
+                Some((i, lt)) if i >= 1 => Err(lt.span().error("only one lifetime is supported")),
+                _ => Ok(())
+            })
+            .struct_validate(|_, data| match data.fields().count() {
+                1 => Ok(()),
+                _ => Err(data.fields().span().error(EXACTLY_ONE_FIELD))
+            })
+            .enum_validate(|_, data| {
+                if data.variants.is_empty() {
+                    return Err(data.span().error("enum must have at least one variant"));
+                }
+
+                if let Some(v) = data.variants().find(|v| !v.fields().is_empty()) {
+                    return Err(v.fields().span().error("variants cannot have fields"));
+                }
+
+                if let Some(d) = first_duplicate(data.variants(), |v| v.form_field_values())? {
+                    let (variant_a_i, variant_a, value_a) = d.0;
+                    let (variant_b_i, variant_b, value_b) = d.1;
+
+                    if variant_a_i == variant_b_i {
+                        return Err(variant_a.error("variant has conflicting values")
+                            .span_note(value_a, "this value...")
+                            .span_note(value_b, "...conflicts with this value"));
+                    }
+
+                    return Err(value_b.error("field value conflicts with previous value")
+                        .span_help(variant_b, "...declared in this variant")
+                        .span_note(variant_a, "previous field with conflicting name"));
+                }
+
+                Ok(())
+            })
+        )
+        .outer_mapper(quote! {
+            #[allow(unused_imports)]
+            use #_http::uncased::AsUncased;
+        })
+        .inner_mapper(MapperBuild::new()
+            .try_enum_map(|mapper, data| {
+                let variant_condition = data.variants()
+                    .map(|v| mapper.map_variant(v))
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(quote! {
+                    type Error = &'a str;
+
+                    fn from_param(__p: &'a str) -> #_Result<Self, Self::Error> {
+                        #(#variant_condition)*
+
+                        #_Err(__p)
+                    }
+                })
+            })
+            .try_variant_map(|_, variant| {
+                let builder = variant.builder(|_| unreachable!("fieldless"));
+                let value = variant.form_field_values()?;
+
+                Ok(quote_spanned! { variant.span() =>
+                    if #(__p.as_uncased() == #value)||* {
+                        return #_Ok(#builder);
+                    }
+                })
+            })
+            .try_fields_map(|_, fields| {
+                let field = fields.iter().next().expect("exactly one field");
+                let ty = field_ty(&field, "'a");
+                let builder = fields.builder(|_| quote!(__v));
+
+                Ok(quote_spanned! { ty.span() =>
+                    type Error = <#ty as #FromParam<'a>>::Error;
+
+                    fn from_param(__p: &'a str) -> #_Result<Self, Self::Error> {
+                        <#ty as #FromParam<'a>>::from_param(__p).map(|__v| #builder)
+                    }
+                })
+            })
+        )
+        .try_to_tokens();
+
+    with_uri_display(input, tokens)
+}
+
+pub fn derive_from_segments(input: proc_macro::TokenStream) -> TokenStream {
+    let tokens = DeriveGenerator::build_for(input.clone(), quote!(impl<'r> #FromSegments<'r>))
+        .support(Support::TupleStruct | Support::Lifetime | Support::Type)
+        .replace_generic(0, 0)
+        .type_bound_mapper(newtype_bounds(quote!(#FromSegments<'r>), "'r"))
+        .validator(ValidatorBuild::new()
+            .input_validate(|_, i| match i.generics().lifetimes().enumerate().last() {
+                Some((i, lt)) if i >= 1 => Err(lt.span().error("only one lifetime is supported")),
+                _ => Ok(())
+            })
+            .struct_validate(|_, data| match data.fields().count() {
+                1 => Ok(()),
+                _ => Err(data.fields().span().error(EXACTLY_ONE_FIELD))
+            })
+        )
+        .inner_mapper(MapperBuild::new()
+            .try_fields_map(|_, fields| {
+                let field = fields.iter().next().expect("exactly one field");
+                let ty = field_ty(&field, "'r");
+                let builder = fields.builder(|_| quote!(__v));
+
+                Ok(quote_spanned! { ty.span() =>
+                    type Error = <#ty as #FromSegments<'r>>::Error;
+
+                    fn from_segments(
+                        __s: #_uri::Segments<'r, #_fmt::Path>
+                    ) -> #_Result<Self, Self::Error> {
+                        <#ty as #FromSegments<'r>>::from_segments(__s).map(|__v| #builder)
+                    }
+                })
+            })
+        )
+        .try_to_tokens();
+
+    with_uri_display(input, tokens)
+}
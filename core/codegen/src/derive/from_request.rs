@@ -0,0 +1,177 @@
+use devise::{*, ext::{TypeExt, SpanDiagnosticExt}};
+use proc_macro2::TokenStream;
+
+use crate::exports::*;
+use crate::syn_ext::{TypeExt as _, GenericsExt as _};
+
+#[derive(Default, FromMeta)]
+struct FieldAttr {
+    header: Option<SpanWrapped<String>>,
+}
+
+impl FieldAttr {
+    fn from_field(field: &Field<'_>) -> Result<FieldAttr> {
+        let attr = FieldAttr::one_from_attrs("request", &field.attrs)?.unwrap_or_default();
+        if let Some(header) = &attr.header {
+            let valid = !header.is_empty() && header.bytes().all(|b| b.is_ascii_graphic());
+            if !valid || header.contains(':') {
+                return Err(header.span.error("invalid header name"));
+            }
+        }
+
+        Ok(attr)
+    }
+}
+
+/// The field's type with its lifetimes replaced with `'r`.
+fn field_ty(field: &Field<'_>) -> syn::Type {
+    field.ty.with_replaced_lifetimes(syn::Lifetime::new("'r", field.ty.span()))
+}
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => p.path.segments.last()?,
+        _ => return None,
+    };
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first()? {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn header_guard(field: &Field<'_>, name: &str) -> TokenStream {
+    let ty = field_ty(field);
+    if let Some(inner) = option_inner(&ty) {
+        return quote_spanned! { ty.span() =>
+            #__req.headers().get_one(#name)
+                .and_then(|__s| <#inner as #FromParam<'r>>::from_param(__s).ok())
+        };
+    }
+
+    quote_spanned! { ty.span() =>
+        match #__req.headers().get_one(#name) {
+            #_Some(__s) => match <#ty as #FromParam<'r>>::from_param(__s) {
+                #_Ok(__v) => __v,
+                #_Err(__e) => {
+                    #_log::warn_!("Header `{}` is invalid: {:?}.", #name, __e);
+                    #_catcher::resolution::Resolve(__e).stash(#__req);
+                    return #Outcome::Failure((#Status::BadRequest, ()));
+                }
+            },
+            #_None => {
+                #_log::warn_!("Header `{}` is missing.", #name);
+                return #Outcome::Failure((#Status::BadRequest, ()));
+            }
+        }
+    }
+}
+
+fn request_guard(field: &Field<'_>) -> TokenStream {
+    let ty = field_ty(field);
+    quote_spanned! { ty.span() =>
+        match #__req.guard::<#ty>().await {
+            #Outcome::Success(__v) => __v,
+            #Outcome::Forward(_) => {
+                #_log::warn_!("Request guard `{}` is forwarding.", stringify!(#ty));
+                return #Outcome::Forward(());
+            },
+            #Outcome::Failure((__c, __e)) => {
+                #_log::warn_!("Request guard `{}` failed: {:?}.", stringify!(#ty), __e);
+                #_catcher::resolution::Resolve(__e).stash(#__req);
+                return #Outcome::Failure((__c, ()));
+            }
+        }
+    }
+}
+
+pub fn derive_from_request(input: proc_macro::TokenStream) -> TokenStream {
+    DeriveGenerator::build_for(input, quote!(impl<'r> #FromRequest<'r>))
+        .support(Support::Struct | Support::Lifetime | Support::Type)
+        .replace_generic(0, 0)
+        .type_bound_mapper(MapperBuild::new()
+            .try_fields_map(|_, fields| {
+                let generic_idents = fields.parent.input().generics().type_idents();
+
+                let mut bounds = vec![];
+                for field in fields.iter() {
+                    let ty = field_ty(&field);
+                    if ty.is_concrete(&generic_idents) {
+                        continue;
+                    }
+
+                    let span = ty.span();
+                    match FieldAttr::from_field(&field)?.header {
+                        Some(_) => match option_inner(&ty) {
+                            Some(inner) => bounds.push(quote_spanned!(span =>
+                                #inner: #FromParam<'r>
+                            )),
+                            None => bounds.push(quote_spanned!(span => #ty: #FromParam<'r>)),
+                        },
+                        None => bounds.push(quote_spanned!(span => #ty: #FromRequest<'r>)),
+                    }
+                }
+
+                Ok(quote!(#(#bounds,)*))
+            })
+        )
+        .validator(ValidatorBuild::new()
+            .input_validate(|_, i| match i.generics().lifetimes().enumerate().last() {
+                Some((i, lt)) if i >= 1 => Err(lt.span().error("only one lifetime is supported")),
+                _ => Ok(())
+            })
+            .fields_validate(|_, fields| match fields.is_empty() {
+                true => Err(fields.span().error("at least one field is required")),
+                false => Ok(())
+            })
+        )
+        .outer_mapper(quote!(#[rocket::async_trait]))
+        .inner_mapper(MapperBuild::new()
+            .with_output(|_, output| quote! {
+                type Error = ();
+
+                async fn from_request(
+                    #__req: &'r #Request<'_>
+                ) -> #_request::Outcome<Self, ()> {
+                    #[allow(unused_imports)]
+                    use #_catcher::resolution::DefaultStash as _;
+
+                    #output
+                }
+            })
+            .try_fields_map(|_, fields| {
+                let mut guards = vec![];
+                for field in fields.iter() {
+                    let ident = syn::Ident::new(&format!("__f{}", field.index), field.span());
+                    let ty = field_ty(&field);
+                    let guard = match FieldAttr::from_field(&field)?.header {
+                        Some(name) => header_guard(&field, &name),
+                        None => request_guard(&field),
+                    };
+
+                    guards.push(quote_spanned!(ty.span() => let #ident: #ty = #guard;));
+                }
+
+                let builder = fields.builder(|f| {
+                    let ident = syn::Ident::new(&format!("__f{}", f.index), f.span());
+                    quote!(#ident)
+                });
+
+                Ok(quote! {
+                    #(#guards)*
+                    #Outcome::Success(#builder)
+                })
+            })
+        )
+        .to_tokens()
+}
@@ -1,5 +1,7 @@
 mod form_field;
 pub mod from_form;
 pub mod from_form_field;
+pub mod from_param;
+pub mod from_request;
 pub mod responder;
 pub mod uri_display;
@@ -112,13 +112,24 @@ pub fn derive_uri_display_query(input: proc_macro::TokenStream) -> TokenStream {
 
 #[allow(non_snake_case)]
 pub fn derive_uri_display_path(input: proc_macro::TokenStream) -> TokenStream {
+    uri_display_path(input, Support::TupleStruct | Support::Type | Support::Lifetime)
+}
+
+/// Like [`derive_uri_display_path()`] but additionally accepts fieldless enums,
+/// which are displayed as their first form field value, for `FromParam`.
+pub(crate) fn derive_uri_display_path_param(input: proc_macro::TokenStream) -> TokenStream {
+    let support = Support::TupleStruct | Support::Enum | Support::Type | Support::Lifetime;
+    uri_display_path(input, support)
+}
+
+fn uri_display_path(input: proc_macro::TokenStream, support: Support) -> TokenStream {
     let uri_display = DeriveGenerator::build_for(input.clone(), quote!(impl #P_URI_DISPLAY))
-        .support(Support::TupleStruct | Support::Type | Support::Lifetime)
+        .support(support)
         .type_bound_mapper(generic_bounds_mapper(P_URI_DISPLAY))
         .validator(ValidatorBuild::new()
-            .fields_validate(|_, fields| match fields.count() {
+            .struct_validate(|_, data| match data.fields().count() {
                 1 => Ok(()),
-                _ => Err(fields.span().error(EXACTLY_ONE_FIELD))
+                _ => Err(data.fields().span().error(EXACTLY_ONE_FIELD))
             })
         )
         .inner_mapper(MapperBuild::new()
@@ -128,6 +139,12 @@ pub fn derive_uri_display_path(input: proc_macro::TokenStream) -> TokenStream {
                     Ok(())
                 }
             })
+            .try_variant_map(|_, variant| {
+                let value = variant.first_form_field_value()?;
+                Ok(quote_spanned! { variant.span() =>
+                    f.write_value(#value)?;
+                })
+            })
             .field_map(|_, field| {
                 let accessor = field.accessor();
                 quote_spanned!(field.span() => f.write_value(&#accessor)?;)
@@ -34,6 +34,9 @@ pub struct Reference<'a>(pub &'a http::uri::Reference<'a>, pub Span);
 #[derive(Debug)]
 pub struct Asterisk(pub http::uri::Asterisk, pub Span);
 
+#[derive(Debug)]
+pub struct HostPattern(pub http::uri::HostPattern, pub Span);
+
 impl FromMeta for Status {
     fn from_meta(meta: &MetaItem) -> Result<Self> {
         let num = usize::from_meta(meta)?;
@@ -110,7 +113,14 @@ impl FromMeta for Method {
         let span = meta.value_span();
         let help_text = format!("method must be one of: {}", VALID_METHODS_STR);
 
-        if let MetaItem::Path(path) = meta {
+        // Accept both a naked `GET` and a key-value `method = GET`.
+        let path = match meta {
+            MetaItem::Path(path) => Some(path.clone()),
+            MetaItem::KeyValue { .. } => meta.parse_value::<syn::Path>("method").ok(),
+            _ => None,
+        };
+
+        if let Some(path) = path {
             if let Some(ident) = path.last_ident() {
                 let method = ident.to_string().parse()
                     .map_err(|_| span.error("invalid HTTP method").help(&*help_text))?;
@@ -147,6 +157,23 @@ impl ToTokens for Method {
     }
 }
 
+impl FromMeta for HostPattern {
+    fn from_meta(meta: &MetaItem) -> Result<Self> {
+        let span = meta.value_span();
+        http::uri::HostPattern::parse(&String::from_meta(meta)?)
+            .map(|pattern| HostPattern(pattern, span))
+            .map_err(|e| span.error(format!("invalid host pattern: {}", e))
+                .help("expected a host pattern like \"api.example.com\" or \"*.example.com\""))
+    }
+}
+
+impl ToTokens for HostPattern {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let pattern = self.0.to_string();
+        tokens.extend(quote_spanned!(self.1 => #pattern));
+    }
+}
+
 impl<T: ToTokens> ToTokens for Optional<T> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         use crate::exports::{_Some, _None};
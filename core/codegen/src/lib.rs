@@ -152,17 +152,32 @@ macro_rules! route_attribute {
         ///
         /// segment := URI_SEG
         ///          | SINGLE_PARAM
+        ///          | CONSTRAINED_PARAM
         ///          | TRAILING_PARAM
         ///
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'host' '=' '"' HOST_PATTERN '"'
+        ///            | 'deprecated'
+        ///            | 'sunset' '=' '"' DATE '"'
+        ///            | 'tags' '=' '[' ('"' STRING '"'),* ']'
+        ///            | 'summary' '=' '"' STRING '"'
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
+        /// CONSTRAINED_PARAM := '<' IDENT ':' CONSTRAINT '>'
         /// TRAILING_PARAM := '<' IDENT '..>'
         ///
+        /// CONSTRAINT := INTEGER_TYPE | 'uuid' | REGEX
+        /// INTEGER_TYPE := 'u8' | 'u16' | 'u32' | 'u64' | 'u128' | 'usize'
+        ///               | 'i8' | 'i16' | 'i32' | 'i64' | 'i128' | 'isize'
+        /// REGEX := regular expression without '/' or '?'
+        ///
         /// URI_SEG := valid, non-percent-encoded HTTP URI segment
         /// MEDIA_TYPE := valid HTTP media type or known shorthand
+        /// HOST_PATTERN := '.'-separated domain labels, each possibly '*'
+        /// DATE := date of the form YYYY-MM-DD
+        /// STRING := any string
         ///
         /// INTEGER := unsigned integer, as defined by Rust
         /// IDENT := valid identifier, as defined by Rust
@@ -206,6 +221,26 @@ macro_rules! route_attribute {
         /// | query    | `<ident..>` | [`FromForm`]      |
         /// | data     | `<ident>`   | [`FromData`]      |
         ///
+        /// A path parameter with a constraint (`CONSTRAINED_PARAM`) only
+        /// matches segments that satisfy the constraint: values of an integer
+        /// type, UUIDs, or strings that a regular expression matches in their
+        /// entirety. A `uuid` constraint fails to compile without Rocket's
+        /// `uuid` feature. The router uses
+        /// constraints both to match requests and to prove that routes don't
+        /// collide. If the constraint is an integer type or `uuid`, the
+        /// argument's type must be that type (`Uuid` for `uuid`), optionally
+        /// wrapped in an `Option` or `Result`:
+        ///
+        /// ```rust
+        /// # #[macro_use] extern crate rocket;
+        /// // These routes don't collide: a `u64` never begins with a letter.
+        /// #[get("/item/<id:u64>")]
+        /// fn item_by_id(id: u64) { /* .. */ }
+        ///
+        /// #[get("/item/<slug:[a-z][a-z0-9-]*>")]
+        /// fn item_by_slug(slug: &str) { /* .. */ }
+        /// ```
+        ///
         /// The type of each function argument that _does not_ have a
         /// corresponding dynamic parameter is required to implement the
         /// [`FromRequest`] trait.
@@ -258,9 +293,11 @@ macro_rules! route_attribute {
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
-        ///      generated handler.
+        ///      with the name (the function's name), path, query, rank,
+        ///      format, host pattern, deprecation, sunset date, tags, and
+        ///      summary from the route attribute, and with the function's doc
+        ///      comment, if any. A `sunset` date requires `deprecated`. The
+        ///      handler is set to the generated handler.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
         ///      [`Origin`].
@@ -314,9 +351,44 @@ route_attribute!(options => Method::Options);
 /// The grammar for the `#[catch]` attributes is defined as:
 ///
 /// ```text
-/// catch := STATUS | 'default'
+/// catch := (STATUS | 'default') (',' param)*
+///
+/// param := error | format | method
 ///
 /// STATUS := valid HTTP status code (integer in [200, 599])
+/// METHOD := 'GET' | 'PUT' | 'POST' | 'DELETE' | 'HEAD' | 'PATCH' | 'OPTIONS'
+/// MEDIA_TYPE := valid HTTP media type or known shorthand
+///
+/// error := 'error' '=' '"<' IDENT '>"'
+/// format := 'format' '=' '"' MEDIA_TYPE '"'
+/// method := 'method' '=' METHOD
+/// ```
+///
+/// A catcher with a `format` only handles errors for requests whose preferred
+/// `Accept` media type matches `format`, or that have no `Accept` header. A
+/// catcher with a `method` only handles errors for requests with that method.
+/// This allows API and browser clients under the same base to receive
+/// different error responses:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::response::content::{Html, Json};
+///
+/// #[catch(404, format = "json")]
+/// fn json_not_found(req: &Request) -> Json<String> {
+///     Json(format!("{{ \"error\": \"{} not found\" }}", req.uri()))
+/// }
+///
+/// #[catch(404, format = "html")]
+/// fn html_not_found(req: &Request) -> Html<String> {
+///     Html(format!("<p>Sorry, {} does not exist.</p>", req.uri()))
+/// }
+///
+/// #[catch(405, method = POST)]
+/// fn post_not_allowed() -> &'static str {
+///     "this resource can't be created"
+/// }
 /// ```
 ///
 /// # Typing Requirements
@@ -328,6 +400,26 @@ route_attribute!(options => Method::Options);
 ///   * `fn(`[`&Request`]`) -> R`
 ///   * `fn(`[`Status`]`, `[`&Request`]`) -> R`
 ///
+/// If `error = "<ident>"` is present, the function must additionally take an
+/// argument named `ident`, in any position, of type `T` or `&T`. The catcher
+/// is then _typed_: it only handles errors caused by a failing request or data
+/// guard whose error value is of type `T`, up to lifetimes. Such values are
+/// those of types implementing [`TypedError`], [`form::Errors`], and
+/// [`json::Error`]. Errors that borrow from the request are stashed in an
+/// owned form; the argument's type may borrow from the stashed value.
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::form;
+///
+/// #[catch(422, error = "<errors>")]
+/// fn invalid_form(errors: &form::Errors<'_>, req: &Request) -> String {
+///     let fields: Vec<_> = errors.iter().filter_map(|e| e.name.as_ref()).collect();
+///     format!("invalid fields in {}: {:?}", req.uri(), fields)
+/// }
+/// ```
+///
 /// # Semantics
 ///
 /// The attribute generates two items:
@@ -335,19 +427,27 @@ route_attribute!(options => Method::Options);
 ///   1. An error [`Handler`].
 ///
 ///      The generated handler calls the decorated function, passing in the
-///      [`Status`] and [`&Request`] values if requested. The returned value is
-///      used to generate a [`Response`] via the type's [`Responder`]
-///      implementation.
+///      [`Status`] and [`&Request`] values if requested. A typed catcher's
+///      handler first takes the guard's error value out of the request with
+///      [`Request::take_error()`]. The returned value is used to generate a
+///      [`Response`] via the type's [`Responder`] implementation.
 ///
 ///   2. A static structure used by [`catchers!`] to generate a [`Catcher`].
 ///
 ///      The static structure (and resulting [`Catcher`]) is populated with the
-///      name (the function's name) and status code from the route attribute or
-///      `None` if `default`. The handler is set to the generated handler.
+///      name (the function's name), status code from the route attribute or
+///      `None` if `default`, and error type, format, and method, if any. The
+///      handler is set to the generated handler. When a typed catcher's error
+///      type, or a catcher's format or method, doesn't match the request,
+///      routing falls through to the next catcher.
 ///
 /// [`&Request`]: ../rocket/struct.Request.html
 /// [`Status`]: ../rocket/http/struct.Status.html
 /// [`Handler`]: ../rocket/catcher/trait.Handler.html
+/// [`TypedError`]: ../rocket/catcher/trait.TypedError.html
+/// [`form::Errors`]: ../rocket/form/struct.Errors.html
+/// [`json::Error`]: ../rocket/serde/json/enum.Error.html
+/// [`Request::take_error()`]: ../rocket/struct.Request.html#method.take_error
 /// [`catchers!`]: macro.catchers.html
 /// [`Catcher`]: ../rocket/struct.Catcher.html
 /// [`Response`]: ../rocket/struct.Response.html
@@ -746,6 +846,168 @@ pub fn derive_from_form(input: TokenStream) -> TokenStream {
     emit!(derive::from_form::derive_from_form(input))
 }
 
+/// Derive for the [`FromRequest`] trait.
+///
+/// The [`FromRequest`] derive can be applied to structures with named or
+/// unnamed fields, each of which is a request guard or, with a `request`
+/// attribute, a request header:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::State;
+/// use rocket::http::CookieJar;
+/// use rocket::http::uri::Host;
+///
+/// struct Db;
+///
+/// #[derive(FromRequest)]
+/// struct Context<'r> {
+///     host: &'r Host<'r>,
+///     cookies: &'r CookieJar<'r>,
+///     db: &'r State<Db>,
+///     #[request(header = "X-Api-Key")]
+///     api_key: &'r str,
+///     #[request(header = "X-Page")]
+///     page: Option<usize>,
+/// }
+///
+/// #[get("/")]
+/// fn index(context: Context<'_>) { /* .. */ }
+/// ```
+///
+/// The derive generates an implementation of the [`FromRequest`] trait with an
+/// `Error` of `()`. The implementation resolves each field, in the order the
+/// fields are declared, and succeeds only when all fields do:
+///
+///   * A field without an attribute is resolved with [`Request::guard()`]. Its
+///     type must implement [`FromRequest`].
+///   * A field with a `header` attribute is parsed from the first value of the
+///     named header with [`FromParam`], which its type must implement. If the
+///     header is missing or fails to parse, the guard fails with a status of
+///     `400 Bad Request`. If the field's type is an `Option<T>`, `T` must
+///     implement `FromParam` instead, and the field is `None` when the header
+///     is missing or fails to parse.
+///
+/// As soon as a field forwards or fails, the implementation stops and returns
+/// the same outcome. A failing field's error value is stashed on the request,
+/// as if the field had been a request guard of the route, so that typed
+/// catchers can receive it.
+///
+/// ## Syntax
+///
+/// The derive accepts one field attribute: `request`, with the following
+/// syntax:
+///
+/// ```text
+/// request := 'header' '=' '"' HEADER_NAME '"'
+///
+/// HEADER_NAME := valid HTTP header name
+/// ```
+///
+/// ## Generics
+///
+/// The derive accepts any number of type generics and at most one lifetime
+/// generic, which is replaced with the request lifetime `'r`. For each field
+/// whose type contains a type generic, a bound of `FromRequest<'r>` or, for
+/// header fields, `FromParam<'r>` is added.
+///
+/// [`FromRequest`]: ../rocket/request/trait.FromRequest.html
+/// [`FromParam`]: ../rocket/request/trait.FromParam.html
+/// [`Request::guard()`]: ../rocket/request/struct.Request.html#method.guard
+#[proc_macro_derive(FromRequest, attributes(request))]
+pub fn derive_from_request(input: TokenStream) -> TokenStream {
+    emit!(derive::from_request::derive_from_request(input))
+}
+
+/// Derive for the [`FromParam`] trait.
+///
+/// The [`FromParam`] derive can be applied to enums whose variants have no
+/// fields and to tuple structs with exactly one field, also known as
+/// newtypes:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[derive(FromParam)]
+/// enum Format {
+///     Json,
+///     #[field(value = "yml")]
+///     #[field(value = "yaml")]
+///     Yaml,
+/// }
+///
+/// #[derive(FromParam)]
+/// struct Id(usize);
+///
+/// #[derive(FromParam)]
+/// struct Name<'r>(&'r str);
+///
+/// #[get("/<name>/<id>/<format>")]
+/// fn item(name: Name<'_>, id: Id, format: Format) { /* .. */ }
+/// ```
+///
+/// For enums, the derive generates an implementation of the [`FromParam`]
+/// trait that parses a variant when the parameter is case-insensitively equal
+/// to the name of the variant or, if the variant has one or more `field`
+/// attributes, to any of the `value`s in the attributes. In the example above,
+/// `"JSON"` parses as `Format::Json` while `"YAML"` and `"yml"` parse as
+/// `Format::Yaml`. The `field` attribute and its semantics are identical to
+/// those of [`FromFormField`](derive.FromFormField.html), so the same
+/// attributes can be used to derive both traits. The implementation's `Error`
+/// is `&str`: the parameter that failed to parse.
+///
+/// For newtypes, the field's type must implement [`FromParam`], and the
+/// derived implementation delegates to it, wrapping the parsed value. The
+/// implementation's `Error` is that of the field's type. The derive accepts at
+/// most one lifetime generic, which is replaced with the parameter lifetime,
+/// and any number of type generics. For each field whose type contains a type
+/// generic, a bound of `FromParam` is added.
+///
+/// Alongside [`FromParam`], the derive generates an implementation of
+/// [`UriDisplay<Path>`] so that the type can be used with `uri!`. Enums are
+/// displayed as the first `value` of the variant or, without a `field`
+/// attribute, as the name of the variant. Newtypes are displayed as their
+/// field, as with [`UriDisplayPath`](derive.UriDisplayPath.html), which thus
+/// must not be derived as well.
+///
+/// [`FromParam`]: ../rocket/request/trait.FromParam.html
+/// [`UriDisplay<Path>`]: ../rocket/http/uri/fmt/trait.UriDisplay.html
+#[proc_macro_derive(FromParam, attributes(field))]
+pub fn derive_from_param(input: TokenStream) -> TokenStream {
+    emit!(derive::from_param::derive_from_param(input))
+}
+
+/// Derive for the [`FromSegments`] trait.
+///
+/// The [`FromSegments`] derive can be applied to tuple structs with exactly
+/// one field whose type implements [`FromSegments`]:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use std::path::PathBuf;
+///
+/// #[derive(FromSegments)]
+/// struct Asset(PathBuf);
+///
+/// #[get("/assets/<asset..>")]
+/// fn asset(asset: Asset) { /* .. */ }
+/// ```
+///
+/// The derive generates an implementation of the [`FromSegments`] trait that
+/// delegates to the field's type, wrapping the parsed value. The
+/// implementation's `Error` is that of the field's type. Generics are handled
+/// as they are by the [`FromParam`](derive.FromParam.html) derive.
+///
+/// As with [`FromParam`](derive.FromParam.html), the derive also generates an
+/// implementation of [`UriDisplay<Path>`], which requires the field's type to
+/// implement [`UriDisplay<Path>`].
+///
+/// [`FromSegments`]: ../rocket/request/trait.FromSegments.html
+/// [`UriDisplay<Path>`]: ../rocket/http/uri/fmt/trait.UriDisplay.html
+#[proc_macro_derive(FromSegments)]
+pub fn derive_from_segments(input: TokenStream) -> TokenStream {
+    emit!(derive::from_param::derive_from_segments(input))
+}
+
 /// Derive for the [`Responder`] trait.
 ///
 /// The [`Responder`] derive can be applied to enums and structs with named
@@ -1372,6 +1634,29 @@ pub fn catchers(input: TokenStream) -> TokenStream {
 /// assert_eq!(bob.to_string(), "/person/Bob?woo#bam");
 /// ```
 ///
+/// ### Hosts
+///
+/// If the route declares a `host` pattern, the host of a string literal prefix
+/// is checked against the pattern, and `uri!` fails to compile if the route
+/// would not match requests to the prefix's host:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[get("/status", host = "api.*")]
+/// fn status() { }
+///
+/// let status = uri!("https://api.rocket.rs", status());
+/// assert_eq!(status.to_string(), "https://api.rocket.rs/status");
+/// ```
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate rocket;
+/// # #[get("/status", host = "api.*")]
+/// # fn status() { }
+/// // error: URI prefix host `rocket.rs` does not match route host `api.*`
+/// let status = uri!("https://rocket.rs", status());
+/// ```
+///
 /// ## Grammar
 ///
 /// The grammar for this variant of the `uri!` macro is:
@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::request::{FromParam, FromSegments};
+
+#[derive(Debug, PartialEq, FromParam)]
+enum Format {
+    Json,
+    #[field(value = "yml")]
+    #[field(value = "yaml")]
+    Yaml,
+    #[field(value = "text")]
+    PlainText,
+}
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Id(u8);
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Name<'r>(&'r str);
+
+#[derive(Debug, PartialEq, FromParam)]
+struct Wrapper<T>(T);
+
+#[derive(Debug, PartialEq, FromSegments)]
+struct Asset(PathBuf);
+
+#[get("/<name>/<id>/<format>")]
+fn item(name: Name<'_>, id: Id, format: Format) -> String {
+    format!("{} {} {:?}", name.0, id.0, format)
+}
+
+#[get("/assets/<asset..>")]
+fn asset(asset: Asset) -> String {
+    asset.0.display().to_string()
+}
+
+#[test]
+fn test_from_param() {
+    assert_eq!(Format::from_param("json"), Ok(Format::Json));
+    assert_eq!(Format::from_param("JSON"), Ok(Format::Json));
+    assert_eq!(Format::from_param("yml"), Ok(Format::Yaml));
+    assert_eq!(Format::from_param("YAML"), Ok(Format::Yaml));
+    assert_eq!(Format::from_param("Text"), Ok(Format::PlainText));
+    assert_eq!(Format::from_param("plaintext"), Err("plaintext"));
+    assert_eq!(Format::from_param("xml"), Err("xml"));
+
+    assert_eq!(Id::from_param("7"), Ok(Id(7)));
+    assert!(Id::from_param("700").is_err());
+    assert_eq!(Name::from_param("bob"), Ok(Name("bob")));
+    assert_eq!(Wrapper::<bool>::from_param("true"), Ok(Wrapper(true)));
+    assert_eq!(Wrapper::<bool>::from_param("yes"), Err("yes"));
+
+    let segments = rocket::http::uri::Origin::parse("/a/b").unwrap();
+    let asset = Asset::from_segments(segments.path().segments());
+    assert_eq!(asset, Ok(Asset(PathBuf::from("a/b"))));
+}
+
+#[test]
+fn test_routing() {
+    let rocket = rocket::build().mount("/", routes![item, asset]);
+    let client = Client::debug(rocket).unwrap();
+
+    let response = client.get("/bob/7/YML").dispatch();
+    assert_eq!(response.into_string().unwrap(), "bob 7 Yaml");
+
+    let response = client.get("/assets/css/style.css").dispatch();
+    assert_eq!(response.into_string().unwrap(), "css/style.css");
+
+    assert_eq!(client.get("/bob/700/json").dispatch().status(), Status::NotFound);
+    assert_eq!(client.get("/bob/7/xml").dispatch().status(), Status::NotFound);
+}
+
+#[test]
+fn test_uri_display() {
+    assert_eq!(uri!(item(Name("bob"), Id(7), Format::Json)), "/bob/7/Json");
+    assert_eq!(uri!(item(Name("a b"), Id(1), Format::Yaml)), "/a%20b/1/yml");
+    assert_eq!(uri!(item(Name("a"), Id(1), &Format::PlainText)), "/a/1/text");
+    assert_eq!(uri!(asset(Asset("css/style.css".into()))), "/assets/css/style.css");
+}
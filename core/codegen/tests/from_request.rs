@@ -0,0 +1,146 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, State};
+use rocket::catcher::TypedError;
+use rocket::http::{Header, Status, CookieJar, Cookie};
+use rocket::local::blocking::Client;
+use rocket::request::{self, FromRequest};
+
+struct Db(&'static str);
+
+#[derive(Debug)]
+struct Locked;
+
+impl TypedError for Locked { }
+
+struct Unlocked;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Unlocked {
+    type Error = Locked;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Locked> {
+        match req.headers().contains("X-Locked") {
+            true => request::Outcome::Failure((Status::Locked, Locked)),
+            false => request::Outcome::Success(Unlocked),
+        }
+    }
+}
+
+#[derive(FromRequest)]
+struct Context<'r> {
+    cookies: &'r CookieJar<'r>,
+    db: &'r State<Db>,
+    #[request(header = "X-Api-Key")]
+    api_key: &'r str,
+    #[request(header = "X-Page")]
+    page: Option<usize>,
+    _unlocked: Unlocked,
+}
+
+#[derive(FromRequest)]
+struct Count(#[request(header = "X-Count")] u8);
+
+#[get("/")]
+fn index(ctxt: Context<'_>) -> String {
+    let user = ctxt.cookies.get("user").map(|c| c.value().to_string());
+    format!("{:?} {} {} {:?}", user, ctxt.db.0, ctxt.api_key, ctxt.page)
+}
+
+#[get("/count")]
+fn count(count: Count) -> String {
+    count.0.to_string()
+}
+
+#[get("/count", rank = 2)]
+fn no_count() -> &'static str {
+    "no count"
+}
+
+#[catch(423, error = "<e>")]
+fn locked(e: &Locked) -> String {
+    format!("{:?}", e)
+}
+
+fn client() -> Client {
+    let rocket = rocket::build()
+        .manage(Db("db"))
+        .mount("/", routes![index, count, no_count])
+        .register("/", catchers![locked]);
+
+    Client::debug(rocket).unwrap()
+}
+
+#[test]
+fn test_composite_guard() {
+    let client = client();
+
+    let response = client.get("/")
+        .header(Header::new("X-Api-Key", "key"))
+        .header(Header::new("X-Page", "3"))
+        .cookie(Cookie::new("user", "bob"))
+        .dispatch();
+
+    let expected = r#"Some("bob") db key Some(3)"#;
+    assert_eq!(response.into_string().unwrap(), expected);
+
+    let response = client.get("/")
+        .header(Header::new("X-Api-Key", "key"))
+        .header(Header::new("X-Page", "three"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "None db key None");
+}
+
+#[test]
+fn test_field_failures() {
+    let client = client();
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // The first failing field's outcome and error are propagated.
+    let response = client.get("/")
+        .header(Header::new("X-Api-Key", "key"))
+        .header(Header::new("X-Locked", "1"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Locked);
+    assert_eq!(response.into_string().unwrap(), "Locked");
+
+    let response = client.get("/count").header(Header::new("X-Count", "7")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "7");
+
+    let response = client.get("/count").header(Header::new("X-Count", "700")).dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+}
+
+#[test]
+fn test_forwarding() {
+    struct Never;
+
+    #[rocket::async_trait]
+    impl<'r> FromRequest<'r> for Never {
+        type Error = ();
+
+        async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+            request::Outcome::Forward(())
+        }
+    }
+
+    #[derive(FromRequest)]
+    struct Forwards<'r> {
+        _cookies: &'r CookieJar<'r>,
+        _never: Never,
+    }
+
+    #[get("/")]
+    fn forwards(_f: Forwards<'_>) { }
+
+    #[get("/", rank = 2)]
+    fn fallback() -> &'static str { "fallback" }
+
+    let rocket = rocket::build().mount("/", routes![forwards, fallback]);
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(client.get("/").dispatch().into_string().unwrap(), "fallback");
+}
@@ -0,0 +1,81 @@
+use std::io;
+use std::sync::Arc;
+use std::net::SocketAddr;
+
+use rustls::{ClientConfig, Certificate, RootCertStore, TLSError};
+use rustls::{ServerCertVerifier, ServerCertVerified};
+use tokio_rustls::{TlsConnector as Connector, client::TlsStream, webpki::DNSNameRef};
+use tokio::net::TcpStream;
+
+use crate::tls::util::{load_certs, load_private_key};
+
+/// A TLS connector over TCP to a server with a known certificate chain.
+pub struct TlsConnector {
+    connector: Connector,
+}
+
+pub struct ConnectorConfig<R> {
+    pub cert_chain: R,
+    pub identity: Option<(R, R)>,
+}
+
+/// Accepts exactly one end-entity certificate, irrespective of server name.
+struct PinnedCertVerifier(Certificate);
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        _: &RootCertStore,
+        presented_certs: &[Certificate],
+        _: DNSNameRef<'_>,
+        _: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        match presented_certs.first() {
+            Some(cert) if cert == &self.0 => Ok(ServerCertVerified::assertion()),
+            _ => Err(TLSError::General("server presented an unknown certificate".into())),
+        }
+    }
+}
+
+impl TlsConnector {
+    pub fn new<R>(mut c: ConnectorConfig<R>) -> io::Result<TlsConnector>
+        where R: io::BufRead
+    {
+        let server_cert = load_certs(&mut c.cert_chain)
+            .map_err(|e| {
+                let msg = format!("malformed TLS certificate chain: {}", e);
+                io::Error::new(e.kind(), msg)
+            })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "empty TLS certificate chain"))?;
+
+        let mut tls_config = ClientConfig::new();
+        tls_config.set_protocols(&[b"http/1.1".to_vec()]);
+        tls_config.dangerous().set_certificate_verifier(Arc::new(PinnedCertVerifier(server_cert)));
+
+        if let Some((ref mut certs, ref mut key)) = c.identity {
+            let cert_chain = load_certs(certs).map_err(|e| {
+                let msg = format!("malformed client certificate chain: {}", e);
+                io::Error::new(e.kind(), msg)
+            })?;
+
+            let key = load_private_key(key).map_err(|e| {
+                let msg = format!("malformed client private key: {}", e);
+                io::Error::new(e.kind(), msg)
+            })?;
+
+            tls_config.set_single_client_cert(cert_chain, key)
+                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        }
+
+        Ok(TlsConnector { connector: Connector::from(Arc::new(tls_config)) })
+    }
+
+    pub async fn connect(&self, addr: SocketAddr) -> io::Result<TlsStream<TcpStream>> {
+        // The name is irrelevant: the server's certificate is pinned.
+        let domain = DNSNameRef::try_from_ascii_str("localhost").expect("valid DNS name");
+        let stream = TcpStream::connect(addr).await?;
+        self.connector.connect(domain, stream).await
+    }
+}
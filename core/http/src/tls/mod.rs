@@ -1,4 +1,5 @@
 mod listener;
+mod connector;
 mod util;
 
 #[cfg(feature = "mtls")]
@@ -6,3 +7,4 @@ pub mod mtls;
 
 pub use rustls;
 pub use listener::{TlsListener, Config};
+pub use connector::{TlsConnector, ConnectorConfig};
@@ -0,0 +1,197 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::uri::Host;
+
+/// A pattern matching the domain of a [`Host`], used for host-based routing.
+///
+/// A `HostPattern` is a `.`-separated sequence of labels. Each label is either
+/// a literal, which matches the same label case-insensitively, or the wildcard
+/// `*`, which matches one or more labels. The port of a host, if any, is
+/// ignored.
+///
+/// ```text
+/// api.example.com       matches  api.example.com, API.example.com:8000
+/// *.example.com         matches  api.example.com, a.b.example.com
+/// api.*                 matches  api.example.com, api.localhost
+/// ```
+///
+/// A wildcard never matches zero labels: `*.example.com` does not match
+/// `example.com`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::uri::{Host, HostPattern};
+///
+/// let pattern = HostPattern::parse("*.example.com").expect("valid pattern");
+/// assert!(pattern.matches(&Host::new(uri!("api.example.com"))));
+/// assert!(pattern.matches(&Host::new(uri!("WWW.Example.com:8000"))));
+/// assert!(pattern.matches(&Host::new(uri!("a.b.example.com"))));
+/// assert!(!pattern.matches(&Host::new(uri!("example.com"))));
+///
+/// // Empty labels and ports are invalid.
+/// assert!(HostPattern::parse("api..example.com").is_err());
+/// assert!(HostPattern::parse("example.com:8000").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostPattern(String);
+
+impl HostPattern {
+    /// Parses `pattern` as a `HostPattern`. Returns an error message if any
+    /// label in `pattern` is empty or is neither `*` nor a sequence of ASCII
+    /// alphanumerics, `-`, and `_`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("API.*").expect("valid pattern");
+    /// assert_eq!(pattern.to_string(), "api.*");
+    ///
+    /// assert!(HostPattern::parse("").is_err());
+    /// assert!(HostPattern::parse("api.*com").is_err());
+    /// ```
+    pub fn parse(pattern: &str) -> Result<HostPattern, String> {
+        for label in pattern.split('.') {
+            if label.is_empty() {
+                return Err(format!("host pattern '{}' contains an empty label", pattern));
+            }
+
+            let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+            if label != "*" && !label.chars().all(valid) {
+                return Err(format!("invalid label '{}' in host pattern '{}'", label, pattern));
+            }
+        }
+
+        Ok(HostPattern(pattern.to_ascii_lowercase()))
+    }
+
+    /// Returns an iterator over the labels in `self`, from left to right. A
+    /// wildcard label is returned as `*`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("*.example.com").expect("valid pattern");
+    /// assert_eq!(pattern.labels().collect::<Vec<_>>(), ["*", "example", "com"]);
+    /// ```
+    pub fn labels(&self) -> impl Iterator<Item = &str> + Clone {
+        self.0.split('.')
+    }
+
+    /// Returns `true` if `self` matches the domain of `host`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::uri::{Host, HostPattern};
+    ///
+    /// let pattern = HostPattern::parse("api.*").expect("valid pattern");
+    /// assert!(pattern.matches(&Host::new(uri!("api.example.com:443"))));
+    /// assert!(!pattern.matches(&Host::new(uri!("www.example.com"))));
+    /// ```
+    pub fn matches(&self, host: &Host<'_>) -> bool {
+        self.matches_domain(host.domain().as_str())
+    }
+
+    /// Returns `true` if `self` matches `domain`. A single trailing `.` in
+    /// `domain`, denoting a fully qualified domain name, is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::http::uri::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("*.example.com").expect("valid pattern");
+    /// assert!(pattern.matches_domain("www.example.com."));
+    /// assert!(!pattern.matches_domain("www.example.org"));
+    /// ```
+    pub fn matches_domain(&self, domain: &str) -> bool {
+        fn glob(pattern: &[&str], labels: &[&str]) -> bool {
+            match pattern.split_first() {
+                Some((&"*", rest)) => (1..=labels.len()).any(|n| glob(rest, &labels[n..])),
+                Some((literal, rest)) => match labels.split_first() {
+                    Some((label, labels)) => label.eq_ignore_ascii_case(literal)
+                        && glob(rest, labels),
+                    None => false,
+                },
+                None => labels.is_empty(),
+            }
+        }
+
+        let domain = domain.strip_suffix('.').unwrap_or(domain);
+        let labels: Vec<&str> = domain.split('.').collect();
+        if labels.iter().any(|label| label.is_empty()) {
+            return false;
+        }
+
+        glob(&self.labels().collect::<Vec<_>>(), &labels)
+    }
+}
+
+impl FromStr for HostPattern {
+    type Err = String;
+
+    #[inline]
+    fn from_str(pattern: &str) -> Result<HostPattern, String> {
+        HostPattern::parse(pattern)
+    }
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HostPattern;
+
+    fn matches(pattern: &str, domain: &str) -> bool {
+        HostPattern::parse(pattern).expect("valid pattern").matches_domain(domain)
+    }
+
+    #[test]
+    fn test_host_pattern_parse() {
+        for valid in &["localhost", "api.example.com", "*", "*.*", "*.example.com", "a-b_c.1"] {
+            assert!(HostPattern::parse(valid).is_ok(), "{} should be valid", valid);
+        }
+
+        for invalid in &["", ".", "a.", ".a", "a..b", "a.com:80", "a*.com", "a b.com", "[::1]"] {
+            assert!(HostPattern::parse(invalid).is_err(), "{} should be invalid", invalid);
+        }
+    }
+
+    #[test]
+    fn test_host_pattern_matches() {
+        assert!(matches("example.com", "example.com"));
+        assert!(matches("example.com", "EXAMPLE.com"));
+        assert!(matches("EXAMPLE.com", "example.com."));
+        assert!(matches("*.example.com", "api.example.com"));
+        assert!(matches("api.*", "api.localhost"));
+        assert!(matches("*", "localhost"));
+        assert!(matches("*", "a.b"));
+        assert!(matches("*.*", "a.b"));
+        assert!(matches("*.example.com", "a.b.example.com"));
+        assert!(matches("api.*", "api.example.com"));
+        assert!(matches("a.*.d", "a.b.c.d"));
+
+        assert!(!matches("example.com", "example.org"));
+        assert!(!matches("example.com", "api.example.com"));
+        assert!(!matches("*.example.com", "example.com"));
+        assert!(!matches("*.example.com", "example.com.org"));
+        assert!(!matches("*.*", "a"));
+        assert!(!matches("a.*.d", "a.d"));
+        assert!(!matches("*", ""));
+        assert!(!matches("*", "a..b"));
+        assert!(!matches("*.com", ".com"));
+        assert!(!matches("api.*", "api"));
+    }
+}
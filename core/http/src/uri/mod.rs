@@ -10,6 +10,7 @@ mod segments;
 mod path_query;
 mod asterisk;
 mod host;
+mod host_pattern;
 
 pub mod error;
 pub mod fmt;
@@ -26,3 +27,4 @@ pub use self::reference::*;
 pub use self::path_query::*;
 pub use self::asterisk::*;
 pub use self::host::*;
+pub use self::host_pattern::*;
@@ -1,10 +1,11 @@
 use std::fmt;
+use std::any::Any;
 use std::io::Cursor;
 
 use crate::response::Response;
 use crate::request::Request;
-use crate::http::{Status, ContentType, uri};
-use crate::catcher::{Handler, BoxFuture};
+use crate::http::{Status, ContentType, MediaType, Method, uri};
+use crate::catcher::{Handler, BoxFuture, ErrorType};
 
 use yansi::Paint;
 
@@ -35,6 +36,20 @@ use yansi::Paint;
 ///
 ///  * It is a default catcher _or_ has a status code matching the error code.
 ///  * Its base is a prefix of the normalized/decoded request URI path.
+///  * It is untyped _or_ its error type is the type of the error value of the
+///    guard that failed, if any.
+///  * It has no format _or_ its format matches the preferred media type of
+///    the request's `Accept` header, if there is one.
+///  * It has no method _or_ its method is the request's method.
+///
+/// A _typed_ catcher is a catcher generated by [`catch`] with an `error`
+/// parameter. When a typed catcher's error type, a catcher's format, or a
+/// catcher's method doesn't match, routing falls through to the next catcher.
+///
+/// Among catchers matching an error with the same base and status code, typed
+/// catchers are preferred, then those with a format, then those with a method.
+/// If several remain, such as catchers for different formats when the request
+/// has no `Accept` header or accepts `*/*`, the one registered first is used.
 ///
 /// A _default_ catcher is a catcher with no explicit status code: `None`. The
 /// catcher's _base_ is provided as the first argument to
@@ -43,7 +58,9 @@ use yansi::Paint;
 /// # Collisions
 ///
 /// Two catchers are said to _collide_ if there exists an error that matches
-/// both catchers. Colliding catchers present a routing ambiguity and are thus
+/// both catchers and no rule above to determine which of the two to use: they
+/// have the same base, status code, error type, method, and overlapping
+/// formats. Colliding catchers present a routing ambiguity and are thus
 /// disallowed by Rocket. Because catchers can be constructed dynamically,
 /// collision checking is done at [`ignite`](crate::Rocket::ignite()) time,
 /// after it becomes statically impossible to register any more catchers on an
@@ -96,6 +113,19 @@ use yansi::Paint;
 ///   * `fn(`[`&Request`]`) -> R`
 ///   * `fn(`[`Status`]`, `[`&Request`]`) -> R`
 ///
+/// Additionally, a typed catcher takes the failing guard's error value as an
+/// argument named by the `error` parameter:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form;
+///
+/// #[catch(422, error = "<errors>")]
+/// fn invalid_form(errors: &form::Errors<'_>) -> String {
+///     format!("{} form errors", errors.len())
+/// }
+/// ```
+///
 /// See the [`catch`] documentation for full details.
 ///
 /// [`catch`]: crate::catch
@@ -115,6 +145,17 @@ pub struct Catcher {
 
     /// The catcher's associated error handler.
     pub handler: Box<dyn Handler>,
+
+    /// The media type the request's preferred `Accept` type must match, if
+    /// any.
+    pub format: Option<MediaType>,
+
+    /// The method the request's method must match, if any.
+    pub method: Option<Method>,
+
+    /// The type of guard error value this catcher receives, if it is typed.
+    /// See [`Catcher::typed()`].
+    pub error: Option<ErrorType>,
 }
 
 impl Catcher {
@@ -166,10 +207,41 @@ impl Catcher {
             name: None,
             base: uri::Origin::ROOT,
             handler: Box::new(handler),
+            error: None,
+            format: None,
+            method: None,
             code,
         }
     }
 
+    /// Makes this catcher typed with error type `T`. A typed catcher only
+    /// matches errors whose stashed guard error value is of type `T`. Its
+    /// handler can take the value with
+    /// [`Request::take_error()`](crate::Request::take_error()).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    ///
+    /// use rocket::request::Request;
+    /// use rocket::catcher::{Catcher, BoxFuture};
+    /// use rocket::response::Responder;
+    /// use rocket::http::Status;
+    ///
+    /// fn handle_io<'r>(status: Status, req: &'r Request<'_>) -> BoxFuture<'r> {
+    ///    let error = req.take_error::<io::Error>();
+    ///    let res = (status, format!("I/O error: {:?}", error));
+    ///    Box::pin(async move { res.respond_to(req) })
+    /// }
+    ///
+    /// let catcher = Catcher::new(500, handle_io).typed::<io::Error>();
+    /// ```
+    pub fn typed<T: Any + Send>(mut self) -> Self {
+        self.error = Some(ErrorType::of::<T>());
+        self
+    }
+
     /// Maps the `base` of this catcher using `mapper`, returning a new
     /// `Catcher` with the returned base.
     ///
@@ -236,6 +308,12 @@ pub struct StaticInfo {
     pub code: Option<u16>,
     /// The catcher's handler, i.e, the annotated function.
     pub handler: for<'r> fn(Status, &'r Request<'_>) -> BoxFuture<'r>,
+    /// The type of the catcher's error parameter, if any.
+    pub error: Option<ErrorType>,
+    /// The catcher's format, if any.
+    pub format: Option<MediaType>,
+    /// The catcher's method, if any.
+    pub method: Option<Method>,
 }
 
 #[doc(hidden)]
@@ -244,6 +322,9 @@ impl From<StaticInfo> for Catcher {
     fn from(info: StaticInfo) -> Catcher {
         let mut catcher = Catcher::new(info.code, info.handler);
         catcher.name = Some(info.name.into());
+        catcher.error = info.error;
+        catcher.format = info.format;
+        catcher.method = info.method;
         catcher
     }
 }
@@ -254,14 +335,28 @@ impl fmt::Display for Catcher {
             write!(f, "{}{}{} ", Paint::cyan("("), Paint::white(n), Paint::cyan(")"))?;
         }
 
+        if let Some(ref method) = self.method {
+            write!(f, "{} ", Paint::green(method))?;
+        }
+
         if self.base.path() != "/" {
             write!(f, "{} ", Paint::green(self.base.path()))?;
         }
 
         match self.code {
-            Some(code) => write!(f, "{}", Paint::blue(code)),
-            None => write!(f, "{}", Paint::blue("default"))
+            Some(code) => write!(f, "{}", Paint::blue(code))?,
+            None => write!(f, "{}", Paint::blue("default"))?,
+        }
+
+        if let Some(ref format) = self.format {
+            write!(f, " {}", Paint::yellow(format))?;
+        }
+
+        if let Some(ref error) = self.error {
+            write!(f, " {}", Paint::yellow(error.name))?;
         }
+
+        Ok(())
     }
 }
 
@@ -271,6 +366,9 @@ impl fmt::Debug for Catcher {
             .field("name", &self.name)
             .field("base", &self.base)
             .field("code", &self.code)
+            .field("error", &self.error.map(|e| e.name))
+            .field("format", &self.format)
+            .field("method", &self.method)
             .finish()
     }
 }
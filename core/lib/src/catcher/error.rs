@@ -0,0 +1,203 @@
+use std::any::{Any, TypeId};
+
+use crate::form;
+
+/// Trait implemented by guard error types that typed catchers can receive.
+///
+/// When a request or data guard generated by a route attribute, or a
+/// [`Scope`](crate::route::Scope) guard, fails with an error of a type
+/// implementing `TypedError`, Rocket stashes the error value on the request.
+/// A catcher that declares an `error` parameter of that type then receives
+/// the value. See [`catch`](crate::catch) for details.
+///
+/// Rocket implements `TypedError` for [`std::io::Error`] and, with the
+/// `msgpack` feature enabled, for [`msgpack::Error`](crate::serde::msgpack::Error).
+/// Rocket's [`form::Errors`] and [`json::Error`](crate::serde::json::Error),
+/// which borrow from the request, are also stashed in an owned form.
+///
+/// `TypedError` is a marker trait. Only types that are `'static` and `Send`
+/// can implement it. Errors of route guards whose error types don't implement
+/// `TypedError` are discarded as before.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{self, Request, FromRequest};
+/// use rocket::catcher::TypedError;
+/// use rocket::http::Status;
+///
+/// #[derive(Debug)]
+/// enum ApiKeyError {
+///     Missing,
+///     Invalid,
+/// }
+///
+/// impl TypedError for ApiKeyError { }
+///
+/// struct ApiKey;
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for ApiKey {
+///     type Error = ApiKeyError;
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+///         match req.headers().get_one("x-api-key") {
+///             None => request::Outcome::Failure((Status::BadRequest, ApiKeyError::Missing)),
+///             Some("valid") => request::Outcome::Success(ApiKey),
+///             Some(_) => request::Outcome::Failure((Status::BadRequest, ApiKeyError::Invalid)),
+///         }
+///     }
+/// }
+///
+/// #[catch(400, error = "<e>")]
+/// fn bad_key(e: &ApiKeyError) -> String {
+///     format!("Bad API key: {:?}.", e)
+/// }
+/// ```
+pub trait TypedError: Send + 'static { }
+
+impl TypedError for std::io::Error { }
+
+#[cfg(feature = "msgpack")]
+impl TypedError for crate::serde::msgpack::Error { }
+
+/// The type of error value a typed catcher receives.
+///
+/// A typed catcher only handles errors whose stashed guard error value is of
+/// this type. See [`Catcher::typed()`](crate::Catcher::typed()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorType {
+    /// The `TypeId` of the error value's type.
+    pub id: TypeId,
+    /// The name of the error value's type, for display.
+    pub name: &'static str,
+}
+
+impl ErrorType {
+    /// Returns the `ErrorType` of error values of type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::any::TypeId;
+    /// use rocket::catcher::ErrorType;
+    ///
+    /// let error = ErrorType::of::<std::io::Error>();
+    /// assert_eq!(error.id, TypeId::of::<std::io::Error>());
+    /// ```
+    pub fn of<T: Any>() -> ErrorType {
+        ErrorType { id: TypeId::of::<T>(), name: std::any::type_name::<T>() }
+    }
+}
+
+#[doc(hidden)]
+pub mod resolution {
+    use crate::request::Request;
+    use super::*;
+
+    /// Converts a guard error into the `'static` value that is stashed.
+    pub trait IntoTyped {
+        type Typed: Any + Send;
+
+        fn into_typed(self) -> Self::Typed;
+    }
+
+    impl<T: TypedError> IntoTyped for T {
+        type Typed = T;
+
+        fn into_typed(self) -> T {
+            self
+        }
+    }
+
+    impl<'v> IntoTyped for form::Errors<'v> {
+        type Typed = form::Errors<'static>;
+
+        fn into_typed(self) -> Self::Typed {
+            crate::http::ext::IntoOwned::into_owned(self)
+        }
+    }
+
+    #[cfg(feature = "json")]
+    impl<'a> IntoTyped for crate::serde::json::Error<'a> {
+        type Typed = JsonError;
+
+        fn into_typed(self) -> Self::Typed {
+            use crate::serde::json::Error;
+
+            match self {
+                Error::Io(e) => JsonError::Io(Some(e)),
+                Error::Parse(raw, e) => JsonError::Parse(raw.to_string(), Some(e)),
+            }
+        }
+    }
+
+    /// The stashed, owned form of a [`json::Error`](crate::serde::json::Error).
+    /// Typed catchers receive a `json::Error` borrowing the raw data from it.
+    #[cfg(feature = "json")]
+    pub enum JsonError {
+        Io(Option<std::io::Error>),
+        Parse(String, Option<serde_json::Error>),
+    }
+
+    /// Converts a stashed value into the error value a typed catcher receives,
+    /// which may borrow from the stashed value. The inverse of [`IntoTyped`].
+    pub trait FromTyped<'a>: Sized {
+        type Typed: Any + Send;
+
+        fn from_typed(typed: &'a mut Option<Self::Typed>) -> Option<Self>;
+    }
+
+    impl<'a, T: TypedError> FromTyped<'a> for T {
+        type Typed = T;
+
+        fn from_typed(typed: &'a mut Option<T>) -> Option<T> {
+            typed.take()
+        }
+    }
+
+    impl<'a> FromTyped<'a> for form::Errors<'static> {
+        type Typed = form::Errors<'static>;
+
+        fn from_typed(typed: &'a mut Option<Self::Typed>) -> Option<Self> {
+            typed.take()
+        }
+    }
+
+    #[cfg(feature = "json")]
+    impl<'a> FromTyped<'a> for crate::serde::json::Error<'a> {
+        type Typed = JsonError;
+
+        fn from_typed(typed: &'a mut Option<JsonError>) -> Option<Self> {
+            use crate::serde::json::Error;
+
+            match typed.as_mut()? {
+                JsonError::Io(e) => e.take().map(Error::Io),
+                JsonError::Parse(raw, e) => e.take().map(move |e| Error::Parse(raw, e)),
+            }
+        }
+    }
+
+    /// The *magic*, as in `sentinel::resolution`.
+    ///
+    /// `Resolve(e).stash(req)` for `E: IntoTyped` stashes `e` on `req`.
+    /// `Resolve(e).stash(req)` for `E: !IntoTyped` is `DefaultStash::stash`,
+    /// which drops `e`. This _must_ be called with method syntax on a concrete
+    /// type for resolution to work. Unlike a `T: Any` bound, an `IntoTyped`
+    /// bound never imposes a `'static` requirement on borrowed errors.
+    pub struct Resolve<E>(pub E);
+
+    impl<E: IntoTyped> Resolve<E> {
+        pub fn stash(self, req: &Request<'_>) {
+            req.set_error(self.0.into_typed());
+        }
+    }
+
+    /// Fallback trait for errors that can't be stashed.
+    pub trait DefaultStash: Sized {
+        fn stash(self, _: &Request<'_>) { }
+    }
+
+    impl<E> DefaultStash for Resolve<E> { }
+}
@@ -6,7 +6,7 @@ use figment::value::{Map, Dict, magic::RelativePathBuf};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{LogLevel, Shutdown, Ident};
+use crate::config::{LogLevel, Shutdown, Ident, Unreachable};
 use crate::request::{self, Request, FromRequest};
 use crate::data::Limits;
 
@@ -100,6 +100,9 @@ pub struct Config {
     /// Whether to use colors and emoji when logging. **(default: `true`)**
     #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
     pub cli_colors: bool,
+    /// How to report routes, catchers, and mount points detected to never
+    /// handle a request. **(default: [`Unreachable::Warn`])**
+    pub unreachable: Unreachable,
     /// PRIVATE: This structure may grow (but never change otherwise) in a
     /// non-breaking release. As such, constructing this structure should
     /// _always_ be done using a public constructor or update syntax:
@@ -178,6 +181,7 @@ impl Config {
             shutdown: Shutdown::default(),
             log_level: LogLevel::Normal,
             cli_colors: true,
+            unreachable: Unreachable::Warn,
             __non_exhaustive: (),
         }
     }
@@ -382,6 +386,7 @@ impl Config {
         launch_info_!("shutdown: {}", bold(&self.shutdown));
         launch_info_!("log level: {}", bold(self.log_level));
         launch_info_!("cli colors: {}", bold(&self.cli_colors));
+        launch_info_!("unreachable: {}", bold(self.unreachable));
 
         // Check for now depreacted config values.
         for (key, replacement) in Self::DEPRECATED_KEYS {
@@ -470,6 +475,9 @@ impl Config {
 
     /// The stringy parameter name for setting/extracting [`Config::cli_colors`].
     pub const CLI_COLORS: &'static str = "cli_colors";
+
+    /// The stringy parameter name for setting/extracting [`Config::unreachable`].
+    pub const UNREACHABLE: &'static str = "unreachable";
 }
 
 impl Provider for Config {
@@ -114,6 +114,7 @@
 mod ident;
 mod config;
 mod shutdown;
+mod unreachable;
 
 #[cfg(feature = "tls")]
 mod tls;
@@ -126,6 +127,7 @@ pub use config::pretty_print_error;
 pub use config::Config;
 pub use crate::log::LogLevel;
 pub use shutdown::Shutdown;
+pub use unreachable::Unreachable;
 pub use ident::Ident;
 
 #[cfg(feature = "tls")]
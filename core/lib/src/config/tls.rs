@@ -625,7 +625,7 @@ mod with_tls_feature {
     use std::fs;
     use std::io::{self, Error};
 
-    use crate::http::tls::Config;
+    use crate::http::tls::{Config, ConnectorConfig};
     use crate::http::tls::rustls::SupportedCipherSuite as RustlsCipher;
     use crate::http::tls::rustls::ciphersuite as rustls;
 
@@ -672,6 +672,23 @@ mod with_tls_feature {
             })
         }
 
+        /// This is only called when TLS is enabled. Configures a connector
+        /// that trusts this server, optionally presenting a client `identity`
+        /// as a PEM-encoded certificate chain and private key.
+        pub(crate) fn to_connector_config(
+            &self,
+            identity: Option<(&[u8], &[u8])>
+        ) -> io::Result<ConnectorConfig<Reader>> {
+            let from_bytes = |bytes: &[u8]| -> Reader {
+                Box::new(io::Cursor::new(bytes.to_vec()))
+            };
+
+            Ok(ConnectorConfig {
+                cert_chain: to_reader(&self.certs)?,
+                identity: identity.map(|(certs, key)| (from_bytes(certs), from_bytes(key))),
+            })
+        }
+
         fn rustls_ciphers(&self) -> impl Iterator<Item = &'static RustlsCipher> + '_ {
             self.ciphers().map(|ciphersuite| match ciphersuite {
                 CipherSuite::TLS_CHACHA20_POLY1305_SHA256 =>
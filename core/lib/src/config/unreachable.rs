@@ -0,0 +1,52 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How to report items that can never handle a request at ignition.
+///
+/// When a Rocket instance [ignites](crate::Rocket::ignite()), it looks for
+/// routes that are known to always be preempted by a lower-ranked route,
+/// catchers that only match `HEAD` requests when no `HEAD` route is mounted,
+/// and mount points at which no routes were mounted or every route mounted is
+/// known to be preempted. The checks are
+/// conservative: items reported are unreachable, but not every unreachable
+/// item is reported. This value, configured via the `unreachable` parameter,
+/// determines what happens when any are found. See
+/// [`Route`](crate::Route#shadowing) for details.
+///
+/// ## (De)serialization
+///
+/// An `Unreachable` variant serializes and deserializes as a lowercase string
+/// equal to the name of the variant: `"allow"`, `"warn"`, or `"deny"`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, Unreachable};
+///
+/// let config = Config {
+///     unreachable: Unreachable::Deny,
+///     ..Config::default()
+/// };
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unreachable {
+    /// Unreachable items are ignored.
+    Allow,
+    /// Unreachable items are logged as a warning. This is the default.
+    #[default]
+    Warn,
+    /// Unreachable items are logged as an error and ignition fails.
+    Deny,
+}
+
+impl fmt::Display for Unreachable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unreachable::Allow => "allow".fmt(f),
+            Unreachable::Warn => "warn".fmt(f),
+            Unreachable::Deny => "deny".fmt(f),
+        }
+    }
+}
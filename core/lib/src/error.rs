@@ -83,6 +83,9 @@ pub enum ErrorKind {
     Config(figment::Error),
     /// Route collisions were detected.
     Collisions(crate::router::Collisions),
+    /// Unreachable routes, catchers, or mount points were detected and
+    /// [`Config::unreachable`](crate::Config::unreachable) is `deny`.
+    Unreachable(crate::router::Unreachable),
     /// Launch fairing(s) failed.
     FailedFairings(Vec<crate::fairing::Info>),
     /// Sentinels requested abort.
@@ -145,6 +148,7 @@ impl fmt::Display for ErrorKind {
             ErrorKind::Bind(e) => write!(f, "binding failed: {}", e),
             ErrorKind::Io(e) => write!(f, "I/O error: {}", e),
             ErrorKind::Collisions(_) => "collisions detected".fmt(f),
+            ErrorKind::Unreachable(_) => "unreachable items detected".fmt(f),
             ErrorKind::FailedFairings(_) => "launch fairing(s) failed".fmt(f),
             ErrorKind::Runtime(e) => write!(f, "runtime error: {}", e),
             ErrorKind::InsecureSecretKey(_) => "insecure secret key config".fmt(f),
@@ -204,6 +208,11 @@ impl Drop for Error {
                 info_!("Note: Route collisions can usually be resolved by ranking routes.");
                 panic!("routing collisions detected");
             }
+            ErrorKind::Unreachable(ref unreachable) => {
+                error!("Rocket failed to launch due to the following unreachable items:");
+                unreachable.pretty_print();
+                panic!("unreachable items detected");
+            }
             ErrorKind::FailedFairings(ref failures) => {
                 error!("Rocket failed to launch due to failing fairings:");
                 for fairing in failures {
@@ -1,9 +1,9 @@
 use std::path::{PathBuf, Path};
 
 use crate::{Request, Data};
-use crate::http::{Method, uri::Segments, ext::IntoOwned};
+use crate::http::{Method, ContentType, uri::Segments, ext::IntoOwned};
 use crate::route::{Route, Handler, Outcome};
-use crate::response::Redirect;
+use crate::response::{self, Redirect, Responder};
 use crate::fs::NamedFile;
 
 /// Custom handler for serving static files.
@@ -178,6 +178,38 @@ impl FileServer {
         self.rank = rank;
         self
     }
+
+    /// Serves the file at `path` or, if [`Options::Precompressed`] is set, its
+    /// best precompressed sibling acceptable to the client.
+    async fn serve<'r>(&self, req: &'r Request<'_>, data: Data<'r>, path: PathBuf) -> Outcome<'r> {
+        if !self.options.contains(Options::Precompressed) {
+            return Outcome::from_or_forward(req, data, NamedFile::open(path).await.ok());
+        }
+
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => {},
+            _ => return Outcome::forward(data),
+        }
+
+        let content_type = path.extension()
+            .and_then(|ext| ContentType::from_extension(&ext.to_string_lossy()));
+
+        let accept_encoding = req.headers().get("Accept-Encoding").collect::<Vec<_>>().join(",");
+        for encoding in Precompressed::preferred(&accept_encoding) {
+            let mut sibling = path.clone().into_os_string();
+            sibling.push(".");
+            sibling.push(encoding.extension);
+            if let Ok(file) = NamedFile::open(sibling).await {
+                let file = Precompressed { file, content_type, encoding: Some(encoding.name) };
+                return Outcome::from(req, file);
+            }
+        }
+
+        let file = NamedFile::open(path).await.ok()
+            .map(|file| Precompressed { file, content_type, encoding: None });
+
+        Outcome::from_or_forward(req, data, file)
+    }
 }
 
 impl From<FileServer> for Vec<Route> {
@@ -203,8 +235,7 @@ impl Handler for FileServer {
             };
 
             if segments.is_empty() {
-                let file = NamedFile::open(&self.root).await.ok();
-                return Outcome::from_or_forward(req, data, file);
+                return self.serve(req, data, self.root.clone()).await;
             } else {
                 return Outcome::forward(data);
             }
@@ -231,15 +262,86 @@ impl Handler for FileServer {
                     return Outcome::forward(data);
                 }
 
-                let index = NamedFile::open(p.join("index.html")).await.ok();
-                Outcome::from_or_forward(req, data, index)
+                self.serve(req, data, p.join("index.html")).await
             },
-            Some(p) => Outcome::from_or_forward(req, data, NamedFile::open(p).await.ok()),
+            Some(p) => self.serve(req, data, p).await,
             None => Outcome::forward(data),
         }
     }
 }
 
+/// A content coding with precompressed siblings servable by [`FileServer`].
+struct Encoding {
+    /// The name of the coding in `Accept-Encoding` and `Content-Encoding`.
+    name: &'static str,
+    /// The extension, appended to a file's name, of its precompressed sibling.
+    extension: &'static str,
+}
+
+/// A file, possibly precompressed, served with [`Options::Precompressed`].
+struct Precompressed {
+    file: NamedFile,
+    /// The `Content-Type` implied by the extension of the uncompressed file.
+    content_type: Option<ContentType>,
+    /// The `Content-Encoding` of `file`, if it's precompressed.
+    encoding: Option<&'static str>,
+}
+
+impl Precompressed {
+    /// The supported encodings in order of preference when equally acceptable.
+    const ENCODINGS: &'static [Encoding] = &[
+        Encoding { name: "br", extension: "br" },
+        Encoding { name: "gzip", extension: "gz" },
+    ];
+
+    /// Returns the supported encodings acceptable according to the value of
+    /// the `Accept-Encoding` header `accept`, most preferred first.
+    fn preferred(accept: &str) -> Vec<&'static Encoding> {
+        // Returns the quality of `coding` in `accept`, if it's mentioned.
+        let quality = |coding: &str| accept.split(',')
+            .map(|item| item.split(';').map(|s| s.trim()))
+            .filter_map(|mut parts| {
+                let name = parts.next()?;
+                if !name.eq_ignore_ascii_case(coding) {
+                    return None;
+                }
+
+                let q = parts.filter_map(|p| p.strip_prefix("q=").or_else(|| p.strip_prefix("Q=")))
+                    .next()
+                    .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+
+                Some(q)
+            })
+            .next();
+
+        let mut encodings = Self::ENCODINGS.iter()
+            .filter_map(|e| Some((e, quality(e.name).or_else(|| quality("*"))?)))
+            .filter(|(_, q)| *q > 0.0)
+            .collect::<Vec<_>>();
+
+        // The sort is stable: ties are broken by the order in `ENCODINGS`.
+        encodings.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        encodings.into_iter().map(|(e, _)| e).collect()
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Precompressed {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.file.respond_to(req)?;
+        if let Some(encoding) = self.encoding {
+            response.remove_header("Content-Type");
+            if let Some(content_type) = self.content_type {
+                response.set_header(content_type);
+            }
+
+            response.set_raw_header("Content-Encoding", encoding);
+        }
+
+        response.adjoin_raw_header("Vary", "Accept-Encoding");
+        Ok(response)
+    }
+}
+
 /// A bitset representing configurable options for [`FileServer`].
 ///
 /// The valid options are:
@@ -251,6 +353,8 @@ impl Handler for FileServer {
 ///   * [`Options::Missing`] - Don't fail if the path to serve is missing.
 ///   * [`Options::NormalizeDirs`] - Redirect directories without a trailing
 ///     slash to ones with a trailing slash.
+///   * [`Options::Precompressed`] - Serve precompressed `.br` and `.gz`
+///     siblings of files to clients that accept them.
 ///
 /// `Options` structures can be `or`d together to select two or more options.
 /// For instance, to request that both dot files and index pages be returned,
@@ -353,6 +457,49 @@ impl Options {
     /// prevent inevitable 404 errors. This option overrides that.
     pub const Missing: Options = Options(1 << 4);
 
+    /// Serve precompressed siblings of files when the client accepts them.
+    ///
+    /// When enabled, a request for a file `${path}` is served with the
+    /// Brotli-compressed `${path}.br` or the gzip-compressed `${path}.gz`, if
+    /// it exists, when the request's `Accept-Encoding` header accepts `br` or
+    /// `gzip`, respectively. The encoding with the higher quality is chosen,
+    /// with `br` preferred on a tie. The response's `Content-Encoding` is set
+    /// accordingly and its `Content-Type` is set according to the extension
+    /// of `${path}`, not that of the sibling. If no acceptable sibling exists,
+    /// `${path}` itself is served. Either way, the response's `Vary` header
+    /// includes `Accept-Encoding`.
+    ///
+    /// Siblings are only considered for files that themselves exist.
+    ///
+    /// **Disabled by default.**
+    ///
+    /// # Example
+    ///
+    /// Given the following directory structure...
+    ///
+    /// ```text
+    /// static/
+    /// ├── app.js
+    /// ├── app.js.br
+    /// └── app.js.gz
+    /// ```
+    ///
+    /// ...a request for `/app.js` with `Accept-Encoding: gzip, br` is served
+    /// with `app.js.br`, `Content-Encoding: br`, and `Content-Type:
+    /// application/javascript`:
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::fs::{FileServer, Options};
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     let options = Options::Index | Options::Precompressed;
+    ///     rocket::build().mount("/", FileServer::new("static", options))
+    /// }
+    /// ```
+    pub const Precompressed: Options = Options(1 << 5);
+
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the options in `other` are also in `self`.
     ///
@@ -2,9 +2,10 @@ use std::fmt;
 use std::convert::TryInto;
 
 use crate::{Request, Data};
-use crate::http::{Status, Method};
+use crate::http::{Status, Method, CookieJar};
 use crate::http::uri::Origin;
 
+use crate::local::redirect::{self, Follow, Redirection};
 use super::{Client, LocalResponse};
 
 /// An `async` local request as returned by [`Client`](super::Client).
@@ -37,6 +38,7 @@ pub struct LocalRequest<'c> {
     data: Vec<u8>,
     // The `Origin` on the right is INVALID! It should _not_ be used!
     uri: Result<Origin<'c>, Origin<'static>>,
+    max_redirects: usize,
 }
 
 impl<'c> LocalRequest<'c> {
@@ -60,7 +62,7 @@ impl<'c> LocalRequest<'c> {
             })
         }
 
-        LocalRequest { client, request, uri: try_origin, data: vec![] }
+        LocalRequest { client, request, uri: try_origin, data: vec![], max_redirects: 0 }
     }
 
     pub(crate) fn _request(&self) -> &Request<'c> {
@@ -75,8 +77,17 @@ impl<'c> LocalRequest<'c> {
         &mut self.data
     }
 
+    pub(crate) fn _max_redirects_mut(&mut self) -> &mut usize {
+        &mut self.max_redirects
+    }
+
+    // Dispatches the request, following up to `max_redirects` redirects.
+    async fn _dispatch(self) -> LocalResponse<'c> {
+        redirect::dispatch(self, Self::_dispatch_once).await
+    }
+
     // Performs the actual dispatch.
-    async fn _dispatch(mut self) -> LocalResponse<'c> {
+    async fn _dispatch_once(mut self) -> LocalResponse<'c> {
         // First, revalidate the URI, returning an error response (generated
         // from an error catcher) immediately if it's invalid. If it's valid,
         // then `request` already contains a correct URI.
@@ -124,6 +135,28 @@ impl<'c> LocalRequest<'c> {
         use rocket::local::asynchronous::LocalRequest;" async await);
 }
 
+impl<'c> Follow<'c> for LocalRequest<'c> {
+    fn request(&self) -> &Request<'c> {
+        &self.request
+    }
+
+    fn max_redirects(&self) -> usize {
+        self.max_redirects
+    }
+
+    fn follow(self, redirection: &Redirection, cookies: &CookieJar<'_>) -> Self {
+        let location = redirection.hop.location().clone();
+        let mut next = LocalRequest::new(self.client, redirection.method, location);
+        redirection.carry_over(&self.request, &mut next.request, cookies);
+        next.max_redirects = self.max_redirects;
+        if redirection.keep_body {
+            next.data = self.data;
+        }
+
+        next
+    }
+}
+
 impl<'c> Clone for LocalRequest<'c> {
     fn clone(&self) -> Self {
         LocalRequest {
@@ -131,6 +164,7 @@ impl<'c> Clone for LocalRequest<'c> {
             request: self.request.clone(),
             data: self.data.clone(),
             uri: self.uri.clone(),
+            max_redirects: self.max_redirects,
         }
     }
 }
@@ -2,10 +2,13 @@ use std::io;
 use std::future::Future;
 use std::{pin::Pin, task::{Context, Poll}};
 
-use tokio::io::{AsyncRead, ReadBuf};
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+use tokio::time::Duration;
 
 use crate::http::CookieJar;
 use crate::{Request, Response};
+use crate::response::stream::{Event, EventParser};
+use crate::local::Hop;
 
 /// An `async` response from a dispatched [`LocalRequest`](super::LocalRequest).
 ///
@@ -51,11 +54,24 @@ use crate::{Request, Response};
 /// # rocket::async_test(read_body_manually()).expect("read okay");
 /// ```
 ///
+/// Infinite or long-lived bodies, such as those of [`EventStream`] and
+/// [`TextStream`] responses, can be read incrementally with
+/// [`next_chunk()`](LocalResponse::next_chunk()) and, for server-sent events,
+/// [`next_event()`](LocalResponse::next_event()) or
+/// [`events()`](LocalResponse::events()), optionally bounded by a
+/// [read timeout](LocalResponse::set_read_timeout()).
+///
+/// [`EventStream`]: crate::response::stream::EventStream
+/// [`TextStream`]: crate::response::stream::TextStream
+///
 /// For more, see [the top-level documentation](../index.html#localresponse).
 pub struct LocalResponse<'c> {
     _request: Box<Request<'c>>,
     response: Response<'c>,
     cookies: CookieJar<'c>,
+    parser: EventParser,
+    read_timeout: Option<Duration>,
+    redirects: Vec<Hop>,
 }
 
 impl<'c> LocalResponse<'c> {
@@ -93,7 +109,14 @@ impl<'c> LocalResponse<'c> {
                 cookies.add_original(cookie.into_owned());
             }
 
-            LocalResponse { cookies, _request: boxed_req, response, }
+            LocalResponse {
+                cookies,
+                _request: boxed_req,
+                response,
+                parser: EventParser::default(),
+                read_timeout: None,
+                redirects: vec![],
+            }
         }
     }
 }
@@ -107,12 +130,120 @@ impl LocalResponse<'_> {
         &self.cookies
     }
 
+    pub(crate) fn _redirects(&self) -> &[Hop] {
+        &self.redirects
+    }
+
+    pub(crate) fn with_redirects(mut self, redirects: Vec<Hop>) -> Self {
+        self.redirects = redirects;
+        self
+    }
+
     pub(crate) async fn _into_string(mut self) -> io::Result<String> {
-        self.response.body_mut().to_string().await
+        let buffered = self.parser.take_buffered();
+        if buffered.is_empty() {
+            return self.response.body_mut().to_string().await;
+        }
+
+        let bytes = self._into_bytes_after(buffered).await?;
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
     }
 
     pub(crate) async fn _into_bytes(mut self) -> io::Result<Vec<u8>> {
-        self.response.body_mut().to_bytes().await
+        let buffered = self.parser.take_buffered();
+        self._into_bytes_after(buffered).await
+    }
+
+    // Reads the rest of the body, appending it to the `buffered` bytes which
+    // were read, but not consumed, by `_next_event()`.
+    async fn _into_bytes_after(mut self, mut buffered: Vec<u8>) -> io::Result<Vec<u8>> {
+        if buffered.is_empty() {
+            return self.response.body_mut().to_bytes().await;
+        }
+
+        buffered.extend(self.response.body_mut().to_bytes().await?);
+        Ok(buffered)
+    }
+
+    pub(crate) fn _set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
+    }
+
+    pub(crate) async fn _next_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let buffered = self.parser.take_buffered();
+        if !buffered.is_empty() {
+            return Ok(Some(buffered));
+        }
+
+        let timeout = self.read_timeout;
+        let chunk = timed(timeout, self.read_chunk()).await?;
+        Ok(Some(chunk).filter(|chunk| !chunk.is_empty()))
+    }
+
+    pub(crate) async fn _next_event(&mut self) -> io::Result<Option<Event>> {
+        let timeout = self.read_timeout;
+        timed(timeout, async move {
+            loop {
+                if let Some(event) = self.parser.next(false) {
+                    return Ok(Some(event));
+                }
+
+                let chunk = self.read_chunk().await?;
+                if chunk.is_empty() {
+                    return Ok(self.parser.next(true));
+                }
+
+                self.parser.push(&chunk);
+            }
+        }).await
+    }
+
+    // Reads at most one chunk from the body. Returns an empty `Vec` on EOF.
+    async fn read_chunk(&mut self) -> io::Result<Vec<u8>> {
+        let body = self.response.body_mut();
+        let mut chunk = Vec::with_capacity(body.max_chunk_size());
+        body.read_buf(&mut chunk).await?;
+        Ok(chunk)
+    }
+
+    /// Returns a stream of the server-sent events in the body of `self`.
+    ///
+    /// Each item is the result of calling
+    /// [`next_event()`](LocalResponse::next_event()); as such, reads from the
+    /// stream are subject to the [read
+    /// timeout](LocalResponse::set_read_timeout()). The stream ends when the
+    /// body does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::local::asynchronous::Client;
+    /// use rocket::response::stream::{Event, EventStream};
+    /// use rocket::futures::stream::StreamExt;
+    ///
+    /// #[get("/")]
+    /// fn events() -> EventStream![] {
+    ///     EventStream! {
+    ///         yield Event::data("hi").event("greeting");
+    ///         yield Event::data("bye");
+    ///     }
+    /// }
+    ///
+    /// # rocket::async_test(async {
+    /// let rocket = rocket::build().mount("/", routes![events]);
+    /// let client = Client::tracked(rocket).await.unwrap();
+    /// let mut response = client.get("/").dispatch().await;
+    ///
+    /// let events: Vec<_> = response.events().map(|e| e.unwrap()).collect().await;
+    /// assert_eq!(events, [Event::data("hi").event("greeting"), Event::data("bye")]);
+    /// # });
+    /// ```
+    pub fn events(&mut self) -> futures::stream::BoxStream<'_, io::Result<Event>> {
+        Box::pin(futures::stream::unfold(self, |response| async move {
+            let event = response._next_event().await.transpose()?;
+            Some((event, response))
+        }))
     }
 
     #[cfg(feature = "json")]
@@ -135,7 +266,6 @@ impl LocalResponse<'_> {
               F: FnOnce(&mut dyn io::Read) -> T + Send + 'static
     {
         use tokio::sync::mpsc;
-        use tokio::io::AsyncReadExt;
 
         struct ChanReader {
             last: Option<io::Cursor<Vec<u8>>>,
@@ -190,12 +320,33 @@ impl LocalResponse<'_> {
         use rocket::local::asynchronous::LocalResponse;" async await);
 }
 
+/// Bounds `future` by `timeout`, if there is one, failing with an error of
+/// kind [`io::ErrorKind::TimedOut`] if it elapses.
+async fn timed<T, F>(timeout: Option<Duration>, future: F) -> io::Result<T>
+    where F: Future<Output = io::Result<T>>
+{
+    match timeout {
+        Some(duration) => tokio::time::timeout(duration, future).await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "response read timed out"))?,
+        None => future.await,
+    }
+}
+
 impl AsyncRead for LocalResponse<'_> {
     fn poll_read(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
+        // Bytes read by, but not consumed by, `next_event()` come first.
+        let buffered = self.parser.take_buffered();
+        if !buffered.is_empty() {
+            let n = std::cmp::min(buf.remaining(), buffered.len());
+            buf.put_slice(&buffered[..n]);
+            self.parser.push(&buffered[n..]);
+            return Poll::Ready(Ok(()));
+        }
+
         Pin::new(self.response.body_mut()).poll_read(cx, buf)
     }
 }
@@ -57,6 +57,10 @@ impl<'c> LocalRequest<'c> {
         self.inner._body_mut()
     }
 
+    fn _max_redirects_mut(&mut self) -> &mut usize {
+        self.inner._max_redirects_mut()
+    }
+
     fn _dispatch(self) -> LocalResponse<'c> {
         let inner = self.client.block_on(self.inner.dispatch());
         LocalResponse { inner, client: self.client }
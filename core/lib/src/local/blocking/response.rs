@@ -2,6 +2,7 @@ use std::io;
 use tokio::io::AsyncReadExt;
 
 use crate::{Response, local::asynchronous, http::CookieJar};
+use crate::response::stream::Event;
 
 use super::Client;
 
@@ -48,6 +49,16 @@ use super::Client;
 /// # read_body_manually().expect("read okay");
 /// ```
 ///
+/// Infinite or long-lived bodies, such as those of [`EventStream`] and
+/// [`TextStream`] responses, can be read incrementally with
+/// [`next_chunk()`](LocalResponse::next_chunk()) and, for server-sent events,
+/// [`next_event()`](LocalResponse::next_event()) or
+/// [`events()`](LocalResponse::events()), optionally bounded by a
+/// [read timeout](LocalResponse::set_read_timeout()).
+///
+/// [`EventStream`]: crate::response::stream::EventStream
+/// [`TextStream`]: crate::response::stream::TextStream
+///
 /// For more, see [the top-level documentation](../index.html#localresponse).
 pub struct LocalResponse<'c> {
     pub(in super) inner: asynchronous::LocalResponse<'c>,
@@ -63,6 +74,10 @@ impl LocalResponse<'_> {
         self.inner._cookies()
     }
 
+    fn _redirects(&self) -> &[crate::local::Hop] {
+        self.inner._redirects()
+    }
+
     fn _into_string(self) -> io::Result<String> {
         self.client.block_on(self.inner._into_string())
     }
@@ -71,6 +86,54 @@ impl LocalResponse<'_> {
         self.client.block_on(self.inner._into_bytes())
     }
 
+    fn _set_read_timeout(&mut self, timeout: Option<std::time::Duration>) {
+        self.inner._set_read_timeout(timeout)
+    }
+
+    fn _next_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+        self.client.block_on(self.inner._next_chunk())
+    }
+
+    fn _next_event(&mut self) -> io::Result<Option<Event>> {
+        self.client.block_on(self.inner._next_event())
+    }
+
+    /// Returns an iterator over the server-sent events in the body of `self`.
+    ///
+    /// Each item is the result of calling
+    /// [`next_event()`](LocalResponse::next_event()); as such, reads are
+    /// subject to the [read timeout](LocalResponse::set_read_timeout()). The
+    /// iterator ends when the body does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use std::time::Duration;
+    ///
+    /// use rocket::local::blocking::Client;
+    /// use rocket::response::stream::{Event, EventStream};
+    ///
+    /// #[get("/")]
+    /// fn events() -> EventStream![] {
+    ///     EventStream! {
+    ///         for i in 0.. {
+    ///             yield Event::data(i.to_string()).id("counter");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let client = Client::tracked(rocket::build().mount("/", routes![events])).unwrap();
+    /// let mut response = client.get("/").dispatch();
+    /// response.set_read_timeout(Some(Duration::from_secs(5)));
+    ///
+    /// let events: Vec<_> = response.events().take(2).map(|e| e.unwrap()).collect();
+    /// assert_eq!(events, [Event::data("0").id("counter"), Event::data("1").id("counter")]);
+    /// ```
+    pub fn events(&mut self) -> Box<dyn Iterator<Item = io::Result<Event>> + '_> {
+        Box::new(std::iter::from_fn(move || self._next_event().transpose()))
+    }
+
     #[cfg(feature = "json")]
     fn _into_json<T: Send + 'static>(self) -> Option<T>
         where T: serde::de::DeserializeOwned
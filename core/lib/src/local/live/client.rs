@@ -0,0 +1,299 @@
+use std::{fmt, io};
+use std::sync::Arc;
+use std::net::SocketAddr;
+use std::convert::TryInto;
+
+use futures::future::{self, FutureExt};
+use parking_lot::{Mutex, RwLock};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{Rocket, Phase, Orbit, Error};
+use crate::local::live::{LocalRequest, LocalResponse};
+use crate::http::{Method, hyper, uri::Origin, private::cookie};
+
+/// A client that dispatches requests to a live, networked server.
+///
+/// For details, see [the module-level documentation](super).
+///
+/// ## Example
+///
+/// The following snippet creates a `Client` from a `Rocket` instance, which
+/// launches it on an ephemeral port, and dispatches a `POST /` request with a
+/// body of `Hello, world!` over the network.
+///
+/// ```rust,no_run
+/// use rocket::local::live::Client;
+///
+/// # rocket::async_test(async {
+/// let rocket = rocket::build();
+/// let client = Client::tracked(rocket).await.expect("valid rocket");
+/// let response = client.post("/")
+///     .body("Hello, world!")
+///     .dispatch()
+///     .await;
+/// # });
+/// ```
+pub struct Client {
+    rocket: Arc<Rocket<Orbit>>,
+    cookies: RwLock<cookie::CookieJar>,
+    connection: Mutex<Option<SendRequest>>,
+    #[cfg(feature = "tls")]
+    tls: Option<crate::http::tls::TlsConnector>,
+    pub(in super) tracked: bool,
+}
+
+type SendRequest = hyper::client::conn::SendRequest<hyper::Body>;
+
+impl Client {
+    pub(crate) async fn _new<P: Phase>(
+        rocket: Rocket<P>,
+        tracked: bool
+    ) -> Result<Client, Error> {
+        let rocket = rocket.live_launch().await?;
+        let cookies = RwLock::new(cookie::CookieJar::new());
+        let connection = Mutex::new(None);
+
+        #[cfg(feature = "tls")]
+        let tls = match rocket.config().tls {
+            Some(ref tls) if rocket.config().tls_enabled() => {
+                let connector = tls.to_connector_config(None)
+                    .and_then(crate::http::tls::TlsConnector::new)
+                    .map_err(crate::error::ErrorKind::Io)?;
+
+                Some(connector)
+            }
+            _ => None,
+        };
+
+        #[cfg(feature = "tls")]
+        return Ok(Client { rocket, cookies, connection, tls, tracked });
+
+        #[cfg(not(feature = "tls"))]
+        return Ok(Client { rocket, cookies, connection, tracked });
+    }
+
+    // WARNING: This is unstable! Do not use this method outside of Rocket!
+    #[doc(hidden)]
+    pub fn _test<T, F>(f: F) -> T
+        where F: FnOnce(&Self, LocalRequest<'_>, LocalResponse<'_>) -> T + Send
+    {
+        crate::async_test(async {
+            let client = Client::debug(crate::build()).await.unwrap();
+            let request = client.get("/");
+            let response = request.clone().dispatch().await;
+            f(&client, request, response)
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn _rocket(&self) -> &Rocket<Orbit> {
+        &self.rocket
+    }
+
+    #[inline(always)]
+    pub(crate) fn _with_raw_cookies<F, T>(&self, f: F) -> T
+        where F: FnOnce(&cookie::CookieJar) -> T
+    {
+        f(&self.cookies.read())
+    }
+
+    #[inline(always)]
+    pub(crate) fn _with_raw_cookies_mut<F, T>(&self, f: F) -> T
+        where F: FnOnce(&mut cookie::CookieJar) -> T
+    {
+        f(&mut self.cookies.write())
+    }
+
+    #[inline(always)]
+    fn _req<'c, 'u: 'c, U>(&'c self, method: Method, uri: U) -> LocalRequest<'c>
+        where U: TryInto<Origin<'u>> + fmt::Display
+    {
+        LocalRequest::new(self, method, uri)
+    }
+
+    /// The address the server is listening on.
+    pub(crate) fn _addr(&self) -> SocketAddr {
+        let config = self.rocket.config();
+        SocketAddr::new(config.address, config.port)
+    }
+
+    /// Opens a new connection to the server. If `connector` is `Some`, the
+    /// connection uses it in place of the client's default TLS connector.
+    async fn _connect(
+        &self,
+        #[cfg(feature = "tls")]
+        connector: Option<&crate::http::tls::TlsConnector>,
+    ) -> io::Result<SendRequest> {
+        #[cfg(feature = "tls")]
+        if let Some(connector) = connector.or(self.tls.as_ref()) {
+            return handshake(connector.connect(self._addr()).await?).await;
+        }
+
+        handshake(tokio::net::TcpStream::connect(self._addr()).await?).await
+    }
+
+    /// Sends `request` to the server, reusing an idle connection if there is
+    /// one. A request that presents a client `identity` always uses a fresh
+    /// connection, which is closed once the response is received.
+    pub(crate) async fn _send(
+        &self,
+        request: hyper::Request<hyper::Body>,
+        #[cfg(feature = "mtls")]
+        identity: Option<crate::http::tls::TlsConnector>,
+    ) -> io::Result<hyper::Response<hyper::Body>> {
+        #[cfg(feature = "mtls")]
+        if let Some(ref connector) = identity {
+            let mut sender = self._connect(Some(connector)).await?;
+            return sender.send_request(request).await.map_err(hyper_err);
+        }
+
+        // Only reuse the idle connection if it can accept a request right now.
+        // A connection with an unread response in flight is not ready.
+        let idle = self.connection.lock().take()
+            .and_then(|mut sender| {
+                future::poll_fn(|cx| sender.poll_ready(cx))
+                    .now_or_never()
+                    .and_then(|result| result.ok())
+                    .map(|_| sender)
+            });
+
+        let mut sender = match idle {
+            Some(sender) => sender,
+            #[cfg(feature = "tls")]
+            None => self._connect(None).await?,
+            #[cfg(not(feature = "tls"))]
+            None => self._connect().await?,
+        };
+
+        let response = sender.send_request(request);
+        *self.connection.lock() = Some(sender);
+        response.await.map_err(hyper_err)
+    }
+
+    /// Writes the raw HTTP/1.1 `request`, made with `method`, to a new
+    /// connection and reads the response until the server closes it. Used for
+    /// requests `hyper` refuses to send, such as those with an invalid URI.
+    pub(crate) async fn _send_raw(
+        &self,
+        method: Method,
+        request: &[u8],
+    ) -> io::Result<hyper::Response<hyper::Body>> {
+        #[cfg(feature = "tls")]
+        if let Some(ref connector) = self.tls {
+            let io = connector.connect(self._addr()).await?;
+            return exchange(io, method, request).await;
+        }
+
+        exchange(tokio::net::TcpStream::connect(self._addr()).await?, method, request).await
+    }
+
+    // Generates the public API methods, which call the private methods above.
+    pub_client_impl!("use rocket::local::live::Client;" @async await);
+}
+
+fn hyper_err<E>(e: E) -> io::Error
+    where E: Into<Box<dyn std::error::Error + Send + Sync>>
+{
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+/// Performs an HTTP/1.1 handshake over `io`, driving the connection in the
+/// background, and waits until it is ready to send a request.
+async fn handshake<T>(io: T) -> io::Result<SendRequest>
+    where T: AsyncRead + AsyncWrite + Unpin + Send + 'static
+{
+    let (mut sender, conn) = hyper::client::conn::handshake(io).await
+        .map_err(hyper_err)?;
+
+    tokio::spawn(async move {
+        if let Err(e) = conn.await {
+            warn!("Live client connection error: {}", e);
+        }
+    });
+
+    future::poll_fn(|cx| sender.poll_ready(cx)).await.map_err(hyper_err)?;
+    Ok(sender)
+}
+
+/// Writes `request`, made with `method`, to `io`, then reads and parses the
+/// response the server sends before closing the connection.
+async fn exchange<T>(mut io: T, method: Method, request: &[u8])
+    -> io::Result<hyper::Response<hyper::Body>>
+    where T: AsyncRead + AsyncWrite + Unpin
+{
+    io.write_all(request).await?;
+    io.flush().await?;
+
+    let mut raw = vec![];
+    match io.read_to_end(&mut raw).await {
+        // A TLS peer may close the connection without a `close_notify`.
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {},
+        result => { result?; }
+    }
+
+    let mut headers = [httparse::EMPTY_HEADER; 64];
+    let mut parsed = httparse::Response::new(&mut headers);
+    let body_start = match parsed.parse(&raw).map_err(hyper_err)? {
+        httparse::Status::Complete(n) => n,
+        httparse::Status::Partial => return Err(io::ErrorKind::UnexpectedEof.into()),
+    };
+
+    let mut response = hyper::Response::builder().status(parsed.code.unwrap_or(0));
+    let mut chunked = false;
+    for header in parsed.headers.iter() {
+        chunked |= header.name.eq_ignore_ascii_case("Transfer-Encoding")
+            && header.value.eq_ignore_ascii_case(b"chunked");
+
+        response = response.header(header.name, header.value);
+    }
+
+    let body = match &raw[body_start..] {
+        _ if method == Method::Head => vec![],
+        body if chunked => decode_chunked(body)?,
+        body => body.to_vec(),
+    };
+
+    response.body(body.into()).map_err(hyper_err)
+}
+
+/// Decodes a body sent with `Transfer-Encoding: chunked`.
+fn decode_chunked(mut raw: &[u8]) -> io::Result<Vec<u8>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid chunked body");
+
+    let mut body = vec![];
+    loop {
+        let (size, rest) = match httparse::parse_chunk_size(raw).map_err(|_| invalid())? {
+            httparse::Status::Complete((n, size)) => (size as usize, &raw[n..]),
+            httparse::Status::Partial => return Err(invalid()),
+        };
+
+        if size == 0 {
+            return Ok(body);
+        }
+
+        let chunk = rest.get(..size).ok_or_else(invalid)?;
+        body.extend_from_slice(chunk);
+        raw = rest.get(size..).and_then(|r| r.strip_prefix(b"\r\n")).ok_or_else(invalid)?;
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        self.rocket.shutdown().notify();
+    }
+}
+
+impl std::fmt::Debug for Client {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self._rocket().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn test_live_client_impl_send_sync() {
+        fn assert_sync_send<T: Sync + Send>() {}
+        assert_sync_send::<super::Client>();
+    }
+}
@@ -0,0 +1,59 @@
+//! Live, networked dispatching of requests.
+//!
+//! This module contains the `live` variant of the `local` API. Unlike the
+//! [`asynchronous`](super::asynchronous) and [`blocking`](super::blocking)
+//! variants, which dispatch requests in-process, a live [`Client`] launches
+//! the application on an ephemeral port on `127.0.0.1` and dispatches each
+//! request over a real socket. Requests thus pass through the HTTP server,
+//! connection handling, and, if configured, TLS. This makes it possible to
+//! test keep-alive, streaming, graceful shutdown, and mutual TLS.
+//!
+//! The API is identical to that of `asynchronous` with the following
+//! exceptions:
+//!
+//!   * A remote address set via [`LocalRequest::remote()`] is ignored: the
+//!     server observes the real address of the client's connection.
+//!   * Requests are sent with a `Host` header of `127.0.0.1:$port` unless one
+//!     is set explicitly.
+//!   * With the `mtls` feature enabled, a client certificate can be presented
+//!     with [`LocalRequest::identity()`].
+//!   * `dispatch()` panics if the request cannot be sent or the response
+//!     cannot be received due to a network error.
+//!
+//! The server is gracefully shut down when the `Client` is dropped.
+//!
+//! # TLS
+//!
+//! If TLS is enabled in the application's configuration, the client connects
+//! over TLS. The client trusts exactly the leaf certificate configured for the
+//! server; no CA or server name configuration is necessary.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[macro_use] extern crate rocket;
+//!
+//! use rocket::local::live::Client;
+//!
+//! #[get("/")]
+//! fn hello() -> &'static str {
+//!     "Hello, world!"
+//! }
+//!
+//! # rocket::async_test(async {
+//! let rocket = rocket::build().mount("/", routes![hello]);
+//! let client = Client::tracked(rocket).await.expect("valid rocket");
+//! let response = client.get("/").dispatch().await;
+//! assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+//! # });
+//! ```
+//!
+//! See the [top-level documentation](super) for more usage details.
+
+mod client;
+mod request;
+
+pub use client::*;
+pub use request::*;
+
+pub use super::asynchronous::LocalResponse;
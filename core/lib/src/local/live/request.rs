@@ -0,0 +1,321 @@
+use std::{fmt, io};
+use std::convert::TryInto;
+
+use futures::TryStreamExt;
+use tokio_util::io::StreamReader;
+
+use crate::{Request, Response};
+use crate::http::{Status, Method, CookieJar, hyper};
+use crate::http::uri::Origin;
+
+use crate::local::redirect::{self, Follow, Redirection};
+use super::{Client, LocalResponse};
+
+/// A local request, dispatched over the network, as returned by
+/// [`Client`](super::Client).
+///
+/// For details, see [the top-level documentation](../index.html#localrequest).
+///
+/// ## Example
+///
+/// The following snippet uses the available builder methods to construct and
+/// dispatch a `POST` request to `/` with a JSON body:
+///
+/// ```rust,no_run
+/// use rocket::local::live::{Client, LocalRequest};
+/// use rocket::http::{ContentType, Cookie};
+///
+/// # rocket::async_test(async {
+/// let client = Client::tracked(rocket::build()).await.expect("valid rocket");
+/// let req = client.post("/")
+///     .header(ContentType::JSON)
+///     .cookie(Cookie::new("name", "value"))
+///     .body(r#"{ "value": 42 }"#);
+///
+/// let response = req.dispatch().await;
+/// # });
+/// ```
+pub struct LocalRequest<'c> {
+    pub(in super) client: &'c Client,
+    pub(in super) request: Request<'c>,
+    data: Vec<u8>,
+    // The `Origin` on the right is INVALID! It should _not_ be used!
+    uri: Result<Origin<'c>, Origin<'static>>,
+    max_redirects: usize,
+    #[cfg(feature = "mtls")]
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+impl<'c> LocalRequest<'c> {
+    pub(crate) fn new<'u: 'c, U>(client: &'c Client, method: Method, uri: U) -> Self
+        where U: TryInto<Origin<'u>> + fmt::Display
+    {
+        // Try to parse `uri` into an `Origin`, storing whether it's good.
+        let uri_str = uri.to_string();
+        let try_origin = uri.try_into().map_err(|_| Origin::path_only(uri_str));
+
+        // Create a request. We'll handle bad URIs later, in `_dispatch`.
+        let origin = try_origin.clone().unwrap_or_else(|bad| bad);
+        let mut request = Request::new(client.rocket(), method, origin);
+
+        // Add any cookies we know about.
+        if client.tracked {
+            client._with_raw_cookies(|jar| {
+                for cookie in jar.iter() {
+                    request.cookies_mut().add_original(cookie.clone());
+                }
+            })
+        }
+
+        LocalRequest {
+            client, request, uri: try_origin, data: vec![], max_redirects: 0,
+            #[cfg(feature = "mtls")]
+            identity: None,
+        }
+    }
+
+    pub(crate) fn _request(&self) -> &Request<'c> {
+        &self.request
+    }
+
+    pub(crate) fn _request_mut(&mut self) -> &mut Request<'c> {
+        &mut self.request
+    }
+
+    pub(crate) fn _body_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.data
+    }
+
+    pub(crate) fn _max_redirects_mut(&mut self) -> &mut usize {
+        &mut self.max_redirects
+    }
+
+    /// Presents the client certificate chain `cert_chain` with private key
+    /// `key`, both PEM-encoded, when this request is dispatched.
+    ///
+    /// The request is dispatched over a new connection which is used only
+    /// for this request. The server must have TLS enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket::local::live::{Client, LocalRequest};
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let cert_chain = std::fs::read("private/client_cert.pem").unwrap();
+    /// let key = std::fs::read("private/client_key.pem").unwrap();
+    /// let req = request.identity(cert_chain, key);
+    /// # });
+    /// ```
+    #[cfg(feature = "mtls")]
+    #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
+    pub fn identity<C, K>(mut self, cert_chain: C, key: K) -> Self
+        where C: AsRef<[u8]>, K: AsRef<[u8]>
+    {
+        self.identity = Some((cert_chain.as_ref().into(), key.as_ref().into()));
+        self
+    }
+
+    // The headers to send with the request, including `Host` and `Cookie`.
+    fn raw_headers(&self) -> Vec<(String, String)> {
+        let mut headers = self.request.headers().iter()
+            .map(|header| (header.name().to_string(), header.value().to_string()))
+            .collect::<Vec<_>>();
+
+        if !self.request.headers().contains("Host") {
+            let host = self.request.host().map(|h| h.to_string());
+            headers.push(("Host".into(), host.unwrap_or_else(|| self.client._addr().to_string())));
+        }
+
+        let cookies = self.request.cookies().iter()
+            .map(|cookie| cookie.encoded().stripped().to_string())
+            .collect::<Vec<_>>();
+
+        if !cookies.is_empty() {
+            headers.push(("Cookie".into(), cookies.join("; ")));
+        }
+
+        headers
+    }
+
+    // Converts the request into a `hyper` request ready to be sent.
+    fn to_hyper_request(&self) -> hyper::Request<hyper::Body> {
+        let mut builder = hyper::Request::builder()
+            .method(self.request.method().as_str())
+            .uri(self.request.uri().to_string());
+
+        for (name, value) in self.raw_headers() {
+            builder = builder.header(name, value);
+        }
+
+        builder.body(self.data.clone().into()).unwrap_or_else(|e| {
+            panic!("live client: invalid request {}: {}", self.request, e)
+        })
+    }
+
+    // Serializes the request, URI verbatim, as a raw HTTP/1.1 request that
+    // closes the connection.
+    fn to_raw_request(&self) -> Vec<u8> {
+        let mut head = format!("{} {} HTTP/1.1\r\n", self.request.method(), self.request.uri());
+        for (name, value) in self.raw_headers() {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+
+        if !self.request.headers().contains("Content-Length") {
+            head.push_str(&format!("Content-Length: {}\r\n", self.data.len()));
+        }
+
+        head.push_str("Connection: close\r\n\r\n");
+        let mut raw = head.into_bytes();
+        raw.extend_from_slice(&self.data);
+        raw
+    }
+
+    // Sends the request over the network, returning the raw response.
+    async fn _send(&self) -> io::Result<hyper::Response<hyper::Body>> {
+        #[cfg(feature = "mtls")]
+        if let Some((ref certs, ref key)) = self.identity {
+            let tls = self.client.rocket().config().tls.as_ref()
+                .filter(|_| self.client.rocket().config().tls_enabled())
+                .ok_or_else(|| {
+                    let msg = "client identity requires TLS to be enabled";
+                    io::Error::new(io::ErrorKind::Other, msg)
+                })?;
+
+            let connector = tls.to_connector_config(Some((certs, key)))
+                .and_then(crate::http::tls::TlsConnector::new)?;
+
+            return self.client._send(self.to_hyper_request(), Some(connector)).await;
+        }
+
+        #[cfg(feature = "mtls")]
+        return self.client._send(self.to_hyper_request(), None).await;
+
+        #[cfg(not(feature = "mtls"))]
+        return self.client._send(self.to_hyper_request()).await;
+    }
+
+    // Dispatches the request, following up to `max_redirects` redirects.
+    async fn _dispatch(self) -> LocalResponse<'c> {
+        redirect::dispatch(self, Self::_dispatch_once).await
+    }
+
+    // Performs the actual dispatch.
+    async fn _dispatch_once(self) -> LocalResponse<'c> {
+        // Actually dispatch the request over the network. The user may have
+        // changed an invalid URI in the request, in which case it's sent as
+        // usual. Otherwise, `hyper` refuses to send it, so the request is
+        // written to the connection verbatim.
+        let response = match self.uri {
+            Err(ref invalid) if self.inner().uri() == invalid => {
+                let method = self.request.method();
+                self.client._send_raw(method, &self.to_raw_request()).await
+            }
+            _ => self._send().await,
+        };
+
+        let hyp_response = response.unwrap_or_else(|e| {
+            panic!("live client: failed to dispatch {}: {}", self.request, e)
+        });
+
+        // Convert the `hyper` response into a Rocket `Response`.
+        let (parts, body) = hyp_response.into_parts();
+        let mut response = Response::new();
+        response.set_status(Status::new(parts.status.as_u16()));
+        for (name, value) in parts.headers.iter() {
+            let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+            response.adjoin_raw_header(name.as_str().to_string(), value);
+        }
+
+        let body = body.map_err(|e| io::Error::new(io::ErrorKind::Other, e));
+        response.set_streamed_body(StreamReader::new(body));
+
+        let response = LocalResponse::new(self.request, move |_| async move {
+            response
+        }).await;
+
+        // If the client is tracking cookies, updates the internal cookie jar
+        // with the changes reflected by `response`.
+        if self.client.tracked {
+            self.client._with_raw_cookies_mut(|jar| {
+                let current_time = time::OffsetDateTime::now_utc();
+                for cookie in response.cookies().iter() {
+                    if let Some(expires) = cookie.expires_datetime() {
+                        if expires <= current_time {
+                            jar.force_remove(cookie);
+                            continue;
+                        }
+                    }
+
+                    jar.add_original(cookie.clone());
+                }
+            })
+        }
+
+        response
+    }
+
+    pub_request_impl!("# use rocket::local::live::Client;\n\
+        use rocket::local::live::LocalRequest;" async await);
+}
+
+impl<'c> Follow<'c> for LocalRequest<'c> {
+    fn request(&self) -> &Request<'c> {
+        &self.request
+    }
+
+    fn max_redirects(&self) -> usize {
+        self.max_redirects
+    }
+
+    fn follow(self, redirection: &Redirection, cookies: &CookieJar<'_>) -> Self {
+        let location = redirection.hop.location().clone();
+        let mut next = LocalRequest::new(self.client, redirection.method, location);
+        redirection.carry_over(&self.request, &mut next.request, cookies);
+        next.max_redirects = self.max_redirects;
+        if redirection.keep_body {
+            next.data = self.data;
+        }
+
+        #[cfg(feature = "mtls")] {
+            next.identity = self.identity;
+        }
+
+        next
+    }
+}
+
+impl<'c> Clone for LocalRequest<'c> {
+    fn clone(&self) -> Self {
+        LocalRequest {
+            client: self.client,
+            request: self.request.clone(),
+            data: self.data.clone(),
+            uri: self.uri.clone(),
+            max_redirects: self.max_redirects,
+            #[cfg(feature = "mtls")]
+            identity: self.identity.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for LocalRequest<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self._request().fmt(f)
+    }
+}
+
+impl<'c> std::ops::Deref for LocalRequest<'c> {
+    type Target = Request<'c>;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner()
+    }
+}
+
+impl<'c> std::ops::DerefMut for LocalRequest<'c> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner_mut()
+    }
+}
@@ -138,7 +138,9 @@
 //! A [`LocalRequest`] ([`async` `LocalRequest`]) is constructed via a `Client`.
 //! Once obtained, headers, cookies, including private cookies, the remote IP
 //! address, and the request body can all be set via methods on the
-//! `LocalRequest` structure.
+//! `LocalRequest` structure. Bodies can be set directly or as a URL-encoded
+//! form, `multipart/form-data` (see [`Multipart`]), JSON, or MessagePack, in
+//! which case the `Content-Type` is set automatically.
 //!
 //! **Dispatching**
 //!
@@ -178,10 +180,24 @@
 //! [`async` `into_string()`]: asynchronous::LocalResponse::into_string()
 //! [`into_bytes()`]: blocking::LocalResponse::into_bytes()
 //! [`async` `into_bytes()`]: asynchronous::LocalResponse::into_bytes()
+//!
+//! # Live Dispatching
+//!
+//! The [`live`] submodule provides a third variant of the `asynchronous` API.
+//! Instead of dispatching requests in-process, a [`live::Client`] launches
+//! the application on an ephemeral local port and dispatches requests over a
+//! real network connection, optionally over TLS. Use it to test behavior that
+//! depends on the server itself, such as keep-alive, streaming, or mTLS.
 
 #[macro_use] mod client;
 #[macro_use] mod request;
 #[macro_use] mod response;
+mod multipart;
+mod redirect;
 
 pub mod asynchronous;
 pub mod blocking;
+pub mod live;
+
+pub use multipart::Multipart;
+pub use redirect::Hop;
@@ -0,0 +1,192 @@
+use crate::http::ContentType;
+
+/// A builder for `multipart/form-data` request bodies.
+///
+/// A `Multipart` is a sequence of text fields and files. It is set as the body
+/// of a local request via `LocalRequest::multipart()`, which also sets the
+/// `Content-Type` header, boundary included, automatically. Fields are encoded
+/// in the order they are added.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::form::Form;
+/// use rocket::fs::TempFile;
+/// use rocket::http::ContentType;
+/// use rocket::local::Multipart;
+/// use rocket::local::blocking::Client;
+///
+/// #[derive(FromForm)]
+/// struct Upload<'r> {
+///     name: &'r str,
+///     file: TempFile<'r>,
+/// }
+///
+/// #[post("/", data = "<upload>")]
+/// fn upload(upload: Form<Upload<'_>>) -> String {
+///     format!("{}: {} bytes", upload.name, upload.file.len())
+/// }
+///
+/// let client = Client::debug_with(routes![upload]).unwrap();
+/// let form = Multipart::new()
+///     .field("name", "notes")
+///     .file("file", "notes.txt", ContentType::Plain, "hello!");
+///
+/// let response = client.post("/").multipart(form).dispatch();
+/// assert_eq!(response.into_string().unwrap(), "notes: 6 bytes");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Multipart {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone)]
+struct Part {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<ContentType>,
+    data: Vec<u8>,
+}
+
+impl Multipart {
+    /// Creates an empty `Multipart` body with a random boundary.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Multipart;
+    ///
+    /// let form = Multipart::new();
+    /// assert!(!form.boundary().is_empty());
+    /// ```
+    pub fn new() -> Multipart {
+        let boundary = format!("------------------------{:016x}", rand::random::<u64>());
+        Multipart { boundary, parts: vec![] }
+    }
+
+    /// Adds a text field named `name` with value `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Multipart;
+    ///
+    /// let form = Multipart::new()
+    ///     .field("username", "bob")
+    ///     .field("password", "hunter2");
+    /// ```
+    pub fn field<N, V>(mut self, name: N, value: V) -> Self
+        where N: Into<String>, V: AsRef<[u8]>
+    {
+        self.parts.push(Part {
+            name: name.into(),
+            file_name: None,
+            content_type: None,
+            data: value.as_ref().into(),
+        });
+
+        self
+    }
+
+    /// Adds a file field named `name` with file name `file_name`, content type
+    /// `content_type`, and contents `data`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Multipart;
+    /// use rocket::http::ContentType;
+    ///
+    /// let form = Multipart::new()
+    ///     .file("avatar", "me.png", ContentType::PNG, &[0x89, b'P', b'N', b'G'])
+    ///     .file("notes", "notes.txt", ContentType::Plain, "some notes");
+    /// ```
+    pub fn file<N, F, D>(
+        mut self,
+        name: N,
+        file_name: F,
+        content_type: ContentType,
+        data: D
+    ) -> Self
+        where N: Into<String>, F: Into<String>, D: AsRef<[u8]>
+    {
+        self.parts.push(Part {
+            name: name.into(),
+            file_name: Some(file_name.into()),
+            content_type: Some(content_type),
+            data: data.as_ref().into(),
+        });
+
+        self
+    }
+
+    /// Returns the boundary separating the parts of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Multipart;
+    ///
+    /// let form = Multipart::new();
+    /// assert!(form.boundary().starts_with("----"));
+    /// ```
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Returns the `multipart/form-data` `Content-Type`, including the
+    /// boundary, of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Multipart;
+    ///
+    /// let form = Multipart::new();
+    /// let content_type = form.content_type();
+    /// assert!(content_type.is_form_data());
+    /// assert_eq!(content_type.param("boundary"), Some(form.boundary()));
+    /// ```
+    pub fn content_type(&self) -> ContentType {
+        ContentType::new("multipart", "form-data")
+            .with_params(("boundary", self.boundary.clone()))
+    }
+
+    /// Encodes `self` into the bytes of a `multipart/form-data` body.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        // Escape as browsers do: `"`, `\r`, and `\n` are percent-encoded.
+        fn escape(string: &str) -> String {
+            string.replace('"', "%22").replace('\r', "%0D").replace('\n', "%0A")
+        }
+
+        let mut body = Vec::new();
+        for part in &self.parts {
+            body.extend_from_slice(format!("--{}\r\n", self.boundary).as_bytes());
+            let disposition = match part.file_name {
+                Some(ref file_name) => format!("form-data; name=\"{}\"; filename=\"{}\"",
+                    escape(&part.name), escape(file_name)),
+                None => format!("form-data; name=\"{}\"", escape(&part.name)),
+            };
+
+            body.extend_from_slice(format!("Content-Disposition: {}\r\n", disposition).as_bytes());
+            if let Some(ref content_type) = part.content_type {
+                body.extend_from_slice(format!("Content-Type: {}\r\n", content_type).as_bytes());
+            }
+
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(&part.data);
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        body
+    }
+}
+
+impl Default for Multipart {
+    fn default() -> Self {
+        Multipart::new()
+    }
+}
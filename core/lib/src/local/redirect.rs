@@ -0,0 +1,247 @@
+use std::future::Future;
+
+use crate::Request;
+use crate::http::{Method, Status, CookieJar, Header, HeaderMap};
+use crate::http::uri::{Origin, Absolute, Host};
+use crate::http::ext::IntoOwned;
+use crate::http::private::cookie;
+use crate::local::asynchronous::LocalResponse;
+
+/// A redirect followed while dispatching a local request.
+///
+/// A local request only follows redirects when asked to via
+/// `LocalRequest::follow_redirects()`. The hops taken are available, in order,
+/// via `LocalResponse::redirects()`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::response::Redirect;
+/// use rocket::local::blocking::Client;
+/// use rocket::http::{Method, Status};
+///
+/// #[post("/login")]
+/// fn login() -> Redirect {
+///     Redirect::to(uri!(home))
+/// }
+///
+/// #[get("/home")]
+/// fn home() -> &'static str {
+///     "Welcome home!"
+/// }
+///
+/// let client = Client::tracked(rocket::build().mount("/", routes![login, home])).unwrap();
+/// let response = client.post("/login").follow_redirects(5).dispatch();
+///
+/// let hop = &response.redirects()[0];
+/// assert_eq!(hop.method(), Method::Post);
+/// assert_eq!(hop.uri(), "/login");
+/// assert_eq!(hop.status(), Status::SeeOther);
+/// assert_eq!(hop.location(), "/home");
+/// assert_eq!(response.into_string().unwrap(), "Welcome home!");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hop {
+    method: Method,
+    uri: Origin<'static>,
+    status: Status,
+    location: Origin<'static>,
+}
+
+impl Hop {
+    /// The method of the request that was redirected.
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    /// The URI of the request that was redirected.
+    pub fn uri(&self) -> &Origin<'static> {
+        &self.uri
+    }
+
+    /// The redirect status of the response to the request.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// The URI requested next: the URI in the `Location` header of the
+    /// response to the request or, if that URI is absolute, its path and query.
+    pub fn location(&self) -> &Origin<'static> {
+        &self.location
+    }
+}
+
+/// The next request to make in response to a redirect.
+pub(crate) struct Redirection {
+    pub hop: Hop,
+    pub method: Method,
+    /// The host of the next request if the redirect is to an absolute URI.
+    pub host: Option<Host<'static>>,
+    /// Whether the next request is to a different host than this one.
+    pub cross_origin: bool,
+    /// Whether the body and its headers are carried over to the next request.
+    pub keep_body: bool,
+}
+
+/// A local request that can follow redirects.
+pub(crate) trait Follow<'c>: Clone {
+    /// The request to be dispatched.
+    fn request(&self) -> &Request<'c>;
+
+    /// The maximum number of redirects to follow.
+    fn max_redirects(&self) -> usize;
+
+    /// Returns the request to make, in place of `self`, to follow
+    /// `redirection`. `cookies` are those of the response to `self`.
+    fn follow(self, redirection: &Redirection, cookies: &CookieJar<'_>) -> Self;
+}
+
+/// Dispatches `request` with `dispatch`, following up to
+/// `request.max_redirects()` redirects.
+pub(crate) async fn dispatch<'c, R, F, Fut>(mut request: R, dispatch: F) -> LocalResponse<'c>
+    where R: Follow<'c>, F: Fn(R) -> Fut, Fut: Future<Output = LocalResponse<'c>>
+{
+    let mut hops = vec![];
+    loop {
+        let can_follow = hops.len() < request.max_redirects();
+        let template = if can_follow { Some(request.clone()) } else { None };
+        let response = dispatch(request).await;
+        let template = match template {
+            Some(template) => template,
+            None => return response.with_redirects(hops),
+        };
+
+        let (status, headers) = (response.status(), response.headers());
+        let redirection = match Redirection::from(template.request(), status, headers) {
+            Some(redirection) => redirection,
+            None => return response.with_redirects(hops),
+        };
+
+        request = template.follow(&redirection, response.cookies());
+        hops.push(redirection.hop);
+    }
+}
+
+impl Redirection {
+    /// Returns the redirection to follow in response to a request to `request`
+    /// that elicited a response with `status` and `headers`, if any. Only
+    /// redirects to origin URIs and absolute `http` or `https` URIs are
+    /// followed. The latter are followed to the same Rocket instance with the
+    /// URI's host.
+    ///
+    /// As browsers do, `303 See Other` changes the method to `GET`, as do
+    /// `301 Moved Permanently` and `302 Found` for `POST` requests. `307
+    /// Temporary Redirect` and `308 Permanent Redirect` preserve the method and
+    /// body.
+    pub fn from(request: &Request<'_>, status: Status, headers: &HeaderMap<'_>) -> Option<Self> {
+        let method = request.method();
+        let (method, keep_body) = match status.code {
+            303 if method == Method::Head => (method, false),
+            303 => (Method::Get, false),
+            301 | 302 if method == Method::Post => (Method::Get, false),
+            301 | 302 => (method, false),
+            307 | 308 => (method, true),
+            _ => return None,
+        };
+
+        let location = headers.get_one("Location")?;
+        let (location, host) = match Origin::parse_owned(location.to_string()) {
+            Ok(location) => (location, None),
+            Err(_) => match Self::absolute(location) {
+                Some((location, host)) => (location, Some(host)),
+                None => {
+                    warn!("Not following redirect to non-HTTP URI {:?}.", location);
+                    return None;
+                }
+            }
+        };
+
+        let cross_origin = match host {
+            Some(ref host) => request.host() != Some(host),
+            None => false,
+        };
+
+        let uri = request.uri().clone().into_owned();
+        let hop = Hop { method: request.method(), uri, status, location };
+        Some(Redirection { hop, method, host, cross_origin, keep_body })
+    }
+
+    /// Splits an absolute `http` or `https` URI into its path and query and
+    /// its host.
+    fn absolute(uri: &str) -> Option<(Origin<'static>, Host<'static>)> {
+        let uri = Absolute::parse(uri).ok()?;
+        let scheme = uri.scheme();
+        if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+            return None;
+        }
+
+        let host = Host::new(uri.authority()?.clone()).into_owned();
+        let path = match uri.path().as_str() {
+            "" => "/",
+            path => path,
+        };
+
+        let query = uri.query().map(|q| q.as_str());
+        let origin = Origin::parse_owned(format!("{}{}{}", path,
+            query.map_or("", |_| "?"), query.unwrap_or(""))).ok()?;
+
+        Some((origin, host))
+    }
+
+    /// Carries the headers, host, remote address, and cookies of `from` over
+    /// to `to`, the next request, applying the cookie changes in `cookies`,
+    /// the cookies of the response to `from`.
+    ///
+    /// On a cross-origin redirect, credentials are dropped: neither the
+    /// `Authorization` and `Cookie` headers nor any cookies are carried over.
+    pub fn carry_over<'c>(
+        &self,
+        from: &Request<'c>,
+        to: &mut Request<'c>,
+        cookies: &CookieJar<'_>
+    ) {
+        for header in from.headers().iter() {
+            let name = header.name();
+            let drop_body = !self.keep_body && (name == "Content-Type" || name == "Content-Length");
+            let drop_credential = self.cross_origin && (name == "Authorization" || name == "Cookie");
+            let drop_host = self.host.is_some() && name == "Host";
+            if !(drop_body || drop_credential || drop_host) {
+                to.add_header(Header::new(name.as_str().to_owned(), header.value().to_owned()));
+            }
+        }
+
+        match self.host {
+            Some(ref host) => to.set_host(host.clone()),
+            None => if let Some(host) = from.host() {
+                to.set_host(host.clone());
+            }
+        }
+
+        if let Some(remote) = from.remote() {
+            to.set_remote(remote);
+        }
+
+        if self.cross_origin {
+            *to.cookies_mut() = CookieJar::new(to.rocket().config());
+            return;
+        }
+
+        let mut jar = cookie::CookieJar::new();
+        for cookie in from.cookies().iter() {
+            jar.add_original(cookie.clone());
+        }
+
+        let current_time = time::OffsetDateTime::now_utc();
+        for cookie in cookies.iter() {
+            match cookie.expires_datetime() {
+                Some(expires) if expires <= current_time => jar.force_remove(cookie),
+                _ => jar.add_original(cookie.clone()),
+            }
+        }
+
+        for cookie in jar.iter() {
+            to.cookies_mut().add_original(cookie.clone());
+        }
+    }
+}
@@ -285,6 +285,106 @@ macro_rules! pub_request_impl {
         self.header(crate::http::ContentType::MsgPack).body(msgpack)
     }
 
+    /// Sets the body to `fields` URL-encoded as a form with `Content-Type`
+    /// [`ContentType::Form`](crate::http::ContentType::Form).
+    ///
+    /// Each `(name, value)` pair in `fields` is percent-encoded and the pairs
+    /// are joined with `&` in iteration order. The `Content-Type` header is
+    /// _always_ set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::ContentType;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.form(&[("name", "Bob Smith"), ("age", "47")]);
+    /// assert_eq!(req.content_type(), Some(&ContentType::Form));
+    /// # });
+    /// ```
+    pub fn form<'f, F, N, V>(self, fields: F) -> Self
+        where F: IntoIterator<Item = &'f (N, V)>,
+              N: AsRef<str> + 'f,
+              V: AsRef<str> + 'f,
+    {
+        use crate::http::RawStr;
+
+        let form = fields.into_iter()
+            .map(|(name, value)| format!("{}={}",
+                RawStr::new(name.as_ref()).percent_encode(),
+                RawStr::new(value.as_ref()).percent_encode()))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        self.header(crate::http::ContentType::Form).body(form)
+    }
+
+    /// Sets the body to the `multipart/form-data` encoding of `form` with the
+    /// corresponding `Content-Type`, including the boundary.
+    ///
+    /// The `Content-Type` header is _always_ set. See
+    /// [`Multipart`](crate::local::Multipart) for details on building `form`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    /// use rocket::http::ContentType;
+    /// use rocket::local::Multipart;
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let form = Multipart::new()
+    ///     .field("name", "Bob")
+    ///     .file("avatar", "bob.txt", ContentType::Plain, "not really an image");
+    ///
+    /// let request: LocalRequest = request;
+    /// let req = request.multipart(form);
+    /// assert!(req.content_type().unwrap().is_form_data());
+    /// # });
+    /// ```
+    pub fn multipart(self, form: crate::local::Multipart) -> Self {
+        let body = form.to_bytes();
+        self.header(form.content_type()).body(body)
+    }
+
+    /// Follows up to `max_hops` redirects when the request is dispatched.
+    ///
+    /// By default, redirects are not followed. When following redirects, a
+    /// `303 See Other` response is followed with a `GET` request, as is a `301
+    /// Moved Permanently` or `302 Found` response to a `POST` request, without
+    /// the body. `307 Temporary Redirect` and `308 Permanent Redirect`
+    /// responses are followed with the same method and body. Headers are
+    /// carried over to each request, as are cookies, including those set or
+    /// removed by the redirect responses, even if the client is untracked.
+    ///
+    /// Redirects to origin URIs, like `/home`, and to absolute `http` and
+    /// `https` URIs are followed. The latter are dispatched to the same Rocket
+    /// instance with the URI's host. When the host changes, the redirect is
+    /// cross-origin: the `Authorization` and `Cookie` headers and all cookies
+    /// are dropped.
+    ///
+    /// If the last of `max_hops` redirects is followed by yet another redirect,
+    /// the redirect response is returned. The redirects that were followed are
+    /// available via `LocalResponse::redirects()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    #[doc = $import]
+    ///
+    /// # Client::_test(|_, request, _| {
+    /// let request: LocalRequest = request;
+    /// let req = request.follow_redirects(5);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn follow_redirects(mut self, max_hops: usize) -> Self {
+        *self._max_redirects_mut() = max_hops;
+        self
+    }
+
     /// Set the body (data) of the request without consuming `self`.
     ///
     /// # Examples
@@ -57,6 +57,27 @@ macro_rules! pub_response_impl {
     getter_method!($doc_prelude, "response body, if there is one,",
         body -> &crate::response::Body<'_>);
 
+    /// Returns the redirects that were followed, in order, to arrive at this
+    /// response. The slice is empty unless the request was dispatched with
+    /// [`follow_redirects()`] and the response to it was a redirect.
+    ///
+    /// [`follow_redirects()`]: crate::local::blocking::LocalRequest::follow_redirects()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let response: LocalResponse = response;
+    /// let redirects = response.redirects();
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn redirects(&self) -> &[crate::local::Hop] {
+        self._redirects()
+    }
+
     /// Consumes `self` and reads the entirety of its body into a string.
     ///
     /// If reading fails, the body contains invalid UTF-8 characters, or the
@@ -180,6 +201,86 @@ macro_rules! pub_response_impl {
         self._into_msgpack() $(.$suffix)?
     }
 
+    /// Sets the read timeout for [`next_chunk()`](Self::next_chunk()) and
+    /// [`next_event()`](Self::next_event()) to `timeout`. If `timeout` is
+    /// `None`, which is the default, those methods wait indefinitely.
+    ///
+    /// When a read times out, it fails with an error of kind
+    /// [`std::io::ErrorKind::TimedOut`]. No data is lost: the response can
+    /// continue to be read afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    /// use std::time::Duration;
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let mut response: LocalResponse = response;
+    /// response.set_read_timeout(Some(Duration::from_secs(1)));
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn set_read_timeout(&mut self, timeout: Option<std::time::Duration>) {
+        self._set_read_timeout(timeout)
+    }
+
+    /// Reads the next chunk of the body, as it becomes available, without
+    /// waiting for the rest of the body.
+    ///
+    /// Returns `Ok(None)` when the body has been read in its entirety. Fails
+    /// with an error of kind [`std::io::ErrorKind::TimedOut`] if a [read
+    /// timeout](Self::set_read_timeout()) is set and elapses before a chunk is
+    /// available. This makes it possible to test infinite responses, such as
+    /// a [`TextStream`](crate::response::stream::TextStream).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let mut response: LocalResponse = response;
+    /// let chunk = response.next_chunk();
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub $($prefix)? fn next_chunk(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        self._next_chunk() $(.$suffix)?
+    }
+
+    /// Reads and parses the next server-sent [`Event`] in the body, as sent by
+    /// an [`EventStream`].
+    ///
+    /// Events are parsed as a browser would: only blocks with `data` are
+    /// returned, each carrying the last event `id` seen, the block's `event`
+    /// type, and its `retry`, if any. Comments, including heartbeats, and
+    /// blocks without data are skipped. Returns `Ok(None)` when the body has
+    /// been read in its entirety. Fails with an error of kind
+    /// [`std::io::ErrorKind::TimedOut`] if a [read
+    /// timeout](Self::set_read_timeout()) is set and elapses before a complete
+    /// event is available.
+    ///
+    /// [`Event`]: crate::response::stream::Event
+    /// [`EventStream`]: crate::response::stream::EventStream
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    #[doc = $doc_prelude]
+    ///
+    /// # Client::_test(|_, _, response| {
+    /// let mut response: LocalResponse = response;
+    /// let event = response.next_event();
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub $($prefix)? fn next_event(
+        &mut self
+    ) -> std::io::Result<Option<crate::response::stream::Event>> {
+        self._next_event() $(.$suffix)?
+    }
+
     #[cfg(test)]
     #[allow(dead_code)]
     fn _ensure_impls_exist() {
@@ -81,6 +81,7 @@ phases! {
     Build (#[derive(Default, Debug)] Building) {
         pub(crate) routes: Vec<Route>,
         pub(crate) catchers: Vec<Catcher>,
+        pub(crate) empty_mounts: Vec<String>,
         pub(crate) fairings: Fairings,
         pub(crate) figment: Figment,
         pub(crate) state: Container![Send + Sync],
@@ -7,6 +7,9 @@ mod from_request;
 #[cfg(test)]
 mod tests;
 
+#[doc(hidden)]
+pub use rocket_codegen::{FromRequest, FromParam, FromSegments};
+
 pub use self::request::Request;
 pub use self::from_request::{FromRequest, Outcome};
 pub use self::from_param::{FromParam, FromSegments};
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::any::{Any, TypeId};
 use std::ops::RangeFrom;
 use std::{future::Future, borrow::Cow, sync::Arc};
 use std::net::{IpAddr, SocketAddr};
@@ -7,6 +8,7 @@ use yansi::Paint;
 use state::{Container, Storage};
 use futures::future::BoxFuture;
 use atomic::{Atomic, Ordering};
+use parking_lot::Mutex;
 
 use crate::{Rocket, Route, Orbit};
 use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
@@ -49,6 +51,7 @@ pub(crate) struct RequestState<'r> {
     pub content_type: Storage<Option<ContentType>>,
     pub cache: Arc<Container![Send + Sync]>,
     pub host: Option<Host<'r>>,
+    pub error: Arc<Mutex<Option<Box<dyn Any + Send>>>>,
 }
 
 impl Request<'_> {
@@ -73,6 +76,7 @@ impl RequestState<'_> {
             content_type: self.content_type.clone(),
             cache: self.cache.clone(),
             host: self.host.clone(),
+            error: self.error.clone(),
         }
     }
 }
@@ -101,6 +105,7 @@ impl<'r> Request<'r> {
                 content_type: Storage::new(),
                 cache: Arc::new(<Container![Send + Sync]>::new()),
                 host: None,
+                error: Arc::new(Mutex::new(None)),
             }
         }
     }
@@ -673,6 +678,37 @@ impl<'r> Request<'r> {
         self.state.route.load(Ordering::Acquire)
     }
 
+    /// Takes the error value of the guard whose failure caused the current
+    /// error, if it is of type `T`. Returns `None` if no error value was
+    /// stashed, if it was of another type, or if it was already taken.
+    ///
+    /// Rocket stashes the error of a failing request or data guard on the
+    /// request when the guard's error type implements
+    /// [`TypedError`](crate::catcher::TypedError) and when it is a
+    /// [`form::Errors`] or, with the `json` feature enabled, a
+    /// [`json::Error`](crate::serde::json::Error). Errors that borrow from the
+    /// request are stashed in an owned form, such as `form::Errors<'static>`
+    /// for `form::Errors<'r>`. This method is primarily
+    /// used by typed catchers; see [`catch`](crate::catch) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Request;
+    /// use rocket::form;
+    ///
+    /// fn errors(request: &Request<'_>) -> Option<form::Errors<'static>> {
+    ///     request.take_error::<form::Errors<'static>>()
+    /// }
+    /// ```
+    pub fn take_error<T: Any>(&self) -> Option<T> {
+        let mut error = self.state.error.lock();
+        match error.as_ref().map_or(false, |e| e.is::<T>()) {
+            true => error.take().and_then(|e| e.downcast().ok()).map(|e| *e),
+            false => None,
+        }
+    }
+
     /// Invokes the request guard implementation for `T`, returning its outcome.
     ///
     /// # Example
@@ -950,6 +986,18 @@ impl<'r> Request<'r> {
         self.state.route.store(Some(route), Ordering::Release)
     }
 
+    /// Stashes the error value of a failing guard, replacing any other.
+    #[inline]
+    pub(crate) fn set_error<T: Any + Send>(&self, error: T) {
+        *self.state.error.lock() = Some(Box::new(error));
+    }
+
+    /// The type of the stashed guard error value, if there is one.
+    #[inline]
+    pub(crate) fn error_type(&self) -> Option<TypeId> {
+        self.state.error.lock().as_ref().map(|e| (**e).type_id())
+    }
+
     /// Set the method of `self`, even when `self` is a shared reference. Used
     /// during routing to override methods for re-routing.
     #[inline(always)]
@@ -202,6 +202,7 @@ pub use self::text::TextStream;
 pub use self::bytes::ByteStream;
 pub use self::reader::ReaderStream;
 pub use self::sse::{Event, EventStream};
+pub(crate) use self::sse::EventParser;
 
 crate::export! {
     /// Retrofitted support for [`Stream`]s with `yield`, `for await` syntax.
@@ -341,6 +341,110 @@ impl Event {
     }
 }
 
+/// Incrementally parses a stream of bytes into server-sent [`Event`]s.
+///
+/// Bytes are fed via [`EventParser::push()`] and complete events are retrieved
+/// via [`EventParser::next()`]. Parsing follows the SSE specification: lines
+/// are terminated by `\r\n`, `\n`, or `\r`, a single leading space is stripped
+/// from field values, multiple `data` lines are joined with `\n`, and a blank
+/// line dispatches the event. Comments and unknown fields are ignored.
+///
+/// Only blocks with at least one `data` line dispatch an event. As in the
+/// specification, the last event ID persists across events until it is reset
+/// by an empty `id` field, and a block without data, such as a heartbeat or
+/// one carrying only an `id` or `retry`, updates parser state but dispatches
+/// nothing. A `retry` is reported on the event dispatched by its block.
+#[derive(Debug, Default)]
+pub(crate) struct EventParser {
+    buffer: Vec<u8>,
+    event: Option<Event>,
+    data: Option<String>,
+    last_id: Option<String>,
+}
+
+impl EventParser {
+    /// Appends `bytes` to the internal buffer of unparsed bytes.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Removes and returns the unparsed, buffered bytes.
+    pub fn take_buffered(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.buffer)
+    }
+
+    /// Returns the next complete event in the buffer, if there is one. If
+    /// `eof` is `true`, a trailing line without a terminator is parsed.
+    pub fn next(&mut self, eof: bool) -> Option<Event> {
+        while let Some(line) = self.next_line(eof) {
+            if line.is_empty() {
+                let event = self.event.take().unwrap_or_else(Event::new);
+                let mut data = match self.data.take() {
+                    Some(data) => data,
+                    None => continue,
+                };
+
+                data.pop();
+                let id = self.last_id.clone().map(Cow::Owned);
+                return Some(Event { data: Some(Cow::Owned(data)), id, ..event });
+            }
+
+            let line = String::from_utf8_lossy(&line);
+            let (field, value) = match line.find(':') {
+                Some(0) => continue,
+                Some(i) => (&line[..i], &line[(i + 1)..]),
+                None => (&*line, ""),
+            };
+
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "data" => {
+                    let data = self.data.get_or_insert_with(String::new);
+                    data.push_str(value);
+                    data.push('\n');
+                }
+                "event" => {
+                    let event = self.event.take().unwrap_or_else(Event::new);
+                    self.event = Some(event.event(value.to_string()));
+                }
+                "id" if !value.contains('\0') => {
+                    self.last_id = Some(value.to_string()).filter(|id| !id.is_empty());
+                }
+                "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                    if let Ok(millis) = value.parse() {
+                        let event = self.event.take().unwrap_or_else(Event::new);
+                        self.event = Some(event.with_retry(Duration::from_millis(millis)));
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        None
+    }
+
+    // Removes and returns the next terminated line, without its terminator.
+    fn next_line(&mut self, eof: bool) -> Option<Vec<u8>> {
+        let i = self.buffer.iter().position(|&b| b == b'\n' || b == b'\r');
+        let (end, next) = match i {
+            // A `\r` at the end of the buffer may be followed by a `\n`.
+            Some(i) if self.buffer[i] == b'\r' && i + 1 == self.buffer.len() && !eof => {
+                return None;
+            }
+            Some(i) if self.buffer[i] == b'\r' && self.buffer.get(i + 1) == Some(&b'\n') => {
+                (i, i + 2)
+            }
+            Some(i) => (i, i + 1),
+            None if eof && !self.buffer.is_empty() => (self.buffer.len(), self.buffer.len()),
+            None => return None,
+        };
+
+        let line = self.buffer[..end].to_vec();
+        self.buffer.drain(..next);
+        Some(line)
+    }
+}
+
 /// A potentially infinite stream of Server-Sent [`Event`]s (SSE).
 ///
 /// An `EventStream` can be constructed from any [`Stream`] of items of type
@@ -519,25 +623,32 @@ impl<S: Stream<Item = Event>> EventStream<S> {
         self
     }
 
-    fn heartbeat_stream(&self) -> Option<impl Stream<Item = RawLinedEvent>> {
+    fn heartbeat_stream(&self) -> Option<impl Stream<Item = Option<Event>>> {
         use tokio::time::interval;
         use tokio_stream::wrappers::IntervalStream;
 
         self.heartbeat
             .map(|beat| IntervalStream::new(interval(beat)))
-            .map(|stream| stream.map(|_| RawLinedEvent::raw(":\n")))
+            .map(|stream| stream.map(|_| None))
     }
 
     fn into_stream(self) -> impl Stream<Item = RawLinedEvent> {
         use futures::future::Either;
         use crate::ext::StreamExt;
 
+        // Heartbeats are interleaved with whole events, never between the
+        // lines of a single event, which would split it in two.
         let heartbeat_stream = self.heartbeat_stream();
-        let raw_events = self.stream.map(|e| e.into_stream()).flatten();
-        match heartbeat_stream {
-            Some(heartbeat) => Either::Left(raw_events.join(heartbeat)),
-            None => Either::Right(raw_events)
-        }
+        let events = self.stream.map(Some);
+        let events = match heartbeat_stream {
+            Some(heartbeat) => Either::Left(events.join(heartbeat)),
+            None => Either::Right(events)
+        };
+
+        events.map(|event| match event {
+            Some(event) => Either::Left(event.into_stream()),
+            None => Either::Right(stream::iter(Some(RawLinedEvent::raw(":\n")))),
+        }).flatten()
     }
 
     fn into_reader(self) -> impl AsyncRead {
@@ -597,7 +708,7 @@ mod sse_tests {
     use tokio::io::AsyncReadExt;
     use tokio::time::{self, Duration};
     use futures::stream::Stream;
-    use crate::response::stream::{stream, Event, EventStream, ReaderStream};
+    use crate::response::stream::{stream, Event, EventStream, EventParser, ReaderStream};
 
     impl Event {
         fn into_string(self) -> String {
@@ -803,4 +914,91 @@ mod sse_tests {
         assert!(string.contains("data:a\n\n"));
         assert!(string.contains("data:b\n\n"));
     }
+
+    #[test]
+    fn test_event_parser() {
+        fn parse(chunks: &[&str]) -> Vec<Event> {
+            let mut parser = EventParser::default();
+            let mut events = vec![];
+            for chunk in chunks {
+                parser.push(chunk.as_bytes());
+                while let Some(event) = parser.next(false) {
+                    events.push(event);
+                }
+            }
+
+            events.extend(parser.next(true));
+            events
+        }
+
+        assert_eq!(parse(&["data:foo\n\n"]), vec![Event::data("foo")]);
+        assert_eq!(parse(&["data: foo\r\n\r\n"]), vec![Event::data("foo")]);
+        assert_eq!(parse(&["data:foo\r\r"]), vec![Event::data("foo")]);
+        assert_eq!(parse(&["da", "ta:f", "oo\r", "\n\r", "\n"]), vec![Event::data("foo")]);
+        assert_eq!(parse(&["data:a\ndata:\ndata:b\n\n"]), vec![Event::data("a\n\nb")]);
+        assert_eq!(parse(&[":\n\n:hi\n\ndata:x\n\n"]), vec![Event::data("x")]);
+        assert_eq!(parse(&["data:foo\n"]), vec![]);
+        assert_eq!(parse(&["data\n\n"]), vec![Event::data("")]);
+        assert_eq!(parse(&["foo:bar\nretry:1x\n\n"]), vec![]);
+
+        let expected = Event::data("foo\nbar")
+            .id("moo")
+            .event("milk")
+            .with_retry(Duration::from_secs(3));
+
+        let events = parse(&[":??\nretry:3000\nid:moo\nevent:milk\ndata:foo\ndata:bar\n\n"]);
+        assert_eq!(events, vec![expected]);
+
+        // Blocks without data update state but don't dispatch an event.
+        assert_eq!(parse(&["retry:500\n\n", "id:1\n\n", "event:x\n\n"]), vec![]);
+        assert_eq!(parse(&["id:1\n\ndata:a\n\nid\ndata:b\n\n"]), vec![
+            Event::data("a").id("1"),
+            Event::data("b"),
+        ]);
+    }
+
+    #[test]
+    fn test_event_parser_roundtrip() {
+        use futures::stream::iter;
+
+        let events = vec![
+            Event::data("a\nb"),
+            Event::data("b").id("1").event("letter"),
+            Event::retry(Duration::from_secs(1)),
+            Event::data("{\"c\": 3}"),
+        ];
+
+        let string = EventStream::from(iter(events)).into_string();
+        let mut parser = EventParser::default();
+        parser.push(string.as_bytes());
+
+        // The retry-only event isn't dispatched; the last event ID persists.
+        let parsed: Vec<_> = std::iter::from_fn(|| parser.next(true)).collect();
+        assert_eq!(parsed, vec![
+            Event::data("a\nb"),
+            Event::data("b").id("1").event("letter"),
+            Event::data("{\"c\": 3}").id("1"),
+        ]);
+    }
+
+    #[test]
+    fn test_heartbeat_does_not_split_events() {
+        let stream = EventStream! {
+            for i in 0..10 {
+                time::sleep(Duration::from_millis(2)).await;
+                yield Event::data("a\nb").id(i.to_string()).event("split");
+            }
+        };
+
+        let string = stream.heartbeat(Duration::from_millis(1)).into_string();
+        let mut parser = EventParser::default();
+        parser.push(string.as_bytes());
+
+        let parsed: Vec<_> = std::iter::from_fn(|| parser.next(true)).collect();
+        let expected: Vec<_> = (0..10)
+            .map(|i| Event::data("a\nb").id(i.to_string()).event("split"))
+            .collect();
+
+        assert_eq!(parsed, expected);
+    }
 }
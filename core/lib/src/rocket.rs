@@ -1,18 +1,23 @@
-use std::fmt;
+use std::{fmt, io};
+use std::sync::Arc;
+use std::net::Ipv4Addr;
 use std::ops::{Deref, DerefMut};
 use std::convert::TryInto;
 
+use tokio::sync::oneshot;
+
 use yansi::Paint;
 use either::Either;
 use figment::{Figment, Provider};
 
-use crate::{Catcher, Config, Route, Shutdown, sentinel, shield::Shield};
+use crate::{Catcher, Config, Route, Shutdown, route, sentinel, shield::Shield};
 use crate::router::Router;
+use crate::config::Unreachable;
 use crate::trip_wire::TripWire;
 use crate::fairing::{Fairing, Fairings};
 use crate::phase::{Phase, Build, Building, Ignite, Igniting, Orbit, Orbiting};
 use crate::phase::{Stateful, StateRef, State};
-use crate::http::uri::{self, Origin};
+use crate::http::uri::{self, Origin, HostPattern};
 use crate::http::ext::IntoOwned;
 use crate::error::{Error, ErrorKind};
 use crate::log::PaintExt;
@@ -250,6 +255,9 @@ impl Rocket<Build> {
     /// path. Mounting a route with path `path` at path `base` makes the route
     /// available at `base/path`.
     ///
+    /// To require request guards for, or attach fairings to, every route in a
+    /// mount point, mount a [`Scope`](crate::route::Scope).
+    ///
     /// # Panics
     ///
     /// Panics if either:
@@ -300,16 +308,74 @@ impl Rocket<Build> {
     ///     rocket::build().mount("/hello", vec![hi_route])
     /// }
     /// ```
-    pub fn mount<'a, B, R>(self, base: B, routes: R) -> Self
+    pub fn mount<'a, B, R>(mut self, base: B, routes: R) -> Self
         where B: TryInto<Origin<'a>> + Clone + fmt::Display,
               B::Error: fmt::Display,
               R: Into<Vec<Route>>
     {
-        self.load("route", base, routes.into(),
+        let routes = routes.into();
+        if routes.is_empty() {
+            self.0.empty_mounts.push(base.to_string());
+        }
+
+        self.load("route", base, routes,
             |base, route| route.map_base(|old| format!("{}{}", base, old)),
             |r, route| r.0.routes.push(route))
     }
 
+    /// Mounts all of the routes in the supplied vector at the given `base`
+    /// path, as [`Rocket::mount()`] does, restricting them to requests whose
+    /// host matches the [`HostPattern`] `host`. The host pattern of each route,
+    /// if it had one, is replaced.
+    ///
+    /// Routes mounted for different hosts don't collide, so the same paths can
+    /// be served by different routes on different hosts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `host` is not a valid [`HostPattern`] or for any of the
+    /// reasons [`Rocket::mount()`] panics.
+    ///
+    /// # Example
+    ///
+    /// Requests to `/` are dispatched to `api` when the request's host is
+    /// `api.example.com` and to `www` when it is any other subdomain of
+    /// `example.com`. Since `api.example.com` matches both host patterns, `www`
+    /// is given a higher rank.
+    ///
+    /// ```rust,no_run
+    /// # #[macro_use] extern crate rocket;
+    /// #[get("/")]
+    /// fn api() -> &'static str { "API" }
+    ///
+    /// #[get("/", rank = 2)]
+    /// fn www() -> &'static str { "Website" }
+    ///
+    /// #[launch]
+    /// fn rocket() -> _ {
+    ///     rocket::build()
+    ///         .mount_host("api.example.com", "/", routes![api])
+    ///         .mount_host("*.example.com", "/", routes![www])
+    /// }
+    /// ```
+    pub fn mount_host<'a, B, R>(self, host: &str, base: B, routes: R) -> Self
+        where B: TryInto<Origin<'a>> + Clone + fmt::Display,
+              B::Error: fmt::Display,
+              R: Into<Vec<Route>>
+    {
+        let host = HostPattern::parse(host).unwrap_or_else(|e| {
+            error!("invalid route host: {}", Paint::white(host));
+            error_!("{}", e);
+            panic!("aborting due to route host error");
+        });
+
+        let routes: Vec<Route> = routes.into().into_iter()
+            .map(|route| Route { host: Some(host.clone()), ..route })
+            .collect();
+
+        self.mount(base, routes)
+    }
+
     /// Registers all of the catchers in the supplied vector, scoped to `base`.
     ///
     /// # Panics
@@ -440,12 +506,16 @@ impl Rocket<Build> {
     ///     secret key.
     ///   * There are no [`Route#collisions`] or [`Catcher#collisions`]
     ///     collisions.
+    ///   * If [`Config::unreachable`] is `deny`, no route is known to be
+    ///     [shadowed], no `HEAD` catcher is registered without a `HEAD` route,
+    ///     and no mount point has no routes or only shadowed routes.
     ///   * No [`Sentinel`](crate::Sentinel) triggered an abort.
     ///
     /// If any of these conditions fail to be met, a respective [`Error`] is
     /// returned.
     ///
     /// [configured]: Rocket::figment()
+    /// [shadowed]: Route#shadowing
     ///
     /// # Example
     ///
@@ -497,6 +567,20 @@ impl Rocket<Build> {
         self.catchers.clone().into_iter().for_each(|c| router.add_catcher(c));
         router.finalize().map_err(ErrorKind::Collisions)?;
 
+        // Check for routes, catchers, and mount points that are unreachable.
+        let mut unreachable = router.unreachable();
+        unreachable.mounts.extend(self.empty_mounts.iter().cloned());
+        if !unreachable.is_empty() {
+            match config.unreachable {
+                Unreachable::Allow => {},
+                Unreachable::Warn => {
+                    warn!("The following items can never handle a request:");
+                    unreachable.pretty_print();
+                }
+                Unreachable::Deny => return Err(ErrorKind::Unreachable(unreachable).into()),
+            }
+        }
+
         // Finally, freeze managed state.
         self.state.freeze();
 
@@ -616,9 +700,9 @@ impl Rocket<Ignite> {
 
     async fn _launch(self) -> Result<(), Error> {
         self.into_orbit().default_tcp_http_server(|rkt| Box::pin(async move {
-            rkt.fairings.handle_liftoff(&rkt).await;
+            rkt.fairings.handle_liftoff(rkt).await;
 
-            let proto = rkt.config.tls_enabled().then(|| "https").unwrap_or("http");
+            let proto = if rkt.config.tls_enabled() { "https" } else { "http" };
             let addr = format!("{}://{}:{}", proto, rkt.config.address, rkt.config.port);
             launch_info!("{}{} {}",
                 Paint::emoji("🚀 "),
@@ -681,6 +765,58 @@ impl Rocket<Orbit> {
     pub fn shutdown(&self) -> Shutdown {
         self.shutdown.clone()
     }
+
+    /// Builds a URI for the route named `name` mounted at `base`, with its
+    /// dynamic parameters given values from `params`.
+    ///
+    /// This is the runtime counterpart of [`uri!`](crate::uri!) for code, such
+    /// as plugins and templates, that doesn't statically know the route. Route
+    /// names are those of the functions the routes were generated from. If
+    /// several routes with the name are mounted at `base`, the one with the
+    /// lowest rank is used. The parameters are checked and the URI is built
+    /// as described in [`Route::uri_for()`]. Returns an error if no route
+    /// matches or if the parameters don't fit the route.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::local::blocking::Client;
+    /// use rocket::route::UriForError;
+    ///
+    /// #[get("/<id:u64>/<slug>?<page>")]
+    /// fn post(id: u64, slug: &str, page: Option<usize>) { /* .. */ }
+    ///
+    /// let rocket = rocket::build().mount("/blog", routes![post]);
+    /// let client = Client::debug(rocket).unwrap();
+    ///
+    /// let uri = client.rocket().uri_for("post", "/blog", vec![("id", "7"), ("slug", "hi")]);
+    /// assert_eq!(uri.unwrap(), "/blog/7/hi");
+    ///
+    /// let params = vec![("id", "7"), ("slug", "two words"), ("page", "2")];
+    /// let uri = client.rocket().uri_for("post", "/blog", params);
+    /// assert_eq!(uri.unwrap(), "/blog/7/two%20words?page=2");
+    ///
+    /// let error = client.rocket().uri_for("post", "/", vec![("id", "7")]).unwrap_err();
+    /// assert!(matches!(error, UriForError::NoRoute { .. }));
+    /// ```
+    pub fn uri_for<I, K, V>(
+        &self,
+        name: &str,
+        base: &str,
+        params: I
+    ) -> Result<Origin<'static>, route::UriForError>
+        where I: IntoIterator<Item = (K, V)>, K: AsRef<str>, V: fmt::Display
+    {
+        let no_route = || route::UriForError::NoRoute { name: name.into(), base: base.into() };
+        let base = Origin::parse(base).map_err(|_| no_route())?.into_normalized();
+        self.routes()
+            .filter(|r| r.name.as_deref() == Some(name))
+            .filter(|r| r.uri.base.path().segments().eq(base.path().segments()))
+            .min_by_key(|r| r.rank)
+            .ok_or_else(no_route)?
+            .uri_for(params)
+    }
 }
 
 impl<P: Phase> Rocket<P> {
@@ -794,6 +930,57 @@ impl<P: Phase> Rocket<P> {
         Ok(rocket)
     }
 
+    pub(crate) async fn live_launch(self) -> Result<Arc<Rocket<Orbit>>, Error> {
+        let mut rocket = match self.0.into_state() {
+            State::Build(s) => Rocket::from(s).ignite().await?.into_orbit(),
+            State::Ignite(s) => Rocket::from(s).into_orbit(),
+            State::Orbit(s) => Rocket::from(s)
+        };
+
+        // Serve on an ephemeral local port. The server is owned by a client, so
+        // it must never react to signals nor forcibly terminate the process.
+        rocket.config.address = Ipv4Addr::LOCALHOST.into();
+        rocket.config.port = 0;
+        rocket.config.shutdown.ctrlc = false;
+        rocket.config.shutdown.force = false;
+        #[cfg(unix)] rocket.config.shutdown.signals.clear();
+
+        let (ready_tx, ready_rx) = oneshot::channel();
+        let (error_tx, error_rx) = oneshot::channel();
+        let server = rocket.default_tcp_http_server(|rkt| Box::pin(async move {
+            rkt.fairings.handle_liftoff(rkt).await;
+
+            let proto = if rkt.config.tls_enabled() { "https" } else { "http" };
+            let addr = format!("{}://{}:{}", proto, rkt.config.address, rkt.config.port);
+            launch_info!("{}{} {}",
+                Paint::emoji("🚀 "),
+                Paint::default("Rocket has launched into live orbit from").bold(),
+                Paint::default(addr).bold().underline());
+
+            let _ = ready_tx.send(rkt.clone());
+        }));
+
+        tokio::spawn(async move {
+            if let Err(e) = server.await {
+                if let Err(e) = error_tx.send(e) {
+                    error!("Live server failed: {}", e);
+                }
+            }
+        });
+
+        match ready_rx.await {
+            Ok(rocket) => Ok(rocket),
+            Err(_) => match error_rx.await {
+                Ok(e) => Err(e),
+                Err(_) => {
+                    let msg = "server terminated before launching";
+                    let error = io::Error::new(io::ErrorKind::Other, msg);
+                    Err(Error::new(ErrorKind::Runtime(Box::new(error))))
+                }
+            }
+        }
+    }
+
     /// Returns a `Future` that transitions this instance of `Rocket` from any
     /// phase into the _orbit_ phase. When `await`ed, the future drives the
     /// server forward, listening for and dispatching requests to mounted routes
@@ -4,9 +4,37 @@ mod route;
 mod handler;
 mod uri;
 mod segment;
+mod scope;
+mod reverse;
 
 pub use route::*;
 pub use handler::*;
 pub use uri::*;
+pub use scope::Scope;
+pub use reverse::UriForError;
 
 pub(crate) use segment::Segment;
+pub(crate) use scope::{Guard, ScopeFairings};
+
+/// Invoked by codegen for each `uuid` route segment constraint: fails to
+/// compile without the `uuid` feature, which such constraints require.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "uuid")]
+macro_rules! uuid_constraint {
+    () => ()
+}
+
+/// Invoked by codegen for each `uuid` route segment constraint: fails to
+/// compile without the `uuid` feature, which such constraints require.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "uuid"))]
+macro_rules! uuid_constraint {
+    () => (::std::compile_error!(
+        "route segment constraint `uuid` requires Rocket's `uuid` feature"
+    ))
+}
+
+#[doc(hidden)]
+pub use uuid_constraint;
@@ -0,0 +1,155 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+
+use crate::Route;
+use crate::http::uri::Origin;
+use crate::http::uri::fmt::{Formatter, Path, Query, UriDisplay};
+use crate::http::uri::fmt::{RouteUriBuilder, UriArgumentsKind, UriQueryArgument};
+
+/// An error building a URI for a route at runtime with [`Route::uri_for()`] or
+/// [`Rocket::uri_for()`](crate::Rocket::uri_for()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriForError {
+    /// No route with the name `name` is mounted at `base`.
+    NoRoute {
+        /// The requested route name.
+        name: String,
+        /// The requested mount point.
+        base: String,
+    },
+    /// The dynamic path parameter with this name was not given a value.
+    Missing(String),
+    /// A parameter with this name was given but the route has no parameter
+    /// that accepts it.
+    Unknown(String),
+    /// The value given for a path parameter is empty or does not satisfy the
+    /// parameter's constraint.
+    Invalid {
+        /// The parameter's name.
+        name: String,
+        /// The invalid value.
+        value: String,
+    },
+}
+
+impl fmt::Display for UriForError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UriForError::NoRoute { name, base } => {
+                write!(f, "no route named `{}` is mounted at `{}`", name, base)
+            }
+            UriForError::Missing(name) => write!(f, "missing value for parameter `{}`", name),
+            UriForError::Unknown(name) => write!(f, "route has no parameter `{}`", name),
+            UriForError::Invalid { name, value } => {
+                write!(f, "invalid value `{}` for parameter `{}`", value, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UriForError { }
+
+/// A static path segment. It's already a valid, percent-encoded segment, so
+/// it's emitted verbatim, as `uri!` does.
+struct Verbatim<'a>(&'a str);
+
+impl UriDisplay<Path> for Verbatim<'_> {
+    fn fmt(&self, f: &mut Formatter<'_, Path>) -> fmt::Result {
+        f.write_raw(self.0)
+    }
+}
+
+impl Route {
+    /// Builds an absolute URI for this route, including its mount point, with
+    /// the dynamic parameters in its path and query given values from
+    /// `params`.
+    ///
+    /// This is the runtime counterpart of [`uri!`](crate::uri!), intended for
+    /// code that doesn't statically know the route. Every parameter in the
+    /// route's path must be given a value: the value of a trailing parameter
+    /// may span several segments, while the value of any other parameter must
+    /// be nonempty and satisfy its constraint, if any. Parameters in the query
+    /// are optional. Parameters the route doesn't have are rejected unless the
+    /// route's query has a trailing parameter, in which case they're appended
+    /// to the query. Values are percent-encoded as by `uri!`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Route;
+    /// use rocket::route::UriForError;
+    /// use rocket::http::Method;
+    /// # use rocket::route::dummy_handler as handler;
+    ///
+    /// let route = Route::new(Method::Get, "/item/<id:u64>?<lang>", handler);
+    /// let uri = route.uri_for(vec![("id", "10"), ("lang", "en")]).unwrap();
+    /// assert_eq!(uri, "/item/10?lang=en");
+    ///
+    /// let uri = route.uri_for(vec![("id", 10)]).unwrap();
+    /// assert_eq!(uri, "/item/10");
+    ///
+    /// let error = route.uri_for(vec![("id", "ten")]).unwrap_err();
+    /// assert_eq!(error, UriForError::Invalid { name: "id".into(), value: "ten".into() });
+    ///
+    /// let error = route.uri_for(vec![("lang", "en")]).unwrap_err();
+    /// assert_eq!(error, UriForError::Missing("id".into()));
+    /// ```
+    pub fn uri_for<I, K, V>(&self, params: I) -> Result<Origin<'static>, UriForError>
+        where I: IntoIterator<Item = (K, V)>, K: AsRef<str>, V: fmt::Display
+    {
+        let mut params: IndexMap<String, String> = params.into_iter()
+            .map(|(k, v)| (k.as_ref().to_string(), v.to_string()))
+            .collect();
+
+        let metadata = &self.uri.metadata;
+        let mut path_values: Vec<Box<dyn UriDisplay<Path> + '_>> = vec![];
+        for segment in &metadata.path_segs {
+            if !segment.dynamic {
+                path_values.push(Box::new(Verbatim(&segment.value)));
+                continue;
+            }
+
+            let value = params.shift_remove(&segment.value)
+                .ok_or_else(|| UriForError::Missing(segment.value.clone()))?;
+
+            let valid = segment.trailing || !value.is_empty() && segment.matches(&value);
+            if !valid {
+                return Err(UriForError::Invalid { name: segment.value.clone(), value });
+            }
+
+            match segment.trailing {
+                true => path_values.push(Box::new(PathBuf::from(value))),
+                false => path_values.push(Box::new(value)),
+            }
+        }
+
+        let mut query_values: Vec<(String, Option<String>)> = vec![];
+        for segment in &metadata.query_segs {
+            if !segment.dynamic {
+                query_values.push((segment.value.clone(), None));
+            } else if segment.trailing {
+                query_values.extend(params.drain(..).map(|(k, v)| (k, Some(v))));
+            } else if let Some(value) = params.shift_remove(&segment.value) {
+                query_values.push((segment.value.clone(), Some(value)));
+            }
+        }
+
+        if let Some((name, _)) = params.into_iter().next() {
+            return Err(UriForError::Unknown(name));
+        }
+
+        let path_args: Vec<&dyn UriDisplay<Path>> = path_values.iter().map(|v| &**v).collect();
+
+        let query_args: Vec<UriQueryArgument<'_>> = query_values.iter()
+            .map(|(name, value)| match value {
+                Some(value) => UriQueryArgument::NameValue(name, value as &dyn UriDisplay<Query>),
+                None => UriQueryArgument::Raw(name),
+            })
+            .collect();
+
+        let query_args = (!query_args.is_empty()).then(|| UriArgumentsKind::Dynamic(&*query_args));
+        Ok(RouteUriBuilder::new(UriArgumentsKind::Dynamic(&path_args), query_args).render())
+    }
+}
@@ -4,8 +4,10 @@ use std::borrow::Cow;
 
 use yansi::Paint;
 
-use crate::http::{uri, Method, MediaType};
-use crate::route::{Handler, RouteUri, BoxFuture};
+use crate::http::{uri, Method, MediaType, Header};
+use crate::response::Response;
+use crate::http::uri::HostPattern;
+use crate::route::{Handler, RouteUri, BoxFuture, Guard, ScopeFairings};
 use crate::sentinel::Sentry;
 
 /// A request handling route.
@@ -18,7 +20,7 @@ use crate::sentinel::Sentry;
 /// ```rust
 /// # #[macro_use] extern crate rocket;
 /// # use std::path::PathBuf;
-/// #[get("/route/<path..>?query", rank = 2, format = "json")]
+/// #[get("/route/<path..>?query", rank = 2, format = "json", host = "api.*")]
 /// fn route_name(path: PathBuf) { /* handler procedure */ }
 ///
 /// use rocket::http::{Method, MediaType};
@@ -29,18 +31,60 @@ use crate::sentinel::Sentry;
 /// assert_eq!(route.uri, "/route/<path..>?query");
 /// assert_eq!(route.rank, 2);
 /// assert_eq!(route.format.unwrap(), MediaType::JSON);
+/// assert_eq!(route.host.unwrap().to_string(), "api.*");
 /// ```
 ///
-/// Note that the `rank` and `format` attribute parameters are optional. See
-/// [`#[route]`](macro@crate::route) for details on macro usage. Note also that
-/// a route's mounted _base_ becomes part of its URI; see [`RouteUri`] for
-/// details.
+/// Note that the `rank`, `format`, and `host` attribute parameters are
+/// optional. See [`#[route]`](macro@crate::route) for details on macro usage.
+/// Note also that a route's mounted _base_ becomes part of its URI; see
+/// [`RouteUri`] for details. Routes mounted with
+/// [`Rocket::mount_host()`](crate::Rocket::mount_host()) take on its host.
+///
+/// # Metadata
+///
+/// Routes can carry metadata that doesn't affect routing but can be inspected,
+/// via [`Rocket::routes()`](crate::Rocket::routes()), to build API indexes:
+/// `tags`, a `summary`, and `docs`, which for generated routes is the doc
+/// comment of the handler. These, as well as `deprecated` and `sunset`, can be
+/// set in the route attribute:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// /// Returns the user with id `id`.
+/// #[get("/user/<id>", tags = ["users"], summary = "Get a user.")]
+/// fn user(id: usize) { /* .. */ }
+///
+/// /// Superseded by `user`.
+/// #[get("/users/<id>", deprecated, sunset = "2030-01-01")]
+/// fn old_user(id: usize) { /* .. */ }
+///
+/// let route = routes![user].remove(0);
+/// assert_eq!(route.tags, ["users"]);
+/// assert_eq!(route.summary.unwrap(), "Get a user.");
+/// assert_eq!(route.docs.unwrap(), "Returns the user with id `id`.");
+///
+/// let route = routes![old_user].remove(0);
+/// assert!(route.deprecated);
+/// assert_eq!(route.sunset().unwrap(), "2030-01-01");
+/// ```
+///
+/// ## Deprecation
+///
+/// Responses to requests routed to a `deprecated` route carry a `Deprecation:
+/// true` header and, if the route has a `sunset` date, a `Sunset` header
+/// ([RFC 8594]) with the date, unless the response already sets them.
+///
+/// [RFC 8594]: https://tools.ietf.org/html/rfc8594
 ///
 /// # Routing
 ///
 /// A request _matches_ a route _iff_:
 ///
 ///   * The route's method matches that of the incoming request.
+///   * The route's host pattern (if any) matches the incoming request's host.
+///     - If route specifies a host, it only matches requests with a `Host`
+///       whose domain matches the [`HostPattern`].
+///     - If route doesn't specify a host, it matches requests for any host.
 ///   * The route's format (if any) matches that of the incoming request.
 ///     - If route specifies a format, it only matches requests for that format.
 ///     - If route doesn't specify a format, it matches requests for any format.
@@ -50,6 +94,8 @@ use crate::sentinel::Sentry;
 ///     - Non-specific `Accept` header components (`*`) match anything.
 ///   * All static components in the route's path match the corresponding
 ///     components in the same position in the incoming request.
+///   * All constrained dynamic components in the route's path satisfy their
+///     constraints. See [Constraints](#constraints).
 ///   * All static components in the route's query string are also in the
 ///     request query string, though in any position. If there is no query
 ///     in the route, requests with and without queries match.
@@ -70,6 +116,80 @@ use crate::sentinel::Sentry;
 /// parameters are allowed -- queries do not directly impact whether two routes
 /// collide.
 ///
+/// Routes whose paths differ in a constrained dynamic component collide only if
+/// the two constraints can be satisfied by the same value, or, for a constraint
+/// and a static component, if the static component satisfies the constraint.
+/// See [Constraints](#constraints).
+///
+/// Routes with host patterns collide only if some domain matches both
+/// patterns: `api.*` and `*.example.com` collide, while `api.example.com` and
+/// `www.example.com` do not. Like a route without a `format`, a route without a
+/// `host` matches requests for any host, so it collides with routes that do
+/// specify a host. Use ranks to fall back to host-agnostic routes.
+///
+/// ## Shadowing
+///
+/// A route is _shadowed_ when a route with a lower rank always handles the
+/// requests it matches, so it can never be reached. At ignite time, Rocket
+/// reports the shadowed routes it detects, along with `HEAD` catchers that can
+/// never be selected and mount points at which no routes were mounted or every
+/// route is shadowed, as configured by
+/// [`Config::unreachable`](crate::Config::unreachable): as a warning by
+/// default, as an error that aborts launch if `deny`, or not at all if
+/// `allow`. The checks are conservative, so some unreachable items go
+/// unreported.
+///
+/// Because a route that forwards yields to higher-ranked routes, a route only
+/// shadows another if it can never forward: it must be generated for a handler
+/// without any guards and not be mounted in a scope with guards. It must also
+/// match every request the other route matches. For example, `fallback` below
+/// is shadowed by `all`, but not by `user`, whose `id` guard forwards when the
+/// segment isn't a `usize`:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// #[get("/<id>", rank = 1)]
+/// fn user(id: usize) { /* .. */ }
+///
+/// #[get("/<_..>", rank = 2)]
+/// fn all() { /* .. */ }
+///
+/// #[get("/<_>", rank = 3)]
+/// fn fallback() { /* .. */ }
+/// ```
+///
+/// A catcher can never be selected if it only matches `HEAD` requests but no
+/// `HEAD` route is mounted: Rocket retries `HEAD` requests no route handles as
+/// `GET` requests.
+///
+/// ## Constraints
+///
+/// A single dynamic path component can be _constrained_ as `<name:constraint>`
+/// so that it only matches request segments satisfying the constraint. The
+/// constraint is one of:
+///
+///   * An integer type, like `u64` or `i8`, satisfied by values that parse as
+///     that type.
+///   * `uuid`, satisfied by UUIDs. This requires the `uuid` feature: without
+///     it, routes with `uuid` constraints fail to compile or, when built with
+///     [`Route::new()`], panic.
+///   * Any other string, a regular expression that must match the entire
+///     segment, which cannot contain `/` or `?`.
+///
+/// Constraints overlap, and thus routes differing in them collide, unless the
+/// values satisfying them can't begin with the same character. For example,
+/// `<id:u64>` and `<name:[a-z]+>` never collide, but `<id:u64>` and
+/// `<hex:[0-9a-f]+>` do, as does `<id:u64>` and the unconstrained `<name>`.
+///
+/// ```rust
+/// use rocket::Route;
+/// use rocket::http::Method;
+/// # use rocket::route::dummy_handler as handler;
+///
+/// let by_id = Route::new(Method::Get, "/item/<id:u64>", handler);
+/// let by_slug = Route::new(Method::Get, "/item/<slug:[a-z-]+>", handler);
+/// ```
+///
 /// ## Resolving Collisions
 ///
 /// Collisions are resolved through _ranking_. Routes with lower ranks have
@@ -188,8 +308,28 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The pattern the request's host must match, if any.
+    pub host: Option<HostPattern>,
+    /// Whether this route is deprecated. See [Deprecation](#deprecation).
+    pub deprecated: bool,
+    /// The date after which this deprecated route may become unavailable.
+    pub(crate) sunset: Option<time::Date>,
+    /// The tags of this route, used to group routes in API indexes.
+    pub tags: Vec<Cow<'static, str>>,
+    /// A short summary of this route, if any.
+    pub summary: Option<Cow<'static, str>>,
+    /// The documentation of this route, if any. For generated routes, this is
+    /// the doc comment of the handler.
+    pub docs: Option<Cow<'static, str>>,
+    /// Whether the handler may forward. Only routes generated for handlers
+    /// without guards are known not to.
+    pub(crate) may_forward: bool,
     /// The discovered sentinels.
     pub(crate) sentinels: Vec<Sentry>,
+    /// The guards of the scopes containing this route, outermost first.
+    pub(crate) guards: Vec<Guard>,
+    /// The fairings of the scopes containing this route, outermost first.
+    pub(crate) fairings: ScopeFairings,
 }
 
 impl Route {
@@ -198,7 +338,8 @@ impl Route {
     ///
     /// # Panics
     ///
-    /// Panics if `path` is not a valid Rocket route URI.
+    /// Panics if `path` is not a valid Rocket route URI or has an invalid
+    /// constraint.
     ///
     /// # Example
     ///
@@ -223,7 +364,8 @@ impl Route {
     ///
     /// # Panics
     ///
-    /// Panics if `path` is not a valid Rocket route URI.
+    /// Panics if `path` is not a valid Rocket route URI or has an invalid
+    /// constraint.
     ///
     /// # Example
     ///
@@ -250,7 +392,16 @@ impl Route {
         Route {
             name: None,
             format: None,
+            host: None,
+            deprecated: false,
+            sunset: None,
+            tags: Vec::new(),
+            summary: None,
+            docs: None,
+            may_forward: true,
             sentinels: Vec::new(),
+            guards: Vec::new(),
+            fairings: ScopeFairings::default(),
             handler: Box::new(handler),
             rank, uri, method,
         }
@@ -288,6 +439,66 @@ impl Route {
         self.uri = RouteUri::try_new(&base, &self.uri.unmounted_origin.to_string())?;
         Ok(self)
     }
+
+    /// The date, as `YYYY-MM-DD`, after which this deprecated route may become
+    /// unavailable, if any. See [Deprecation](#deprecation).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Route;
+    /// use rocket::http::Method;
+    /// # use rocket::route::dummy_handler as handler;
+    ///
+    /// let route = Route::new(Method::Get, "/", handler);
+    /// assert!(route.sunset().is_none());
+    ///
+    /// let date = time::Date::try_from_ymd(2030, 1, 1).unwrap();
+    /// let route = route.with_sunset(date);
+    /// assert_eq!(route.sunset().unwrap(), "2030-01-01");
+    /// ```
+    pub fn sunset(&self) -> Option<String> {
+        self.sunset.map(|date| date.format("%F"))
+    }
+
+    /// Sets the date after which this deprecated route may become unavailable
+    /// to `date`. See [Deprecation](#deprecation).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Route;
+    /// use rocket::http::Method;
+    /// # use rocket::route::dummy_handler as handler;
+    ///
+    /// let date = time::Date::try_from_ymd(2030, 1, 1).unwrap();
+    /// let mut route = Route::new(Method::Get, "/", handler).with_sunset(date);
+    /// route.deprecated = true;
+    /// assert_eq!(route.sunset().unwrap(), "2030-01-01");
+    /// ```
+    pub fn with_sunset(mut self, date: time::Date) -> Self {
+        self.sunset = Some(date);
+        self
+    }
+
+    /// Sets the `Deprecation` and, if there's a sunset date, `Sunset` headers
+    /// in `response` if this route is deprecated, unless they're already set.
+    pub(crate) fn set_deprecation_headers(&self, response: &mut Response<'_>) {
+        if !self.deprecated {
+            return;
+        }
+
+        if !response.headers().contains("Deprecation") {
+            response.set_header(Header::new("Deprecation", "true"));
+        }
+
+        if let Some(sunset) = self.sunset {
+            if !response.headers().contains("Sunset") {
+                let date = sunset.format("%a, %d %b %Y 00:00:00 GMT");
+                response.set_header(Header::new("Sunset", date));
+            }
+        }
+    }
 }
 
 impl fmt::Display for Route {
@@ -297,6 +508,10 @@ impl fmt::Display for Route {
         }
 
         write!(f, "{} ", Paint::green(&self.method))?;
+        if let Some(ref host) = self.host {
+            write!(f, "{}", Paint::magenta(host))?;
+        }
+
         if self.uri.base() != "/" {
             write!(f, "{}", Paint::blue(self.uri.base()).underline())?;
         }
@@ -311,6 +526,10 @@ impl fmt::Display for Route {
             write!(f, " {}", Paint::yellow(format))?;
         }
 
+        if self.deprecated {
+            write!(f, " {}", Paint::red("deprecated").italic())?;
+        }
+
         Ok(())
     }
 }
@@ -323,6 +542,12 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("host", &self.host)
+            .field("deprecated", &self.deprecated)
+            .field("sunset", &self.sunset)
+            .field("tags", &self.tags)
+            .field("summary", &self.summary)
+            .field("docs", &self.docs)
             .finish()
     }
 }
@@ -342,6 +567,20 @@ pub struct StaticInfo {
     pub handler: for<'r> fn(&'r crate::Request<'_>, crate::Data<'r>) -> BoxFuture<'r>,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The route's host pattern, if any.
+    pub host: Option<&'static str>,
+    /// Whether the route is deprecated.
+    pub deprecated: bool,
+    /// The route's `YYYY-MM-DD` sunset date, if any.
+    pub sunset: Option<&'static str>,
+    /// The route's tags.
+    pub tags: &'static [&'static str],
+    /// The route's summary, if any.
+    pub summary: Option<&'static str>,
+    /// The handler's doc comment, if any.
+    pub docs: Option<&'static str>,
+    /// Whether the handler may forward, i.e, has any guards.
+    pub may_forward: bool,
     /// Route-derived sentinels, if any.
     /// This isn't `&'static [SentryInfo]` because `type_name()` isn't `const`.
     pub sentinels: Vec<Sentry>,
@@ -350,8 +589,11 @@ pub struct StaticInfo {
 #[doc(hidden)]
 impl From<StaticInfo> for Route {
     fn from(info: StaticInfo) -> Route {
-        // This should never panic since `info.path` is statically checked.
+        // These should never panic since `info.path`, `info.host`, and
+        // `info.sunset` are statically checked.
         let uri = RouteUri::new("/", info.uri);
+        let host = info.host.map(|host| HostPattern::parse(host).expect("valid host pattern"));
+        let sunset = info.sunset.map(|date| time::Date::parse(date, "%F").expect("valid date"));
 
         Route {
             name: Some(info.name.into()),
@@ -359,8 +601,15 @@ impl From<StaticInfo> for Route {
             handler: Box::new(info.handler),
             rank: info.rank.unwrap_or_else(|| uri.default_rank()),
             format: info.format,
+            deprecated: info.deprecated,
+            tags: info.tags.iter().map(|&tag| tag.into()).collect(),
+            summary: info.summary.map(Cow::Borrowed),
+            docs: info.docs.map(Cow::Borrowed),
+            may_forward: info.may_forward,
             sentinels: info.sentinels.into_iter().collect(),
-            uri,
+            guards: Vec::new(),
+            fairings: ScopeFairings::default(),
+            uri, host, sunset,
         }
     }
 }
@@ -0,0 +1,363 @@
+use std::fmt;
+use std::sync::Arc;
+
+use yansi::Paint;
+
+use crate::{Request, Response, Route, Data};
+use crate::fairing::{Fairing, Kind};
+use crate::request::{self, FromRequest};
+use crate::route::BoxFuture;
+use crate::outcome::Outcome;
+use crate::http::Status;
+
+/// A request guard run by a [`Scope`] before any of its routes.
+#[derive(Clone, Copy)]
+pub(crate) struct Guard {
+    /// The name of the guard's type.
+    name: &'static str,
+    /// Runs the guard against a request, discarding any value it derives.
+    check: for<'r> fn(&'r Request<'_>) -> BoxFuture<'r, Outcome<(), Status, ()>>,
+}
+
+impl Guard {
+    /// A guard that runs the [`FromRequest`] implementation of `T`.
+    fn of<T>() -> Guard
+        where T: for<'r> FromRequest<'r>,
+              for<'r> <T as FromRequest<'r>>::Error: Send + 'static
+    {
+        fn check<'r, T>(req: &'r Request<'_>) -> BoxFuture<'r, Outcome<(), Status, ()>>
+            where T: for<'x> FromRequest<'x>,
+                  for<'x> <T as FromRequest<'x>>::Error: Send + 'static
+        {
+            Box::pin(async move { resolve::<T, _>(req, T::from_request(req).await) })
+        }
+
+        Guard { name: std::any::type_name::<T>(), check: check::<T> }
+    }
+
+    /// A guard that runs the [`FromRequest`] implementation of `&T`.
+    fn of_ref<T: ?Sized + 'static>() -> Guard
+        where for<'r> &'r T: FromRequest<'r>,
+              for<'r> <&'r T as FromRequest<'r>>::Error: Send + 'static
+    {
+        fn check<'r, T>(req: &'r Request<'_>) -> BoxFuture<'r, Outcome<(), Status, ()>>
+            where T: ?Sized + 'static,
+                  for<'x> &'x T: FromRequest<'x>,
+                  for<'x> <&'x T as FromRequest<'x>>::Error: Send + 'static
+        {
+            Box::pin(async move { resolve::<&T, _>(req, <&T>::from_request(req).await) })
+        }
+
+        Guard { name: std::any::type_name::<&T>(), check: check::<T> }
+    }
+}
+
+/// Discards the value of a guard of type `T` with outcome `outcome`. A failing
+/// guard's error value is stashed on `req` for typed catchers.
+fn resolve<T, E>(req: &Request<'_>, outcome: request::Outcome<T, E>) -> Outcome<(), Status, ()>
+    where E: fmt::Debug + Send + 'static
+{
+    let name = std::any::type_name::<T>();
+    match outcome {
+        Outcome::Success(_) => Outcome::Success(()),
+        Outcome::Forward(_) => {
+            warn_!("Scope guard `{}` is forwarding.", name);
+            Outcome::Forward(())
+        }
+        Outcome::Failure((status, e)) => {
+            warn_!("Scope guard `{}` failed: {:?}.", name, e);
+            req.set_error(e);
+            Outcome::Failure(status)
+        }
+    }
+}
+
+/// A set of routes sharing request guards and fairings.
+///
+/// A `Scope` wraps routes so that every one of them is protected by the same
+/// request guards and observed by the same request and response fairings,
+/// without each handler declaring them. A `Scope` converts into a
+/// `Vec<Route>`, so it can be passed directly to [`Rocket::mount()`]:
+///
+///   * **Guards**, added with [`Scope::guard()`] or [`Scope::guard_ref()`],
+///     are run in the order they were added after a request is routed to a
+///     route in the scope and before its handler is called. If a guard
+///     forwards, the request is forwarded to the next matching route. If a
+///     guard fails, the request fails with the guard's status, and the error
+///     catcher for that status is invoked with the guard's error value, which
+///     a [typed catcher](crate::catcher::TypedError) can receive. Guard
+///     values are discarded, but since guards are ordinary [`FromRequest`]
+///     types, they can use [`Request::local_cache()`] to share work with
+///     handlers that also use them.
+///
+///   * **Fairings**, added with [`Scope::attach()`], only have their
+///     [`on_request`](Fairing::on_request) and
+///     [`on_response`](Fairing::on_response) callbacks invoked. A request
+///     fairing runs after all global request fairings when the first route the
+///     request is routed to, the highest ranked matching route, is in the
+///     scope. A response fairing runs before all global response fairings when
+///     a route in the scope handles the request: it does not run for responses
+///     produced by catchers, even after a guard or handler in the scope fails.
+///     Fairings of other kinds are ignored.
+///
+/// A route retains its scopes when it is remounted with [`Route::map_base()`]
+/// or [`Rocket::mount()`]. Scopes nest: when a route already in a scope is
+/// wrapped in another, the outer scope's guards and fairings run first.
+///
+/// [`Rocket::mount()`]: crate::Rocket::mount()
+///
+/// # Example
+///
+/// Every route mounted at `/admin` below requires an `AdminUser`, and every
+/// response from them is marked as uncacheable. The route at `/` is
+/// unaffected.
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{self, Request, FromRequest};
+/// use rocket::fairing::AdHoc;
+/// use rocket::route::Scope;
+/// use rocket::http::{Header, Status};
+///
+/// struct AdminUser;
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for AdminUser {
+///     type Error = ();
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+///         match req.headers().get_one("x-admin-token") {
+///             Some("secret") => request::Outcome::Success(AdminUser),
+///             _ => request::Outcome::Failure((Status::Unauthorized, ())),
+///         }
+///     }
+/// }
+///
+/// #[get("/")]
+/// fn index() -> &'static str { "Hello, world!" }
+///
+/// #[get("/")]
+/// fn dashboard() -> &'static str { "Admin dashboard" }
+///
+/// #[delete("/user/<id>")]
+/// fn delete_user(id: usize) { /* .. */ }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     let no_cache = AdHoc::on_response("No Cache", |_, res| Box::pin(async move {
+///         res.set_header(Header::new("Cache-Control", "no-store"));
+///     }));
+///
+///     let admin = Scope::new(routes![dashboard, delete_user])
+///         .guard::<AdminUser>()
+///         .attach(no_cache);
+///
+///     rocket::build()
+///         .mount("/", routes![index])
+///         .mount("/admin", admin)
+/// }
+/// ```
+pub struct Scope {
+    routes: Vec<Route>,
+    guards: Vec<Guard>,
+    fairings: Vec<Arc<dyn Fairing>>,
+}
+
+impl Scope {
+    /// Creates a scope containing `routes` with no guards or fairings.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::route::Scope;
+    ///
+    /// #[get("/")]
+    /// fn index() { /* .. */ }
+    ///
+    /// let scope = Scope::new(routes![index]);
+    /// assert_eq!(scope.routes().count(), 1);
+    /// ```
+    pub fn new<R: Into<Vec<Route>>>(routes: R) -> Scope {
+        Scope { routes: routes.into(), guards: vec![], fairings: vec![] }
+    }
+
+    /// Adds the request guard `T` to the scope. `T` is run before the handler
+    /// of every route in the scope, after any previously added guards.
+    ///
+    /// `T` must not borrow from the request. Use [`Scope::guard_ref()`] for
+    /// guards like `&State<T>` that do.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::route::Scope;
+    /// use std::net::IpAddr;
+    ///
+    /// #[get("/")]
+    /// fn index() { /* .. */ }
+    ///
+    /// // Requests without a known client IP address are forwarded.
+    /// let scope = Scope::new(routes![index]).guard::<IpAddr>();
+    /// ```
+    pub fn guard<T>(mut self) -> Self
+        where T: for<'r> FromRequest<'r>,
+              for<'r> <T as FromRequest<'r>>::Error: Send + 'static
+    {
+        self.guards.push(Guard::of::<T>());
+        self
+    }
+
+    /// Adds the request guard `&T`, which borrows from the request, to the
+    /// scope. Otherwise identical to [`Scope::guard()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::route::Scope;
+    /// use rocket::State;
+    ///
+    /// struct Db;
+    ///
+    /// #[get("/")]
+    /// fn index() { /* .. */ }
+    ///
+    /// // Requests fail with a `500` unless a `Db` is managed.
+    /// let scope = Scope::new(routes![index]).guard_ref::<State<Db>>();
+    /// ```
+    pub fn guard_ref<T: ?Sized + 'static>(mut self) -> Self
+        where for<'r> &'r T: FromRequest<'r>,
+              for<'r> <&'r T as FromRequest<'r>>::Error: Send + 'static
+    {
+        self.guards.push(Guard::of_ref::<T>());
+        self
+    }
+
+    /// Attaches `fairing` to the scope. Only the request and response
+    /// callbacks of `fairing` are invoked, and only for requests to routes in
+    /// the scope.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::route::Scope;
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// #[get("/")]
+    /// fn index() { /* .. */ }
+    ///
+    /// let scope = Scope::new(routes![index])
+    ///     .attach(AdHoc::on_request("Log", |req, _| Box::pin(async move {
+    ///         println!("scoped request: {}", req);
+    ///     })));
+    /// ```
+    pub fn attach<F: Fairing>(mut self, fairing: F) -> Self {
+        let info = fairing.info();
+        if !info.kind.is(Kind::Request) && !info.kind.is(Kind::Response) {
+            warn!("Scope fairing `{}` has no request or response callback.",
+                Paint::default(info.name).bold());
+            warn_!("It will never be invoked.");
+        }
+
+        self.fairings.push(Arc::new(fairing));
+        self
+    }
+
+    /// Returns an iterator over the routes in the scope.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::route::Scope;
+    ///
+    /// #[get("/")]
+    /// fn index() { /* .. */ }
+    ///
+    /// #[get("/hello")]
+    /// fn hello() { /* .. */ }
+    ///
+    /// let scope = Scope::new(routes![index, hello]);
+    /// let paths: Vec<_> = scope.routes().map(|r| r.uri.path()).collect();
+    /// assert_eq!(paths, ["/", "/hello"]);
+    /// ```
+    pub fn routes(&self) -> impl Iterator<Item = &Route> + Clone {
+        self.routes.iter()
+    }
+}
+
+impl From<Scope> for Vec<Route> {
+    fn from(scope: Scope) -> Self {
+        let Scope { routes, guards, fairings } = scope;
+        routes.into_iter()
+            .map(|mut route| {
+                route.guards.splice(0..0, guards.iter().copied());
+                route.fairings.all.splice(0..0, fairings.iter().cloned());
+                route
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("routes", &self.routes)
+            .field("guards", &self.guards.iter().map(|g| g.name).collect::<Vec<_>>())
+            .field("fairings", &self.fairings.iter().map(|f| f.info().name).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Route {
+    /// Runs the guards of this route's scopes against `req`, in order,
+    /// stopping at the first that doesn't succeed.
+    pub(crate) async fn check_guards(&self, req: &Request<'_>) -> Outcome<(), Status, ()> {
+        for guard in &self.guards {
+            match (guard.check)(req).await {
+                Outcome::Success(()) => continue,
+                outcome => return outcome,
+            }
+        }
+
+        Outcome::Success(())
+    }
+
+    /// Runs the request callbacks of this route's scope fairings. The
+    /// fairings must have been indexed with [`ScopeFairings::index()`].
+    pub(crate) async fn handle_request(&self, req: &mut Request<'_>, data: &mut Data<'_>) {
+        for fairing in &self.fairings.request {
+            fairing.on_request(req, data).await;
+        }
+    }
+
+    /// Runs the response callbacks of this route's scope fairings. The
+    /// fairings must have been indexed with [`ScopeFairings::index()`].
+    pub(crate) async fn handle_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        for fairing in &self.fairings.response {
+            fairing.on_response(req, res).await;
+        }
+    }
+}
+
+/// The fairings of the scopes containing a route, outermost first.
+#[derive(Clone, Default)]
+pub(crate) struct ScopeFairings {
+    all: Vec<Arc<dyn Fairing>>,
+    request: Vec<Arc<dyn Fairing>>,
+    response: Vec<Arc<dyn Fairing>>,
+}
+
+impl ScopeFairings {
+    /// Indexes the fairings by callback so that requests don't need to query
+    /// their kinds. Called when the route is added to the router at ignite.
+    pub(crate) fn index(&mut self) {
+        let all = &self.all;
+        let with = |kind| all.iter().filter(|f| f.info().kind.is(kind)).cloned().collect();
+        self.request = with(Kind::Request);
+        self.response = with(Kind::Response);
+    }
+}
@@ -1,3 +1,6 @@
+use regex::Regex;
+use regex_syntax::hir::{Class, ClassUnicode, ClassUnicodeRange, Hir, HirKind};
+
 use crate::http::RawStr;
 
 #[derive(Debug, Clone)]
@@ -5,13 +8,30 @@ pub struct Segment {
     pub value: String,
     pub dynamic: bool,
     pub trailing: bool,
+    pub constraint: Option<Constraint>,
+}
+
+/// A constraint on the values a dynamic path segment matches, as in
+/// `<id:u64>`, `<id:uuid>`, or `<name:[a-z]+>`.
+#[derive(Debug, Clone)]
+pub enum Constraint {
+    /// An integer type: the segment must parse as a value of the type.
+    Integer { signed: bool, parse: fn(&str) -> bool },
+    /// A UUID in any format accepted by its `FromParam` implementation.
+    #[cfg(feature = "uuid")]
+    Uuid,
+    /// A regular expression that must match the entire segment, along with the
+    /// set of characters nonempty matches can begin with.
+    Regex(Regex, ClassUnicode),
 }
 
 impl Segment {
     pub fn from(segment: &RawStr) -> Self {
+        let segment = segment.as_str();
         let mut value = segment;
         let mut dynamic = false;
         let mut trailing = false;
+        let mut constraint = None;
 
         if segment.starts_with('<') && segment.ends_with('>') {
             dynamic = true;
@@ -21,8 +41,174 @@ impl Segment {
                 trailing = true;
                 value = &value[..(value.len() - 2)];
             }
+
+            if let Some((name, source)) = value.split_once(':') {
+                if trailing {
+                    panic!("trailing parameter `<{}..>` cannot be constrained", value);
+                }
+
+                value = name;
+                constraint = Some(Constraint::parse(source));
+            }
+        }
+
+        Segment { value: value.to_string(), dynamic, trailing, constraint }
+    }
+
+    /// Returns `true` if `segment`, a segment of a request's path, matches.
+    pub fn matches(&self, segment: &str) -> bool {
+        match self.dynamic {
+            true => self.constraint.as_ref().map_or(true, |c| c.matches(segment)),
+            false => self.value == segment,
+        }
+    }
+
+    /// Returns `true` if some request segment matches both `self` and `other`.
+    pub fn overlaps(&self, other: &Segment) -> bool {
+        match (self.dynamic, other.dynamic) {
+            (true, true) => match (&self.constraint, &other.constraint) {
+                (Some(a), Some(b)) => a.overlaps(b),
+                _ => true,
+            },
+            (true, false) => self.matches(&other.value),
+            (false, true) => other.matches(&self.value),
+            (false, false) => self.value == other.value,
+        }
+    }
+
+    /// Returns `true` if every request segment `other` matches, `self` matches.
+    /// Neither segment may be trailing.
+    pub fn covers(&self, other: &Segment) -> bool {
+        match (self.dynamic, other.dynamic) {
+            (true, _) if self.constraint.is_none() => true,
+            (true, true) => match (&self.constraint, &other.constraint) {
+                (Some(a), Some(b)) => a.same_as(b),
+                _ => false,
+            },
+            (true, false) => self.matches(&other.value),
+            (false, true) => false,
+            (false, false) => self.value == other.value,
+        }
+    }
+}
+
+macro_rules! integer_constraint {
+    ($name:expr, $($T:ident),*) => (
+        match $name {
+            $(stringify!($T) => Some(Constraint::Integer {
+                signed: <$T>::MIN != 0,
+                parse: |s| s.parse::<$T>().is_ok(),
+            }),)*
+            _ => None
+        }
+    )
+}
+
+impl Constraint {
+    /// Parses the constraint in `<name:source>`. An integer type name or
+    /// `uuid` is a type constraint. Anything else is a regular expression.
+    ///
+    /// Panics if `source` is an invalid regular expression or, without the
+    /// `uuid` feature, is `uuid`. Codegen rejects both at compile time.
+    fn parse(source: &str) -> Constraint {
+        let integer = integer_constraint!(source,
+            u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+        if let Some(constraint) = integer {
+            return constraint;
+        } else if source == "uuid" {
+            #[cfg(feature = "uuid")]
+            return Constraint::Uuid;
+
+            #[cfg(not(feature = "uuid"))]
+            panic!("route segment constraint `uuid` requires the `uuid` feature");
+        }
+
+        let hir = regex_syntax::Parser::new().parse(source)
+            .unwrap_or_else(|e| panic!("invalid route segment constraint `{}`: {}", source, e));
+
+        let regex = Regex::new(&format!("^(?:{})$", source))
+            .unwrap_or_else(|e| panic!("invalid route segment constraint `{}`: {}", source, e));
+
+        Constraint::Regex(regex, first_chars(&hir))
+    }
+
+    /// Returns `true` if `segment` satisfies this constraint.
+    pub fn matches(&self, segment: &str) -> bool {
+        match self {
+            Constraint::Integer { parse, .. } => parse(segment),
+            #[cfg(feature = "uuid")]
+            Constraint::Uuid => uuid_::Uuid::parse_str(segment).is_ok(),
+            Constraint::Regex(regex, _) => regex.is_match(segment),
         }
+    }
+
+    /// Returns `true` unless no segment can satisfy both constraints. This is
+    /// decided conservatively by the characters the segments can begin with.
+    pub fn overlaps(&self, other: &Constraint) -> bool {
+        let mut first = self.first_chars();
+        first.intersect(&other.first_chars());
+        !first.ranges().is_empty()
+    }
 
-        Segment { value: value.to_string(), dynamic, trailing }
+    /// Returns `true` if the constraints are known to be satisfied by the same
+    /// segments. This is decided conservatively: only `uuid` constraints and
+    /// identical regular expressions are.
+    fn same_as(&self, other: &Constraint) -> bool {
+        match (self, other) {
+            #[cfg(feature = "uuid")]
+            (Constraint::Uuid, Constraint::Uuid) => true,
+            (Constraint::Regex(a, _), Constraint::Regex(b, _)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+
+    /// The set of characters a segment satisfying the constraint can begin with.
+    fn first_chars(&self) -> ClassUnicode {
+        let class = |ranges: &[(char, char)]| {
+            ClassUnicode::new(ranges.iter().map(|&(a, b)| ClassUnicodeRange::new(a, b)))
+        };
+
+        match self {
+            Constraint::Integer { signed, .. } => match signed {
+                true => class(&[('+', '+'), ('-', '-'), ('0', '9')]),
+                false => class(&[('+', '+'), ('0', '9')]),
+            },
+            #[cfg(feature = "uuid")]
+            Constraint::Uuid => class(&[('0', '9'), ('A', 'F'), ('a', 'f'), ('u', 'u'), ('{', '{')]),
+            Constraint::Regex(_, first) => first.clone(),
+        }
+    }
+}
+
+/// Computes the set of characters a nonempty match of `hir` can begin with.
+/// Byte classes, which can match invalid UTF-8, conservatively match anything.
+fn first_chars(hir: &Hir) -> ClassUnicode {
+    let any = || ClassUnicode::new(Some(ClassUnicodeRange::new('\0', char::MAX)));
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => ClassUnicode::empty(),
+        HirKind::Literal(literal) => match std::str::from_utf8(&literal.0) {
+            Ok(s) => ClassUnicode::new(s.chars().next().map(|c| ClassUnicodeRange::new(c, c))),
+            Err(_) => any(),
+        },
+        HirKind::Class(Class::Unicode(class)) => class.clone(),
+        HirKind::Class(Class::Bytes(_)) => any(),
+        HirKind::Repetition(repetition) => first_chars(&repetition.sub),
+        HirKind::Capture(capture) => first_chars(&capture.sub),
+        HirKind::Alternation(hirs) => hirs.iter().fold(ClassUnicode::empty(), |mut set, hir| {
+            set.union(&first_chars(hir));
+            set
+        }),
+        HirKind::Concat(hirs) => {
+            let mut set = ClassUnicode::empty();
+            for hir in hirs {
+                set.union(&first_chars(hir));
+                if hir.properties().minimum_len() != Some(0) {
+                    break;
+                }
+            }
+
+            set
+        }
     }
 }
@@ -93,6 +93,8 @@ pub(crate) struct Metadata {
     pub query_color: Option<Color>,
     /// Whether the path has a `<trailing..>` parameter.
     pub trailing_path: bool,
+    /// Whether the path has a constrained parameter, like `<id:u64>`.
+    pub constrained_path: bool,
 }
 
 type Result<T, E = uri::Error<'static>> = std::result::Result<T, E>;
@@ -285,10 +287,11 @@ impl Metadata {
         });
 
         let trailing_path = path_segs.last().map_or(false, |p| p.trailing);
+        let constrained_path = path_segs.iter().any(|p| p.constraint.is_some());
 
         Metadata {
             base_segs, path_segs, query_segs, static_query_fields,
-            path_color, query_color, trailing_path,
+            path_color, query_color, trailing_path, constrained_path,
         }
     }
 }
@@ -2,6 +2,7 @@ use crate::catcher::Catcher;
 use crate::route::{Route, Color};
 
 use crate::http::{MediaType, Status};
+use crate::http::uri::HostPattern;
 use crate::request::Request;
 
 pub trait Collide<T = Self> {
@@ -21,6 +22,30 @@ impl Collide for MediaType {
     }
 }
 
+impl Collide for HostPattern {
+    /// Determines if some domain matches both patterns, where a `*` label
+    /// matches one or more labels.
+    fn collides_with(&self, other: &Self) -> bool {
+        fn overlap(a: &[&str], b: &[&str]) -> bool {
+            // When a wildcard consumes a label, it either stops or continues.
+            match (a.split_first(), b.split_first()) {
+                (None, None) => true,
+                (None, Some(_)) | (Some(_), None) => false,
+                (Some((&"*", ra)), Some((&"*", rb))) => {
+                    overlap(ra, rb) || overlap(a, rb) || overlap(ra, b)
+                }
+                (Some((&"*", ra)), Some((_, rb))) => overlap(ra, rb) || overlap(a, rb),
+                (Some((_, ra)), Some((&"*", rb))) => overlap(ra, rb) || overlap(ra, b),
+                (Some((x, ra)), Some((y, rb))) => x == y && overlap(ra, rb),
+            }
+        }
+
+        let a: Vec<&str> = self.labels().collect();
+        let b: Vec<&str> = other.labels().collect();
+        overlap(&a, &b)
+    }
+}
+
 fn paths_collide(route: &Route, other: &Route) -> bool {
     let a_segments = &route.uri.metadata.path_segs;
     let b_segments = &other.uri.metadata.path_segs;
@@ -29,11 +54,7 @@ fn paths_collide(route: &Route, other: &Route) -> bool {
             return true;
         }
 
-        if seg_a.dynamic || seg_b.dynamic {
-            continue;
-        }
-
-        if seg_a.value != seg_b.value {
+        if !seg_a.overlaps(seg_b) {
             return false;
         }
     }
@@ -61,6 +82,14 @@ fn formats_collide(route: &Route, other: &Route) -> bool {
     }
 }
 
+fn hosts_collide(route: &Route, other: &Route) -> bool {
+    // A route without a host pattern matches requests for any host.
+    match (route.host.as_ref(), other.host.as_ref()) {
+        (Some(a), Some(b)) => a.collides_with(b),
+        _ => true
+    }
+}
+
 impl Collide for Route {
     /// Determines if two routes can match against some request. That is, if two
     /// routes `collide`, there exists a request that can match against both
@@ -72,6 +101,16 @@ impl Collide for Route {
     ///   * If route specifies a format, it only gets requests for that format.
     ///   * If route doesn't specify a format, it gets requests for any format.
     ///
+    /// Host collisions work the same way: routes with host patterns collide if
+    /// some domain matches both patterns, and routes without one collide with
+    /// routes for any host.
+    ///
+    /// Dynamic path segments with constraints, such as `<id:u64>`, only
+    /// collide with segments some value satisfying the constraint matches.
+    /// Whether two constraints overlap is decided conservatively by the
+    /// characters values satisfying them can begin with: `<id:u64>` and
+    /// `<name:[a-z]+>` don't collide, but `<id:u64>` and `<n:[0-9a-f]+>` do.
+    ///
     /// Because query parsing is lenient, and dynamic query parameters can be
     /// missing, queries do not impact whether two routes collide.
     fn collides_with(&self, other: &Route) -> bool {
@@ -79,6 +118,7 @@ impl Collide for Route {
             && self.rank == other.rank
             && paths_collide(self, other)
             && formats_collide(self, other)
+            && hosts_collide(self, other)
     }
 }
 
@@ -88,22 +128,34 @@ impl Route {
     /// This means that:
     ///
     ///   * The route's method matches that of the incoming request.
+    ///   * The route's host pattern (if any) matches the request's host.
     ///   * The route's format (if any) matches that of the incoming request.
     ///     - If route specifies format, it only gets requests for that format.
     ///     - If route doesn't specify format, it gets requests for any format.
     ///   * All static components in the route's path match the corresponding
     ///     components in the same position in the incoming request.
+    ///   * All constrained dynamic components in the route's path, such as
+    ///     `<id:u64>`, are satisfied by the corresponding components in the
+    ///     incoming request.
     ///   * All static components in the route's query string are also in the
     ///     request query string, though in any position. If there is no query
     ///     in the route, requests with/without queries match.
     pub(crate) fn matches(&self, req: &Request<'_>) -> bool {
         self.method == req.method()
+            && hosts_match(self, req)
             && paths_match(self, req)
             && queries_match(self, req)
             && formats_match(self, req)
     }
 }
 
+fn hosts_match(route: &Route, req: &Request<'_>) -> bool {
+    match route.host.as_ref() {
+        Some(pattern) => req.host().map_or(false, |host| pattern.matches(host)),
+        None => true
+    }
+}
+
 fn paths_match(route: &Route, req: &Request<'_>) -> bool {
     let route_segments = &route.uri.metadata.path_segs;
     let req_segments = req.uri().path().segments();
@@ -119,7 +171,8 @@ fn paths_match(route: &Route, req: &Request<'_>) -> bool {
         return false;
     }
 
-    if route.uri.metadata.path_color == Color::Wild {
+    let metadata = &route.uri.metadata;
+    if metadata.path_color == Color::Wild && !metadata.constrained_path {
         return true;
     }
 
@@ -128,7 +181,7 @@ fn paths_match(route: &Route, req: &Request<'_>) -> bool {
             return true;
         }
 
-        if !(route_seg.dynamic || route_seg.value == req_seg) {
+        if !route_seg.matches(req_seg) {
             return false;
         }
     }
@@ -177,6 +230,64 @@ fn formats_match(route: &Route, request: &Request<'_>) -> bool {
 }
 
 
+fn paths_cover(route: &Route, other: &Route) -> bool {
+    let a_segments = &route.uri.metadata.path_segs;
+    let b_segments = &other.uri.metadata.path_segs;
+    for (i, seg_a) in a_segments.iter().enumerate() {
+        // A trailing segment matches any remaining segments, even none.
+        if seg_a.trailing {
+            return true;
+        }
+
+        match b_segments.get(i) {
+            Some(seg_b) if !seg_b.trailing && seg_a.covers(seg_b) => continue,
+            _ => return false,
+        }
+    }
+
+    a_segments.len() == b_segments.len()
+}
+
+fn queries_cover(route: &Route, other: &Route) -> bool {
+    // Requests only match if they contain every static query field.
+    let other_fields = &other.uri.metadata.static_query_fields;
+    route.uri.metadata.static_query_fields.iter().all(|field| other_fields.contains(field))
+}
+
+fn formats_cover(route: &Route, other: &Route) -> bool {
+    route.format.is_none() || route.format == other.format
+}
+
+fn hosts_cover(route: &Route, other: &Route) -> bool {
+    match (route.host.as_ref(), other.host.as_ref()) {
+        (None, _) => true,
+        (Some(a), Some(b)) => a == b,
+        (Some(_), None) => false,
+    }
+}
+
+impl Route {
+    /// Determines if this route always preempts `other`: every request that
+    /// matches `other` also matches `self`, which is attempted first because
+    /// of its lower rank and never forwards.
+    ///
+    /// Whether a route can forward is decided conservatively: only routes
+    /// generated for handlers without guards, and not mounted in a scope with
+    /// guards, are known not to. Coverage is similarly conservative: a
+    /// constrained segment only covers a segment with the same constraint, and
+    /// a host pattern only covers the same pattern.
+    pub(crate) fn preempts(&self, other: &Route) -> bool {
+        self.method == other.method
+            && self.rank < other.rank
+            && !self.may_forward
+            && self.guards.is_empty()
+            && paths_cover(self, other)
+            && queries_cover(self, other)
+            && formats_cover(self, other)
+            && hosts_cover(self, other)
+    }
+}
+
 impl Collide for Catcher {
     /// Determines if two catchers are in conflict: there exists a request for
     /// which there exist no rule to determine _which_ of the two catchers to
@@ -184,20 +295,52 @@ impl Collide for Catcher {
     ///
     ///  * Have the same base.
     ///  * Have the same status code or are both defaults.
+    ///  * Have the same error type or are both untyped.
+    ///  * Have the same method or both have no method.
+    ///  * Have colliding formats or both have no format.
     fn collides_with(&self, other: &Self) -> bool {
         self.code == other.code
+            && self.error == other.error
+            && self.method == other.method
+            && catcher_formats_collide(self, other)
             && self.base.path().segments().eq(other.base.path().segments())
     }
 }
 
+fn catcher_formats_collide(catcher: &Catcher, other: &Catcher) -> bool {
+    // A catcher with a format is preferred to one without, so the two only
+    // collide if both or neither have a format.
+    match (catcher.format.as_ref(), other.format.as_ref()) {
+        (Some(a), Some(b)) => a.collides_with(b),
+        (None, None) => true,
+        _ => false
+    }
+}
+
+fn catcher_format_matches(catcher: &Catcher, req: &Request<'_>) -> bool {
+    // Unlike routes, catchers always match against `Accept`: the format is
+    // that of the error response, not of the request's payload.
+    catcher.format.as_ref()
+        .and_then(|a| req.accept().map(|b| (a, b.preferred().media_type())))
+        .map(|(a, b)| a.collides_with(b))
+        .unwrap_or(true)
+}
+
 impl Catcher {
     /// Determines if this catcher is responsible for handling the error with
     /// `status` that occurred during request `req`. A catcher matches if:
     ///
     ///  * It is a default catcher _or_ has a code of `status`.
     ///  * Its base is a prefix of the normalized/decoded `req.path()`.
+    ///  * It is untyped _or_ its error type is that of `req`'s stashed error.
+    ///  * It has no format _or_ its format matches `req`'s preferred `Accept`
+    ///    media type, if it has one.
+    ///  * It has no method _or_ its method is `req.method()`.
     pub(crate) fn matches(&self, status: Status, req: &Request<'_>) -> bool {
         self.code.map_or(true, |code| code == status.code)
+            && self.error.map_or(true, |error| Some(error.id) == req.error_type())
+            && self.method.map_or(true, |method| method == req.method())
+            && catcher_format_matches(self, req)
             && self.base.path().segments().prefix_of(req.uri().path().segments())
     }
 }
@@ -210,7 +353,7 @@ mod tests {
     use crate::route::{Route, dummy_handler};
     use crate::local::blocking::Client;
     use crate::http::{Method, Method::*, MediaType, ContentType, Accept};
-    use crate::http::uri::Origin;
+    use crate::http::uri::{Origin, Host, HostPattern};
 
     type SimpleRoute = (Method, &'static str);
 
@@ -376,6 +519,40 @@ mod tests {
         assert!(s_s_collide("/<a..>", "/"));
     }
 
+    #[test]
+    fn test_constraint_collisions() {
+        assert!(unranked_collide("/<a:u8>", "/<b:i128>"));
+        assert!(unranked_collide("/<a:u8>", "/<b>"));
+        assert!(unranked_collide("/<a:u8>", "/10"));
+        assert!(unranked_collide("/<a:[a-z]+>", "/<b:x*y>"));
+        assert!(unranked_collide("/<a:(ab)*c>", "/<b:a>"));
+        assert!(unranked_collide("/<a:\\d+>", "/<b:u8>"));
+        assert!(unranked_collide("/<a:u8>/b", "/<b:u16>/<c..>"));
+
+        assert!(!unranked_collide("/<a:u8>", "/<b:[a-z]+>"));
+        assert!(!unranked_collide("/<a:u8>", "/abc"));
+        assert!(!unranked_collide("/<a:u8>", "/<b:-(1|2)>"));
+        assert!(!unranked_collide("/<a:[a-z]+>", "/<b:[A-Z]+>"));
+        assert!(!unranked_collide("/<a:^x.*>", "/<b:y>"));
+        assert!(!unranked_collide("/<a:u8>/<b..>", "/item/<c..>"));
+    }
+
+    #[test]
+    fn test_req_route_constraint_matches() {
+        assert!(req_route_path_match("/255", "/<id:u8>"));
+        assert!(req_route_path_match("/-1", "/<id:i64>"));
+        assert!(req_route_path_match("/rocket", "/<name:[a-z]+>"));
+        assert!(req_route_path_match("/bc", "/<name:a|bc>"));
+        assert!(req_route_path_match("/a%20b", "/<name:a b>"));
+        assert!(req_route_path_match("/1/2", "/<a:u8>/<b..>"));
+
+        assert!(!req_route_path_match("/256", "/<id:u8>"));
+        assert!(!req_route_path_match("/-1", "/<id:u64>"));
+        assert!(!req_route_path_match("/rocket1", "/<name:[a-z]+>"));
+        assert!(!req_route_path_match("/abc", "/<name:a|bc>"));
+        assert!(!req_route_path_match("/a/2", "/<a:u8>/<b..>"));
+    }
+
     fn mt_mt_collide(mt1: &str, mt2: &str) -> bool {
         let mt_a = MediaType::from_str(mt1).expect(mt1);
         let mt_b = MediaType::from_str(mt2).expect(mt2);
@@ -571,6 +748,66 @@ mod tests {
     }
 
 
+    fn host_route<H: Into<Option<&'static str>>>(host: H) -> Route {
+        let mut route = Route::new(Get, "/", dummy_handler);
+        route.host = host.into().map(|h| HostPattern::parse(h).unwrap());
+        route
+    }
+
+    fn h_collide(a: Option<&'static str>, b: Option<&'static str>) -> bool {
+        host_route(a).collides_with(&host_route(b))
+    }
+
+    #[test]
+    fn test_host_collisions() {
+        assert!(h_collide(None, None));
+        assert!(h_collide(None, Some("api.example.com")));
+        assert!(h_collide(Some("*.example.com"), None));
+        assert!(h_collide(Some("api.example.com"), Some("api.example.com")));
+        assert!(h_collide(Some("api.example.com"), Some("API.example.com")));
+        assert!(h_collide(Some("*.example.com"), Some("api.example.com")));
+        assert!(h_collide(Some("api.*"), Some("*.example")));
+        assert!(h_collide(Some("*.*"), Some("a.b")));
+        assert!(h_collide(Some("*"), Some("a.b.c")));
+        assert!(h_collide(Some("api.*"), Some("api.example.com")));
+        assert!(h_collide(Some("*.example.com"), Some("a.b.example.com")));
+        assert!(h_collide(Some("a.*.c"), Some("*.b.*")));
+        assert!(h_collide(Some("*.b"), Some("a.*")));
+
+        assert!(!h_collide(Some("api.example.com"), Some("www.example.com")));
+        assert!(!h_collide(Some("*.example.com"), Some("example.com")));
+        assert!(!h_collide(Some("*.example.com"), Some("*.example.org")));
+        assert!(!h_collide(Some("api.*"), Some("www.*")));
+        assert!(!h_collide(Some("*.*"), Some("a")));
+        assert!(!h_collide(Some("a.*.d"), Some("a.d")));
+    }
+
+    fn req_route_host_match(host: Option<&'static str>, pattern: Option<&'static str>) -> bool {
+        let client = Client::debug_with(vec![]).expect("client");
+        let mut req = client.get("/");
+        if let Some(host) = host {
+            req.inner_mut().set_host(Host::parse(host).unwrap());
+        }
+
+        host_route(pattern).matches(&req)
+    }
+
+    #[test]
+    fn test_req_route_host_matches() {
+        assert!(req_route_host_match(None, None));
+        assert!(req_route_host_match(Some("example.com"), None));
+        assert!(req_route_host_match(Some("example.com"), Some("example.com")));
+        assert!(req_route_host_match(Some("Example.COM:8000"), Some("example.com")));
+        assert!(req_route_host_match(Some("api.example.com"), Some("*.example.com")));
+        assert!(req_route_host_match(Some("api.localhost"), Some("api.*")));
+        assert!(req_route_host_match(Some("a.b.example.com"), Some("*.example.com")));
+
+        assert!(!req_route_host_match(None, Some("example.com")));
+        assert!(!req_route_host_match(Some("example.org"), Some("example.com")));
+        assert!(!req_route_host_match(Some("example.com"), Some("*.example.com")));
+        assert!(!req_route_host_match(Some("www.localhost"), Some("api.*")));
+    }
+
     fn catchers_collide<A, B>(a: A, ap: &str, b: B, bp: &str) -> bool
         where A: Into<Option<u16>>, B: Into<Option<u16>>
     {
@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use yansi::Paint;
 
 use crate::request::Request;
 use crate::http::{Method, Status};
@@ -18,12 +20,49 @@ pub struct Collisions {
     pub catchers: Vec<(Catcher, Catcher)>,
 }
 
+/// Items that can never handle a request: routes, each paired with the route
+/// that always preempts it, catchers that can never be selected, and mount
+/// points at which no routes were mounted or every route is shadowed.
+#[derive(Debug, Default)]
+pub struct Unreachable {
+    pub routes: Vec<(Route, Route)>,
+    pub catchers: Vec<Catcher>,
+    pub mounts: Vec<String>,
+}
+
+impl Unreachable {
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty() && self.catchers.is_empty() && self.mounts.is_empty()
+    }
+
+    pub(crate) fn pretty_print(&self) {
+        for (route, by) in &self.routes {
+            info_!("{} {} {}", route, Paint::yellow("is shadowed by").italic(), by);
+        }
+
+        for catcher in &self.catchers {
+            info_!("{} {}", catcher, Paint::yellow("can never be selected").italic());
+        }
+
+        for mount in &self.mounts {
+            info_!("{} {}", Paint::blue(mount), Paint::yellow("has no reachable routes").italic());
+        }
+    }
+}
+
+/// Orders catchers with the same base: typed catchers first, then those with a
+/// format, then those with a method.
+fn specificity(catcher: &Catcher) -> (bool, bool, bool) {
+    (catcher.error.is_some(), catcher.format.is_some(), catcher.method.is_some())
+}
+
 impl Router {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn add_route(&mut self, route: Route) {
+    pub fn add_route(&mut self, mut route: Route) {
+        route.fairings.index();
         let routes = self.routes.entry(route.method).or_default();
         routes.push(route);
         routes.sort_by_key(|r| r.rank);
@@ -32,7 +71,10 @@ impl Router {
     pub fn add_catcher(&mut self, catcher: Catcher) {
         let catchers = self.catchers.entry(catcher.code).or_default();
         catchers.push(catcher);
-        catchers.sort_by(|a, b| b.base.path().segments().len().cmp(&a.base.path().segments().len()))
+        catchers.sort_by(|a, b| {
+            let (a_len, b_len) = (a.base.path().segments().len(), b.base.path().segments().len());
+            b_len.cmp(&a_len).then_with(|| specificity(b).cmp(&specificity(a)))
+        })
     }
 
     #[inline]
@@ -55,9 +97,16 @@ impl Router {
             .flat_map(move |routes| routes.iter().filter(move |r| r.matches(req)))
     }
 
+    /// Returns the highest ranked route matching `req`, if any: the first
+    /// route `req` is routed to.
+    pub fn first_route(&self, req: &Request<'_>) -> Option<&Route> {
+        self.routes.get(&req.method())?.iter().find(|r| r.matches(req))
+    }
+
     // For many catchers, using aho-corasick or similar should be much faster.
     pub fn catch<'r>(&self, status: Status, req: &'r Request<'r>) -> Option<&Catcher> {
-        // Note that catchers are presorted by descending base length.
+        // Note that catchers are presorted by descending base length and then
+        // by descending specificity. See `specificity()`.
         let explicit = self.catchers.get(&Some(status.code))
             .and_then(|c| c.iter().find(|c| c.matches(status, req)));
 
@@ -89,6 +138,36 @@ impl Router {
             })
     }
 
+    /// Returns the routes and catchers that can never handle a request along
+    /// with the mount points at which every route is shadowed.
+    pub fn unreachable(&self) -> Unreachable {
+        let mut routes = vec![];
+        let mut shadowed_mounts = BTreeMap::new();
+        for method_routes in self.routes.values() {
+            for b in method_routes {
+                let by = method_routes.iter().find(|a| a.preempts(b));
+                *shadowed_mounts.entry(b.uri.base.to_string()).or_insert(true) &= by.is_some();
+                if let Some(a) = by {
+                    routes.push((b.clone(), a.clone()));
+                }
+            }
+        }
+
+        // A `HEAD` request no route handles is retried as a `GET` request, so
+        // only catchers see it as `HEAD` if a `HEAD` route handled it.
+        let catchers = self.catchers()
+            .filter(|c| c.method == Some(Method::Head))
+            .filter(|_| !self.routes.contains_key(&Method::Head))
+            .cloned()
+            .collect();
+
+        let mounts = shadowed_mounts.into_iter()
+            .filter_map(|(base, shadowed)| Some(base).filter(|_| shadowed))
+            .collect();
+
+        Unreachable { routes, catchers, mounts }
+    }
+
     pub fn finalize(&self) -> Result<(), Collisions> {
         let routes: Vec<_> = self.collisions(self.routes()).collect();
         let catchers: Vec<_> = self.collisions(self.catchers()).collect();
@@ -187,8 +187,12 @@ impl Rocket<Orbit> {
             }
         }
 
-        // Run request fairings.
+        // Run request fairings, then those of the scopes of the route the
+        // request is first routed to.
         self.fairings.handle_request(req, data).await;
+        if let Some(route) = self.router.first_route(req) {
+            route.handle_request(req, data).await;
+        }
 
         RequestToken
     }
@@ -276,6 +280,13 @@ impl Rocket<Orbit> {
             info_!("Matched: {}", route);
             request.set_route(route);
 
+            // Run the guards of the route's scopes, if any, before its handler.
+            match route.check_guards(request).await {
+                Outcome::Success(()) => {},
+                Outcome::Forward(()) => continue,
+                Outcome::Failure(status) => return Outcome::Failure(status),
+            }
+
             let name = route.name.as_deref();
             let outcome = handle(name, || route.handler.handle(request, data)).await
                 .unwrap_or(Outcome::Failure(Status::InternalServerError));
@@ -285,7 +296,14 @@ impl Rocket<Orbit> {
             // (None) to try again.
             info_!("{} {}", Paint::default("Outcome:").bold(), outcome);
             match outcome {
-                o@Outcome::Success(_) | o@Outcome::Failure(_) => return o,
+                Outcome::Success(mut response) => {
+                    // Advertise the route's deprecation, then run the response
+                    // fairings of the route's scopes, if any.
+                    route.set_deprecation_headers(&mut response);
+                    route.handle_response(request, &mut response).await;
+                    return Outcome::Success(response);
+                }
+                o@Outcome::Failure(_) => return o,
                 Outcome::Forward(unused_data) => data = unused_data,
             }
         }
@@ -356,7 +374,7 @@ impl Rocket<Orbit> {
     }
 
     pub(crate) async fn default_tcp_http_server<C>(mut self, ready: C) -> Result<(), Error>
-        where C: for<'a> Fn(&'a Self) -> BoxFuture<'a, ()>
+        where C: for<'a> FnOnce(&'a Arc<Self>) -> BoxFuture<'a, ()>
     {
         use std::net::ToSocketAddrs;
 
@@ -376,8 +394,9 @@ impl Rocket<Orbit> {
                 addr = l.local_addr().unwrap_or(addr);
                 self.config.address = addr.ip();
                 self.config.port = addr.port();
-                ready(&mut self).await;
-                return self.http_server(l).await;
+                let rocket = Arc::new(self);
+                ready(&rocket).await;
+                return rocket.http_server(l).await;
             }
         }
 
@@ -385,12 +404,13 @@ impl Rocket<Orbit> {
         addr = l.local_addr().unwrap_or(addr);
         self.config.address = addr.ip();
         self.config.port = addr.port();
-        ready(&mut self).await;
-        self.http_server(l).await
+        let rocket = Arc::new(self);
+        ready(&rocket).await;
+        rocket.http_server(l).await
     }
 
     // TODO.async: Solidify the Listener APIs and make this function public
-    pub(crate) async fn http_server<L>(self, listener: L) -> Result<(), Error>
+    pub(crate) async fn http_server<L>(self: Arc<Self>, listener: L) -> Result<(), Error>
         where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
     {
         // Emit a warning if we're not running inside of Rocket's async runtime.
@@ -439,7 +459,7 @@ impl Rocket<Orbit> {
         }
 
         // Create the Hyper `Service`.
-        let rocket = Arc::new(self);
+        let rocket = self;
         let service_fn = move |conn: &CancellableIo<_, L::Connection>| {
             let rocket = rocket.clone();
             let connection = ConnectionMeta {
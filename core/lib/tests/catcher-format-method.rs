@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Rocket, Build};
+use rocket::http::{Accept, ContentType, Status};
+use rocket::local::blocking::Client;
+use rocket::response::content::{Html, Json};
+
+#[catch(404, format = "json")]
+fn json_not_found(req: &Request) -> Json<String> {
+    Json(format!("{{\"missing\":\"{}\"}}", req.uri()))
+}
+
+#[catch(404, format = "html")]
+fn html_not_found(req: &Request) -> Html<String> {
+    Html(format!("<p>{} is missing</p>", req.uri()))
+}
+
+#[catch(404)]
+fn not_found() -> &'static str {
+    "missing"
+}
+
+#[catch(404, method = POST)]
+fn post_not_found() -> &'static str {
+    "nothing to post to"
+}
+
+#[catch(404, format = "json", method = POST)]
+fn json_post_not_found() -> Json<&'static str> {
+    Json("\"nothing to post to\"")
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .register("/", catchers![not_found, post_not_found])
+        .register("/api", catchers![html_not_found, json_not_found, json_post_not_found])
+}
+
+#[test]
+fn test_format_selects_catcher() {
+    let client = Client::debug(rocket()).unwrap();
+
+    let response = client.get("/api/foo").header(Accept::JSON).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.content_type(), Some(ContentType::JSON));
+    assert_eq!(response.into_string().unwrap(), r#"{"missing":"/api/foo"}"#);
+
+    let response = client.get("/api/foo").header(Accept::HTML).dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+    assert_eq!(response.into_string().unwrap(), "<p>/api/foo is missing</p>");
+
+    // Without a preference, the first registered catcher is used.
+    let response = client.get("/api/foo").dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+    // When no catcher's format matches, routing falls through.
+    let response = client.get("/api/foo").header(Accept::Plain).dispatch();
+    assert_eq!(response.into_string().unwrap(), "missing");
+}
+
+#[test]
+fn test_method_selects_catcher() {
+    let client = Client::debug(rocket()).unwrap();
+
+    let response = client.get("/foo").dispatch();
+    assert_eq!(response.into_string().unwrap(), "missing");
+
+    let response = client.post("/foo").dispatch();
+    assert_eq!(response.into_string().unwrap(), "nothing to post to");
+
+    // A catcher with both a format and a method is preferred.
+    let response = client.post("/api/foo").header(Accept::JSON).dispatch();
+    assert_eq!(response.into_string().unwrap(), "\"nothing to post to\"");
+
+    let response = client.post("/api/foo").header(Accept::HTML).dispatch();
+    assert_eq!(response.content_type(), Some(ContentType::HTML));
+}
+
+#[test]
+fn test_format_method_collisions() {
+    fn collides(rocket: Rocket<Build>) -> bool {
+        match Client::debug(rocket) {
+            Err(e) => matches!(e.kind(), rocket::error::ErrorKind::Collisions(..)),
+            Ok(_) => false,
+        }
+    }
+
+    #[catch(404, format = "application/json")]
+    fn other_json() { }
+
+    #[catch(404, method = GET)]
+    fn get_not_found() { }
+
+    #[catch(404, method = POST)]
+    fn other_post() { }
+
+    assert!(!collides(rocket().register("/", catchers![json_not_found, get_not_found])));
+    assert!(!collides(rocket().register("/", catchers![other_json])));
+    assert!(collides(rocket().register("/api", catchers![other_json])));
+    assert!(collides(rocket().register("/", catchers![other_post])));
+}
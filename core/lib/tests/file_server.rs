@@ -20,6 +20,7 @@ fn rocket() -> Rocket<Build> {
         .mount("/both", FileServer::new(&root, Options::DotFiles | Options::Index))
         .mount("/redir", FileServer::new(&root, Options::NormalizeDirs))
         .mount("/redir_index", FileServer::new(&root, Options::NormalizeDirs | Options::Index))
+        .mount("/compressed", FileServer::new(&root, Options::Index | Options::Precompressed))
 }
 
 static REGULAR_FILES: &[&str] = &[
@@ -186,3 +187,61 @@ fn test_redirection() {
     assert_eq!(response.status(), Status::PermanentRedirect);
     assert_eq!(response.headers().get("Location").next(), Some("/redir_index/"));
 }
+
+#[test]
+fn test_precompressed() {
+    use rocket::http::{ContentType, Header};
+
+    fn get(client: &Client, path: &str, accept: Option<&str>) -> (Option<String>, String) {
+        let mut request = client.get(format!("/compressed/compressed/{}", path));
+        if let Some(accept) = accept {
+            request.add_header(Header::new("Accept-Encoding", accept.to_string()));
+        }
+
+        let response = request.dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+
+        let encoding = response.headers().get_one("Content-Encoding").map(|s| s.to_string());
+        if path.ends_with(".js") {
+            assert_eq!(response.content_type(), Some(ContentType::JavaScript));
+        } else if path.ends_with(".css") {
+            assert_eq!(response.content_type(), Some(ContentType::CSS));
+        }
+
+        (encoding, response.into_string().unwrap())
+    }
+
+    let client = Client::debug(rocket()).expect("valid rocket");
+    let br = (Some("br".into()), "brotli-compressed app.js\n".into());
+    let gzip = (Some("gzip".into()), "gzip-compressed app.js\n".into());
+    let none = (None, "console.log(\"uncompressed\");\n".into());
+
+    assert_eq!(get(&client, "app.js", Some("gzip, deflate, br")), br);
+    assert_eq!(get(&client, "app.js", Some("br;q=0.5, gzip")), gzip);
+    assert_eq!(get(&client, "app.js", Some("GZIP")), gzip);
+    assert_eq!(get(&client, "app.js", Some("*")), br);
+    assert_eq!(get(&client, "app.js", Some("*;q=0.1, br;q=0")), gzip);
+    assert_eq!(get(&client, "app.js", Some("deflate")), none);
+    assert_eq!(get(&client, "app.js", Some("gzip;q=0, br;q=0")), none);
+    assert_eq!(get(&client, "app.js", None), none);
+
+    let css = get(&client, "style.css", Some("br, gzip"));
+    assert_eq!(css, (Some("gzip".into()), "gzip-compressed style.css\n".into()));
+
+    // Siblings of missing files aren't served.
+    let response = client.get("/compressed/compressed/orphan.txt")
+        .header(Header::new("Accept-Encoding", "gzip"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::NotFound);
+
+    // Without the option, the file is always served as-is.
+    let response = client.get("/default/compressed/app.js")
+        .header(Header::new("Accept-Encoding", "br, gzip"))
+        .dispatch();
+
+    assert!(response.headers().get_one("Content-Encoding").is_none());
+    assert!(response.headers().get_one("Vary").is_none());
+    assert_eq!(response.into_string().unwrap(), "console.log(\"uncompressed\");\n");
+}
@@ -0,0 +1,111 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::error::ErrorKind;
+use rocket::http::Status;
+use rocket::http::uri::Host;
+use rocket::local::blocking::Client;
+
+#[get("/", host = "api.*")]
+fn api() -> &'static str { "api" }
+
+#[get("/", host = "www.example.com")]
+fn www() -> &'static str { "www" }
+
+#[get("/", rank = 2)]
+fn any() -> &'static str { "any" }
+
+#[get("/")]
+fn admin() -> &'static str { "admin" }
+
+#[get("/user/<id>", host = "*.example.com")]
+fn user(id: usize) -> String { id.to_string() }
+
+fn get(client: &Client, host: Option<&'static str>, path: &'static str) -> Option<String> {
+    let mut req = client.get(path);
+    if let Some(host) = host {
+        req.inner_mut().set_host(Host::parse(host).unwrap());
+    }
+
+    let response = req.dispatch();
+    match response.status() == Status::Ok {
+        true => response.into_string(),
+        false => None,
+    }
+}
+
+#[test]
+fn test_host_routing() {
+    let rocket = rocket::build()
+        .mount("/", routes![api, www, any, user])
+        .mount_host("admin.example.com", "/", routes![admin]);
+
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(get(&client, Some("api.example.com"), "/").unwrap(), "api");
+    assert_eq!(get(&client, Some("API.localhost:8000"), "/").unwrap(), "api");
+    assert_eq!(get(&client, Some("www.example.com"), "/").unwrap(), "www");
+    assert_eq!(get(&client, Some("admin.example.com"), "/").unwrap(), "admin");
+    assert_eq!(get(&client, Some("example.com"), "/").unwrap(), "any");
+    assert_eq!(get(&client, None, "/").unwrap(), "any");
+
+    assert_eq!(get(&client, Some("api.example.com"), "/user/7").unwrap(), "7");
+    assert_eq!(get(&client, Some("example.com"), "/user/7"), None);
+    assert_eq!(get(&client, None, "/user/7"), None);
+}
+
+#[test]
+fn test_mount_host_replaces_host() {
+    let rocket = rocket::build().mount_host("v2.example.com", "/api", routes![api, user]);
+    let hosts: Vec<_> = rocket.routes()
+        .map(|r| r.host.as_ref().unwrap().to_string())
+        .collect();
+
+    assert_eq!(hosts, ["v2.example.com", "v2.example.com"]);
+
+    let client = Client::debug(rocket).unwrap();
+    assert_eq!(get(&client, Some("v2.example.com"), "/api").unwrap(), "api");
+    assert_eq!(get(&client, Some("api.example.com"), "/api"), None);
+}
+
+fn collides(rocket: Rocket<Build>) -> bool {
+    match Client::debug(rocket) {
+        Ok(_) => false,
+        Err(e) => matches!(e.kind(), ErrorKind::Collisions(..)),
+    }
+}
+
+#[test]
+fn test_host_collisions() {
+    // Distinct hosts don't collide.
+    let rocket = rocket::build().mount("/", routes![www])
+        .mount_host("api.example.com", "/", routes![admin]);
+
+    assert!(!collides(rocket));
+
+    // Overlapping hosts do, as do routes without a host.
+    let rocket = rocket::build().mount("/", routes![api])
+        .mount_host("*.example.com", "/", routes![admin]);
+
+    assert!(collides(rocket));
+
+    let rocket = rocket::build().mount("/", routes![www, admin]);
+    assert!(collides(rocket));
+}
+
+#[test]
+#[should_panic]
+fn bad_host_mount() {
+    rocket::build().mount_host("api..example.com", "/", routes![admin]);
+}
+
+#[test]
+fn test_uri_with_host() {
+    let uri = uri!("https://api.rocket.rs", api());
+    assert_eq!(uri.to_string(), "https://api.rocket.rs");
+
+    let uri = uri!("https://a.example.com/v1", user(7));
+    assert_eq!(uri.to_string(), "https://a.example.com/v1/user/7");
+
+    let uri = uri!(user(7));
+    assert_eq!(uri.to_string(), "/user/7");
+}
@@ -0,0 +1,169 @@
+#[macro_use] extern crate rocket;
+
+use std::net::SocketAddr;
+
+use rocket::{Rocket, Build, Shutdown};
+use rocket::http::{Status, Cookie, CookieJar};
+use rocket::local::live::Client;
+use rocket::response::stream::TextStream;
+use rocket::tokio::time::{self, Duration};
+
+#[get("/")]
+fn hello() -> &'static str {
+    "Hello, world!"
+}
+
+#[post("/", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[get("/remote")]
+fn remote(remote: SocketAddr) -> String {
+    remote.ip().to_string()
+}
+
+#[get("/cookie/set")]
+fn set_cookie(jar: &CookieJar<'_>) {
+    jar.add(Cookie::new("live", "cookie"));
+}
+
+#[get("/cookie/get")]
+fn get_cookie(jar: &CookieJar<'_>) -> Option<String> {
+    jar.get("live").map(|c| c.value().to_string())
+}
+
+#[get("/infinite")]
+fn infinite(mut shutdown: Shutdown) -> TextStream![&'static str] {
+    TextStream! {
+        let mut interval = time::interval(Duration::from_millis(10));
+        loop {
+            rocket::tokio::select! {
+                _ = interval.tick() => yield "tick",
+                _ = &mut shutdown => break,
+            }
+        }
+    }
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build().mount("/", routes![hello, echo, remote, set_cookie, get_cookie, infinite])
+}
+
+#[rocket::async_test]
+async fn live_client_dispatches_over_network() {
+    let client = Client::debug(rocket()).await.unwrap();
+    assert_eq!(client.rocket().config().address.to_string(), "127.0.0.1");
+    assert_ne!(client.rocket().config().port, 0);
+
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+
+    let response = client.post("/").body("Hello, network!").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Hello, network!");
+
+    let response = client.get("/remote").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "127.0.0.1");
+
+    let response = client.get("/unknown").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[rocket::async_test]
+async fn live_client_sends_invalid_uris_over_network() {
+    let client = Client::debug(rocket()).await.unwrap();
+    let response = client.get("this is a bad URI").dispatch().await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // The connection used for the invalid URI doesn't affect later requests.
+    let response = client.get("/").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+}
+
+#[rocket::async_test]
+async fn live_client_tracks_cookies() {
+    let client = Client::debug(rocket()).await.unwrap();
+
+    let response = client.get("/cookie/get").dispatch().await;
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client.get("/cookie/set").dispatch().await;
+    assert!(response.cookies().get("live").is_some());
+
+    let response = client.get("/cookie/get").dispatch().await;
+    assert_eq!(response.into_string().await.unwrap(), "cookie");
+}
+
+#[rocket::async_test]
+async fn live_client_streams_and_shuts_down() {
+    use rocket::tokio::io::AsyncReadExt;
+
+    let client = Client::debug(rocket()).await.unwrap();
+    let shutdown = client.rocket().shutdown();
+
+    // Read from an infinite stream while issuing another request concurrently.
+    let mut response = client.get("/infinite").dispatch().await;
+    let mut buf = [0; 4];
+    response.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"tick");
+
+    let hello = client.get("/").dispatch().await;
+    assert_eq!(hello.into_string().await.unwrap(), "Hello, world!");
+
+    response.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"tick");
+
+    drop(response);
+    drop(client);
+    time::timeout(Duration::from_secs(5), shutdown).await.expect("shutdown notified");
+}
+
+#[cfg(feature = "tls")]
+mod tls {
+    use rocket::fs::relative;
+    use rocket::config::{Config, TlsConfig};
+    use rocket::local::live::Client;
+
+    fn tls_config() -> TlsConfig {
+        let cert_path = relative!("../../examples/tls/private/rsa_sha256_cert.pem");
+        let key_path = relative!("../../examples/tls/private/rsa_sha256_key.pem");
+        TlsConfig::from_paths(cert_path, key_path)
+    }
+
+    #[rocket::async_test]
+    async fn live_client_connects_over_tls() {
+        let config = Config { tls: Some(tls_config()), ..Config::debug_default() };
+        let rocket = rocket::custom(config).mount("/", routes![super::hello]);
+        let client = Client::debug(rocket).await.unwrap();
+
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "Hello, world!");
+    }
+
+    #[cfg(feature = "mtls")]
+    #[rocket::async_test]
+    async fn live_client_presents_client_certificate() {
+        use rocket::config::MutualTls;
+        use rocket::mtls::Certificate;
+
+        #[get("/")]
+        fn cert(cert: Option<Certificate<'_>>) -> String {
+            cert.map(|c| c.subject().to_string()).unwrap_or_else(|| "none".into())
+        }
+
+        let ca_path = relative!("../../examples/tls/private/ca_cert.pem");
+        let tls = tls_config().with_mutual(MutualTls::from_path(ca_path));
+        let config = Config { tls: Some(tls), ..Config::debug_default() };
+        let rocket = rocket::custom(config).mount("/", routes![cert]);
+        let client = Client::debug(rocket).await.unwrap();
+
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.into_string().await.unwrap(), "none");
+
+        let cert = include_bytes!("../../../examples/tls/private/ed25519_cert.pem");
+        let key = include_bytes!("../../../examples/tls/private/ed25519_key.pem");
+        let response = client.get("/").identity(&cert[..], &key[..]).dispatch().await;
+        assert!(response.into_string().await.unwrap().contains("localhost"));
+    }
+}
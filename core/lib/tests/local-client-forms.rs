@@ -0,0 +1,81 @@
+#[macro_use] extern crate rocket;
+
+use rocket::form::Form;
+use rocket::fs::TempFile;
+use rocket::http::ContentType;
+use rocket::local::Multipart;
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Person<'r> {
+    name: &'r str,
+    motto: String,
+}
+
+#[derive(FromForm)]
+struct Upload<'r> {
+    title: String,
+    file: TempFile<'r>,
+    notes: Vec<TempFile<'r>>,
+}
+
+#[post("/person", data = "<person>")]
+fn person(person: Form<Person<'_>>) -> String {
+    format!("{}: {}", person.name, person.motto)
+}
+
+#[post("/upload", data = "<upload>")]
+fn upload(upload: Form<Upload<'_>>) -> String {
+    let file = &upload.file;
+    format!("{}: {} {} {} ({} notes)",
+        upload.title,
+        file.name().unwrap_or("-"),
+        file.content_type().map(|c| c.to_string()).unwrap_or_default(),
+        file.len(),
+        upload.notes.len())
+}
+
+fn client() -> Client {
+    Client::debug_with(routes![person, upload]).unwrap()
+}
+
+#[test]
+fn urlencoded_form_body() {
+    let client = client();
+    let request = client.post("/person").form(&[("name", "Bob"), ("motto", "a+b = c & d")]);
+    assert_eq!(request.content_type(), Some(&ContentType::Form));
+
+    let response = request.dispatch();
+    assert_eq!(response.into_string().unwrap(), "Bob: a+b = c & d");
+
+    let fields = vec![("name".to_string(), "Alice".to_string()), ("motto".into(), "hi".into())];
+    let response = client.post("/person").form(&fields).dispatch();
+    assert_eq!(response.into_string().unwrap(), "Alice: hi");
+}
+
+#[test]
+fn multipart_form_body() {
+    let form = Multipart::new()
+        .field("title", "My \"Doc\"")
+        .file("file", "report.json", ContentType::JSON, r#"{"ok": true}"#)
+        .file("notes", "a.txt", ContentType::Plain, "one")
+        .file("notes", "b.txt", ContentType::Plain, "two");
+
+    let client = client();
+    let request = client.post("/upload").multipart(form.clone());
+    let content_type = request.content_type().unwrap();
+    assert!(content_type.is_form_data());
+    assert_eq!(content_type.param("boundary"), Some(form.boundary()));
+
+    let response = request.dispatch();
+    assert_eq!(response.into_string().unwrap(),
+        "My \"Doc\": report application/json 12 (2 notes)");
+}
+
+#[test]
+fn multipart_form_body_missing_field() {
+    let form = Multipart::new().field("title", "No File");
+    let client = client();
+    let response = client.post("/upload").multipart(form).dispatch();
+    assert_eq!(response.status().code, 422);
+}
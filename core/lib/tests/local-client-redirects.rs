@@ -0,0 +1,198 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build};
+use rocket::form::Form;
+use rocket::http::{Cookie, CookieJar, Header, Method, Status};
+use rocket::http::uri::Host;
+use rocket::request::{self, FlashMessage, FromRequest, Request};
+use rocket::response::{Flash, Redirect};
+use rocket::local::blocking::Client;
+
+#[derive(FromForm)]
+struct Login<'r> {
+    user: &'r str,
+}
+
+#[post("/login", data = "<login>")]
+fn login(jar: &CookieJar<'_>, login: Form<Login<'_>>) -> Flash<Redirect> {
+    jar.add(Cookie::new("user", login.user.to_string()));
+    Flash::success(Redirect::to(uri!(home)), "Logged in.")
+}
+
+#[get("/home")]
+fn home(jar: &CookieJar<'_>, flash: Option<FlashMessage<'_>>) -> String {
+    let user = jar.get("user").map(|c| c.value().to_string()).unwrap_or_default();
+    let message = flash.map(|f| f.message().to_string()).unwrap_or_default();
+    format!("{}: {}", user, message)
+}
+
+#[post("/temporary", data = "<body>")]
+fn temporary(body: String) -> Redirect {
+    Redirect::temporary(format!("/echo?from={}", body))
+}
+
+#[post("/echo?<from>", data = "<body>")]
+fn echo(from: &str, body: String) -> String {
+    format!("{} -> {}", from, body)
+}
+
+#[post("/found")]
+fn found() -> Redirect {
+    Redirect::found("/landing")
+}
+
+#[get("/landing")]
+fn landing() -> &'static str {
+    "landed"
+}
+
+#[get("/loop/<n>")]
+fn looping(n: usize) -> Redirect {
+    Redirect::to(uri!(looping(n + 1)))
+}
+
+#[get("/away")]
+fn away() -> Redirect {
+    Redirect::to("https://rocket.rs/whoami?away")
+}
+
+#[get("/ftp")]
+fn ftp() -> Redirect {
+    Redirect::to("ftp://rocket.rs/")
+}
+
+#[get("/whoami")]
+fn whoami(jar: &CookieJar<'_>, host: Option<&Host<'_>>, auth: Authorization<'_>) -> String {
+    let host = host.map(|h| h.to_string()).unwrap_or_default();
+    let cookies = jar.iter().count();
+    format!("{} {:?} {}", host, auth.0, cookies)
+}
+
+struct Authorization<'r>(Option<&'r str>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Authorization<'r> {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        request::Outcome::Success(Authorization(req.headers().get_one("Authorization")))
+    }
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![login, home, temporary, echo, found, landing, looping, away, ftp, whoami])
+}
+
+#[test]
+fn redirects_are_not_followed_by_default() {
+    let client = Client::tracked(rocket()).unwrap();
+    let response = client.post("/login").form(&[("user", "bob")]).dispatch();
+    assert_eq!(response.status(), Status::SeeOther);
+    assert!(response.redirects().is_empty());
+}
+
+#[test]
+fn follows_login_flash_redirect() {
+    let client = Client::tracked(rocket()).unwrap();
+    let response = client.post("/login")
+        .form(&[("user", "bob")])
+        .follow_redirects(5)
+        .dispatch();
+
+    let hops = response.redirects();
+    assert_eq!(hops.len(), 1);
+    assert_eq!(hops[0].method(), Method::Post);
+    assert_eq!(hops[0].uri(), "/login");
+    assert_eq!(hops[0].status(), Status::SeeOther);
+    assert_eq!(hops[0].location(), "/home");
+    assert_eq!(response.into_string().unwrap(), "bob: Logged in.");
+
+    // The flash message was consumed; the login cookie persists.
+    let response = client.get("/home").dispatch();
+    assert_eq!(response.into_string().unwrap(), "bob: ");
+}
+
+#[test]
+fn untracked_client_carries_cookies_across_redirects() {
+    let client = Client::untracked(rocket()).unwrap();
+    let response = client.post("/login")
+        .form(&[("user", "alice")])
+        .follow_redirects(1)
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "alice: Logged in.");
+
+    let response = client.get("/home").dispatch();
+    assert_eq!(response.into_string().unwrap(), ": ");
+}
+
+#[test]
+fn method_semantics_by_status() {
+    let client = Client::tracked(rocket()).unwrap();
+    let response = client.post("/temporary").body("hi").follow_redirects(1).dispatch();
+    assert_eq!(response.redirects()[0].status(), Status::TemporaryRedirect);
+    assert_eq!(response.into_string().unwrap(), "hi -> hi");
+
+    let response = client.post("/found").body("ignored").follow_redirects(1).dispatch();
+    assert_eq!(response.redirects()[0].status(), Status::Found);
+    assert_eq!(response.into_string().unwrap(), "landed");
+}
+
+#[test]
+fn hop_limit_is_respected() {
+    let client = Client::tracked(rocket()).unwrap();
+    let response = client.get("/loop/0").follow_redirects(3).dispatch();
+    assert_eq!(response.status(), Status::SeeOther);
+    assert_eq!(response.headers().get_one("Location"), Some("/loop/4"));
+
+    let locations: Vec<_> = response.redirects().iter()
+        .map(|hop| hop.location().to_string())
+        .collect();
+
+    assert_eq!(locations, ["/loop/1", "/loop/2", "/loop/3"]);
+}
+
+#[test]
+fn non_http_redirects_are_not_followed() {
+    let client = Client::tracked(rocket()).unwrap();
+    let response = client.get("/ftp").follow_redirects(3).dispatch();
+    assert_eq!(response.status(), Status::SeeOther);
+    assert!(response.redirects().is_empty());
+}
+
+#[test]
+fn cross_origin_redirects_drop_credentials() {
+    let client = Client::tracked(rocket()).unwrap();
+    let response = client.get("/away")
+        .header(Header::new("Authorization", "Bearer secret"))
+        .cookie(Cookie::new("user", "bob"))
+        .follow_redirects(3)
+        .dispatch();
+
+    assert_eq!(response.redirects()[0].location(), "/whoami?away");
+    assert_eq!(response.into_string().unwrap(), "rocket.rs None 0");
+
+    // Same-origin redirects keep them.
+    let mut request = client.get("/away")
+        .header(Header::new("Authorization", "Bearer secret"))
+        .cookie(Cookie::new("user", "bob"))
+        .follow_redirects(3);
+
+    request.set_host(Host::new(uri!("rocket.rs")));
+    let response = request.dispatch();
+    assert_eq!(response.into_string().unwrap(), "rocket.rs Some(\"Bearer secret\") 1");
+}
+
+#[rocket::async_test]
+async fn live_client_follows_redirects() {
+    let client = rocket::local::live::Client::tracked(rocket()).await.unwrap();
+    let response = client.post("/login")
+        .form(&[("user", "carol")])
+        .follow_redirects(5)
+        .dispatch()
+        .await;
+
+    assert_eq!(response.redirects().len(), 1);
+    assert_eq!(response.into_string().await.unwrap(), "carol: Logged in.");
+}
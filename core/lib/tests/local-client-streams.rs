@@ -0,0 +1,157 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+
+use rocket::{Rocket, Build};
+use rocket::response::stream::{Event, EventStream, TextStream};
+use rocket::tokio::time::{self, Duration};
+
+#[get("/ticks")]
+fn ticks() -> TextStream![&'static str] {
+    TextStream! {
+        let mut interval = time::interval(Duration::from_millis(10));
+        loop {
+            interval.tick().await;
+            yield "tick";
+        }
+    }
+}
+
+#[get("/events")]
+fn events() -> EventStream![] {
+    EventStream! {
+        // Only updates the client's reconnection time; never parsed as an event.
+        yield Event::retry(Duration::from_secs(3));
+        for i in 0.. {
+            yield Event::data(format!("message\n{}", i)).id(i.to_string()).event("count");
+        }
+    }.heartbeat(Duration::from_millis(10))
+}
+
+#[get("/slow")]
+fn slow() -> EventStream![] {
+    EventStream! {
+        yield Event::data("first");
+        time::sleep(Duration::from_millis(500)).await;
+        yield Event::data("second");
+    }
+}
+
+#[get("/finite")]
+fn finite() -> EventStream![] {
+    EventStream! {
+        yield Event::data("a");
+        yield Event::comment("not an event");
+        yield Event::data("b");
+    }
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build().mount("/", routes![ticks, events, slow, finite])
+}
+
+fn count(i: usize) -> Event {
+    Event::data(format!("message\n{}", i)).id(i.to_string()).event("count")
+}
+
+mod blocking {
+    use super::*;
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn reads_infinite_text_stream_in_chunks() {
+        let client = Client::debug(rocket()).unwrap();
+        let mut response = client.get("/ticks").dispatch();
+        response.set_read_timeout(Some(Duration::from_secs(5)));
+        for _ in 0..3 {
+            let chunk = response.next_chunk().unwrap().expect("chunk");
+            assert!(chunk.starts_with(b"tick"));
+        }
+    }
+
+    #[test]
+    fn parses_infinite_event_stream() {
+        let client = Client::debug(rocket()).unwrap();
+        let mut response = client.get("/events").dispatch();
+        response.set_read_timeout(Some(Duration::from_secs(5)));
+
+        let events = response.events().take(5).collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(events, (0..5).map(count).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn read_timeout_elapses_without_losing_events() {
+        let client = Client::debug(rocket()).unwrap();
+        let mut response = client.get("/slow").dispatch();
+        response.set_read_timeout(Some(Duration::from_millis(50)));
+
+        assert_eq!(response.next_event().unwrap(), Some(Event::data("first")));
+        let error = response.next_event().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+        response.set_read_timeout(None);
+        assert_eq!(response.next_event().unwrap(), Some(Event::data("second")));
+        assert_eq!(response.next_event().unwrap(), None);
+        assert_eq!(response.next_chunk().unwrap(), None);
+    }
+
+    #[test]
+    fn events_end_with_body() {
+        let client = Client::debug(rocket()).unwrap();
+        let mut response = client.get("/finite").dispatch();
+        let events = response.events().collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(events, vec![Event::data("a"), Event::data("b")]);
+    }
+}
+
+mod asynchronous {
+    use super::*;
+    use rocket::local::asynchronous::Client;
+    use rocket::futures::stream::{StreamExt, TryStreamExt};
+
+    #[rocket::async_test]
+    async fn reads_infinite_text_stream_in_chunks() {
+        let client = Client::debug(rocket()).await.unwrap();
+        let mut response = client.get("/ticks").dispatch().await;
+        response.set_read_timeout(Some(Duration::from_secs(5)));
+        for _ in 0..3 {
+            let chunk = response.next_chunk().await.unwrap().expect("chunk");
+            assert!(chunk.starts_with(b"tick"));
+        }
+    }
+
+    #[rocket::async_test]
+    async fn parses_infinite_event_stream() {
+        let client = Client::debug(rocket()).await.unwrap();
+        let mut response = client.get("/events").dispatch().await;
+        response.set_read_timeout(Some(Duration::from_secs(5)));
+
+        let events: Vec<_> = response.events().take(5).try_collect().await.unwrap();
+        assert_eq!(events, (0..5).map(count).collect::<Vec<_>>());
+    }
+
+    #[rocket::async_test]
+    async fn read_timeout_elapses_without_losing_events() {
+        let client = Client::debug(rocket()).await.unwrap();
+        let mut response = client.get("/slow").dispatch().await;
+        response.set_read_timeout(Some(Duration::from_millis(50)));
+
+        assert_eq!(response.next_event().await.unwrap(), Some(Event::data("first")));
+        let error = response.next_event().await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+
+        response.set_read_timeout(None);
+        assert_eq!(response.next_event().await.unwrap(), Some(Event::data("second")));
+        assert_eq!(response.next_event().await.unwrap(), None);
+    }
+
+    #[rocket::async_test]
+    async fn live_client_parses_event_stream() {
+        let client = rocket::local::live::Client::debug(rocket()).await.unwrap();
+        let mut response = client.get("/events").dispatch().await;
+        response.set_read_timeout(Some(Duration::from_secs(5)));
+
+        let events: Vec<_> = response.events().take(3).try_collect().await.unwrap();
+        assert_eq!(events, (0..3).map(count).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,90 @@
+#[macro_use] extern crate rocket;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use rocket::{Rocket, Build};
+use rocket::local::blocking::Client;
+use rocket::route::UriForError;
+
+#[get("/<id:u64>/<slug>?<page>")]
+fn post(id: u64, slug: &str, page: Option<usize>) -> String {
+    format!("{} {} {:?}", id, slug, page)
+}
+
+#[get("/files/<path..>?<rest..>")]
+fn files(path: PathBuf, rest: BTreeMap<&str, &str>) -> String {
+    format!("{} {:?}", path.display(), rest)
+}
+
+#[get("/about?lang=en")]
+fn about() { }
+
+#[get("/caf%C3%A9/<name>")]
+fn cafe(name: &str) -> &str { name }
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/blog", routes![post])
+        .mount("/", routes![files, about, cafe])
+}
+
+#[test]
+fn test_uri_for_by_name_and_base() {
+    let client = Client::debug(rocket()).unwrap();
+    let rocket = client.rocket();
+
+    let uri = rocket.uri_for("post", "/blog", vec![("id", "10"), ("slug", "hello")]).unwrap();
+    assert_eq!(uri, "/blog/10/hello");
+    assert_eq!(client.get(uri).dispatch().into_string().unwrap(), "10 hello None");
+
+    let params = vec![("slug", "a b"), ("page", "3"), ("id", "7")];
+    let uri = rocket.uri_for("post", "/blog/", params).unwrap();
+    assert_eq!(uri, "/blog/7/a%20b?page=3");
+    assert_eq!(client.get(uri).dispatch().into_string().unwrap(), "7 a b Some(3)");
+
+    let uri = rocket.uri_for("about", "/", Vec::<(&str, &str)>::new()).unwrap();
+    assert_eq!(uri, "/about?lang=en");
+
+    // Static segments are emitted as they are, without encoding them again.
+    let uri = rocket.uri_for("cafe", "/", vec![("name", "crème")]).unwrap();
+    assert_eq!(uri, "/caf%C3%A9/cr%C3%A8me");
+
+    let error = rocket.uri_for("post", "/", vec![("id", "10"), ("slug", "hello")]).unwrap_err();
+    assert_eq!(error, UriForError::NoRoute { name: "post".into(), base: "/".into() });
+
+    let error = rocket.uri_for("nope", "/blog", Vec::<(&str, &str)>::new()).unwrap_err();
+    assert_eq!(error, UriForError::NoRoute { name: "nope".into(), base: "/blog".into() });
+}
+
+#[test]
+fn test_uri_for_trailing() {
+    let client = Client::debug(rocket()).unwrap();
+    let rocket = client.rocket();
+
+    let params = vec![("path", "a/b c/d.txt"), ("x", "1"), ("y", "two")];
+    let uri = rocket.uri_for("files", "/", params).unwrap();
+    assert_eq!(uri, "/files/a/b%20c/d.txt?x=1&y=two");
+
+    let response = client.get(uri).dispatch();
+    assert_eq!(response.into_string().unwrap(), r#"a/b c/d.txt {"x": "1", "y": "two"}"#);
+}
+
+#[test]
+fn test_uri_for_param_errors() {
+    let client = Client::debug(rocket()).unwrap();
+    let rocket = client.rocket();
+
+    let error = rocket.uri_for("post", "/blog", vec![("id", "10")]).unwrap_err();
+    assert_eq!(error, UriForError::Missing("slug".into()));
+
+    let error = rocket.uri_for("post", "/blog", vec![("id", "-1"), ("slug", "s")]).unwrap_err();
+    assert_eq!(error, UriForError::Invalid { name: "id".into(), value: "-1".into() });
+
+    let error = rocket.uri_for("post", "/blog", vec![("id", "1"), ("slug", "")]).unwrap_err();
+    assert_eq!(error, UriForError::Invalid { name: "slug".into(), value: "".into() });
+
+    let params = vec![("id", "1"), ("slug", "s"), ("lang", "en")];
+    let error = rocket.uri_for("post", "/blog", params).unwrap_err();
+    assert_eq!(error, UriForError::Unknown("lang".into()));
+}
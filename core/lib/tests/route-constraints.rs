@@ -0,0 +1,83 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Route};
+use rocket::http::{Method, Status};
+use rocket::local::blocking::Client;
+
+#[get("/item/<id:u64>")]
+fn item_by_id(id: u64) -> String { format!("id: {}", id) }
+
+#[get("/item/<slug:[a-z-]+>")]
+fn item_by_slug(slug: &str) -> String { format!("slug: {}", slug) }
+
+#[get("/small/<n:u8>")]
+fn small(n: Option<u8>) -> String { format!("small: {:?}", n) }
+
+#[get("/signed/<_:i32>/<rest..>")]
+fn signed(rest: std::path::PathBuf) -> String { format!("signed: {}", rest.display()) }
+
+fn rocket() -> Rocket<Build> {
+    rocket::build().mount("/", routes![item_by_id, item_by_slug, small, signed])
+}
+
+#[test]
+fn test_constrained_routing() {
+    let client = Client::debug(rocket()).unwrap();
+
+    let response = client.get("/item/10").dispatch();
+    assert_eq!(response.into_string().unwrap(), "id: 10");
+
+    let response = client.get("/item/rocket-launch").dispatch();
+    assert_eq!(response.into_string().unwrap(), "slug: rocket-launch");
+
+    let response = client.get("/item/Rocket").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client.get("/small/255").dispatch();
+    assert_eq!(response.into_string().unwrap(), "small: Some(255)");
+
+    let response = client.get("/small/256").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+
+    let response = client.get("/signed/-3/a/b").dispatch();
+    assert_eq!(response.into_string().unwrap(), "signed: a/b");
+
+    let response = client.get("/signed/x/a/b").dispatch();
+    assert_eq!(response.status(), Status::NotFound);
+}
+
+#[test]
+fn test_constrained_uri() {
+    assert_eq!(uri!(item_by_id(5)), "/item/5");
+    assert_eq!(uri!(item_by_slug("hello")), "/item/hello");
+}
+
+#[test]
+fn test_constrained_collisions() {
+    fn collides(routes: Vec<Route>) -> bool {
+        match Client::debug(rocket::build().mount("/", routes)) {
+            Err(e) => matches!(e.kind(), rocket::error::ErrorKind::Collisions(..)),
+            Ok(_) => false,
+        }
+    }
+
+    let route = |uri| Route::new(Method::Get, uri, rocket::route::dummy_handler);
+
+    assert!(!collides(vec![route("/<a:u8>"), route("/<b:[a-z]+>")]));
+    assert!(!collides(vec![route("/<a:u8>"), route("/static")]));
+    assert!(collides(vec![route("/<a:u8>"), route("/<b>")]));
+    assert!(collides(vec![route("/<a:u8>"), route("/<b:[0-9a-f]+>")]));
+}
+
+#[test]
+#[should_panic(expected = "invalid route segment constraint")]
+fn test_invalid_constraint() {
+    Route::new(Method::Get, "/<a:[a-z>", rocket::route::dummy_handler);
+}
+
+#[test]
+#[cfg(not(feature = "uuid"))]
+#[should_panic(expected = "requires the `uuid` feature")]
+fn test_uuid_constraint_without_feature() {
+    Route::new(Method::Get, "/<id:uuid>", rocket::route::dummy_handler);
+}
@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::Header;
+use rocket::local::blocking::Client;
+use rocket::response::Responder;
+
+/// Returns the item with id `id`.
+///
+/// Items are numbered from `0`.
+#[get("/item/<id>", tags = ["items", "v2"], summary = "Get an item.")]
+fn item(id: usize) -> String { id.to_string() }
+
+#[get("/items/<id>", deprecated)]
+fn old_item(id: usize) -> String { id.to_string() }
+
+#[get("/v1/items/<id>", deprecated, sunset = "2030-02-28")]
+fn v1_item(id: usize) -> String { id.to_string() }
+
+#[derive(Responder)]
+#[response(content_type = "plain")]
+struct Sunsetting(&'static str, Header<'static>);
+
+#[route(GET, uri = "/v0/items/<_id>", deprecated, sunset = "2030-02-28")]
+fn v0_item(_id: usize) -> Sunsetting {
+    Sunsetting("gone soon", Header::new("Sunset", "Tue, 01 Jan 2030 00:00:00 GMT"))
+}
+
+#[test]
+fn test_route_metadata() {
+    let rocket = rocket::build().mount("/", routes![item, old_item, v1_item]);
+    let client = Client::debug(rocket).unwrap();
+    let mut routes = client.rocket().routes();
+
+    let item = routes.next().unwrap();
+    assert_eq!(item.tags, ["items", "v2"]);
+    assert_eq!(item.summary.as_deref(), Some("Get an item."));
+    assert_eq!(item.docs.as_deref(),
+        Some("Returns the item with id `id`.\n\nItems are numbered from `0`."));
+    assert!(!item.deprecated);
+
+    let old_item = routes.next().unwrap();
+    assert!(old_item.deprecated);
+    assert!(old_item.sunset().is_none() && old_item.tags.is_empty());
+    assert!(old_item.summary.is_none() && old_item.docs.is_none());
+
+    let v1_item = routes.next().unwrap();
+    assert!(v1_item.deprecated);
+    assert_eq!(v1_item.sunset().unwrap(), "2030-02-28");
+}
+
+#[test]
+fn test_deprecation_headers() {
+    let rocket = rocket::build().mount("/", routes![item, old_item, v1_item, v0_item]);
+    let client = Client::debug(rocket).unwrap();
+
+    let response = client.get("/item/1").dispatch();
+    assert!(!response.headers().contains("Deprecation"));
+    assert!(!response.headers().contains("Sunset"));
+
+    let response = client.get("/items/1").dispatch();
+    assert_eq!(response.headers().get_one("Deprecation"), Some("true"));
+    assert!(!response.headers().contains("Sunset"));
+
+    let response = client.get("/v1/items/1").dispatch();
+    assert_eq!(response.headers().get_one("Deprecation"), Some("true"));
+    assert_eq!(response.headers().get_one("Sunset"), Some("Thu, 28 Feb 2030 00:00:00 GMT"));
+
+    // Headers set by the handler take precedence.
+    let response = client.get("/v0/items/1").dispatch();
+    assert_eq!(response.headers().get_one("Deprecation"), Some("true"));
+    assert_eq!(response.headers().get_one("Sunset"), Some("Tue, 01 Jan 2030 00:00:00 GMT"));
+}
+
+#[test]
+fn test_no_deprecation_headers_from_catchers() {
+    let rocket = rocket::build().mount("/", routes![old_item, v1_item]);
+    let client = Client::debug(rocket).unwrap();
+
+    // `old_item` forwards, so the `404` comes from a catcher, not the route.
+    let response = client.get("/items/one").dispatch();
+    assert_eq!(response.status(), rocket::http::Status::NotFound);
+    assert!(!response.headers().contains("Deprecation"));
+
+    let response = client.get("/v1/items/one").dispatch();
+    assert!(!response.headers().contains("Deprecation"));
+    assert!(!response.headers().contains("Sunset"));
+}
+
+#[test]
+fn test_manual_sunset() {
+    use rocket::Route;
+    use rocket::http::Method;
+
+    let date = time::Date::try_from_ymd(2030, 3, 1).unwrap();
+    let mut route = Route::new(Method::Get, "/", rocket::route::dummy_handler).with_sunset(date);
+    route.deprecated = true;
+    assert_eq!(route.sunset().unwrap(), "2030-03-01");
+
+    let client = Client::debug(rocket::build().mount("/", vec![route])).unwrap();
+    let response = client.get("/").dispatch();
+    assert_eq!(response.headers().get_one("Sunset"), Some("Fri, 01 Mar 2030 00:00:00 GMT"));
+}
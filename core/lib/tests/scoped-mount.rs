@@ -0,0 +1,170 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{Request, Route, State};
+use rocket::request::{self, FromRequest};
+use rocket::fairing::AdHoc;
+use rocket::route::Scope;
+use rocket::catcher::TypedError;
+use rocket::http::{Header, Status};
+use rocket::local::blocking::Client;
+
+struct AdminUser;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminUser {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        match req.headers().get_one("x-token") {
+            Some("admin") => request::Outcome::Success(AdminUser),
+            Some(_) => request::Outcome::Failure((Status::Unauthorized, ())),
+            None => request::Outcome::Forward(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NotEditor;
+
+impl TypedError for NotEditor { }
+
+struct Editor;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Editor {
+    type Error = NotEditor;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, NotEditor> {
+        match req.headers().get_one("x-editor") {
+            Some(_) => request::Outcome::Success(Editor),
+            None => request::Outcome::Failure((Status::Forbidden, NotEditor)),
+        }
+    }
+}
+
+struct Flag;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/")]
+fn dashboard() -> &'static str { "dashboard" }
+
+#[get("/", rank = 2)]
+fn login() -> &'static str { "login" }
+
+#[get("/edit")]
+fn edit() -> &'static str { "edit" }
+
+#[get("/flagged")]
+fn flagged() -> &'static str { "flagged" }
+
+#[catch(403, error = "<e>")]
+fn not_editor(e: &NotEditor) -> String { format!("{:?}", e) }
+
+fn rocket(requests: Arc<AtomicUsize>) -> rocket::Rocket<rocket::Build> {
+    let counter = AdHoc::on_request("Counter", move |_, _| {
+        let requests = requests.clone();
+        Box::pin(async move { requests.fetch_add(1, Ordering::SeqCst); })
+    });
+
+    let marker = AdHoc::on_response("Marker", |req, res| Box::pin(async move {
+        let route = req.route().and_then(|r| r.name.as_deref()).unwrap_or("none");
+        res.set_header(Header::new("X-Scoped", route.to_string()));
+    }));
+
+    let editor = Scope::new(routes![edit]).guard::<Editor>();
+    let admin = Scope::new(routes![dashboard])
+        .guard::<AdminUser>()
+        .attach(counter)
+        .attach(marker);
+
+    let nested: Vec<Route> = Scope::new(editor).guard::<AdminUser>().into();
+
+    rocket::build()
+        .mount("/", routes![index])
+        .mount("/admin", admin)
+        .mount("/admin", routes![login])
+        .mount("/admin", nested)
+        .register("/", catchers![not_editor])
+}
+
+#[test]
+fn test_scope_guards() {
+    let client = Client::debug(rocket(Arc::default())).unwrap();
+
+    let response = client.get("/").dispatch();
+    assert_eq!(response.into_string().unwrap(), "index");
+
+    let response = client.get("/admin").header(Header::new("x-token", "admin")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "dashboard");
+
+    let response = client.get("/admin").header(Header::new("x-token", "guest")).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // A forwarding guard forwards to the next matching route.
+    let response = client.get("/admin").dispatch();
+    assert_eq!(response.into_string().unwrap(), "login");
+
+    // The outer scope's guard runs first.
+    let response = client.get("/admin/edit").header(Header::new("x-token", "guest")).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // A failing guard's error value is passed to typed catchers.
+    let response = client.get("/admin/edit").header(Header::new("x-token", "admin")).dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(response.into_string().unwrap(), "NotEditor");
+
+    let response = client.get("/admin/edit")
+        .header(Header::new("x-token", "admin"))
+        .header(Header::new("x-editor", "yes"))
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "edit");
+}
+
+#[test]
+fn test_scope_fairings() {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let client = Client::debug(rocket(requests.clone())).unwrap();
+
+    let response = client.get("/").dispatch();
+    assert!(!response.headers().contains("X-Scoped"));
+    assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+    let response = client.get("/admin/edit").dispatch();
+    assert!(!response.headers().contains("X-Scoped"));
+    assert_eq!(requests.load(Ordering::SeqCst), 0);
+
+    let response = client.get("/admin").header(Header::new("x-token", "admin")).dispatch();
+    assert_eq!(response.headers().get_one("X-Scoped"), Some("dashboard"));
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // Responses produced by catchers after a guard failure aren't scoped.
+    let response = client.get("/admin").header(Header::new("x-token", "guest")).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(!response.headers().contains("X-Scoped"));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+    // Request fairings run for the first route, even if it forwards; response
+    // fairings only for the route that handles the request.
+    let response = client.get("/admin").dispatch();
+    assert_eq!(response.into_string().unwrap(), "login");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_scope_borrowed_guards() {
+    let scope = || Scope::new(routes![flagged]).guard_ref::<State<Flag>>();
+
+    let client = Client::debug(rocket::build().mount("/", scope())).unwrap();
+    let response = client.get("/flagged").dispatch();
+    assert_eq!(response.status(), Status::InternalServerError);
+
+    let client = Client::debug(rocket::build().manage(Flag).mount("/", scope())).unwrap();
+    let response = client.get("/flagged").dispatch();
+    assert_eq!(response.into_string().unwrap(), "flagged");
+}
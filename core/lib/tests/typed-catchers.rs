@@ -0,0 +1,219 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Rocket, Build};
+use rocket::request::{self, FromRequest};
+use rocket::catcher::TypedError;
+use rocket::form::{self, Form};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::blocking::Client;
+
+#[derive(Debug, PartialEq)]
+enum TokenError {
+    Missing,
+    Invalid(String),
+}
+
+impl TypedError for TokenError { }
+
+struct Token;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Token {
+    type Error = TokenError;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, TokenError> {
+        match req.headers().get_one("x-token") {
+            Some("valid") => request::Outcome::Success(Token),
+            Some(t) => {
+                let error = TokenError::Invalid(t.into());
+                request::Outcome::Failure((Status::Unauthorized, error))
+            }
+            None => request::Outcome::Failure((Status::Unauthorized, TokenError::Missing)),
+        }
+    }
+}
+
+struct Untyped;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Untyped {
+    type Error = &'r str;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, &'r str> {
+        request::Outcome::Failure((Status::Unauthorized, req.uri().path().as_str()))
+    }
+}
+
+#[derive(FromForm)]
+struct Login<'r> {
+    #[field(validate = len(1..))]
+    user: &'r str,
+    #[field(validate = range(1..))]
+    age: u8,
+}
+
+#[get("/secret")]
+fn secret(_token: Token) -> &'static str { "secret" }
+
+#[get("/other")]
+fn other(_guard: Untyped) -> &'static str { "other" }
+
+#[post("/login", data = "<form>")]
+fn login(form: Form<Login<'_>>) -> String { form.user.into() }
+
+#[catch(401, error = "<error>")]
+fn unauthorized(error: &TokenError) -> String {
+    format!("token: {:?}", error)
+}
+
+#[catch(401)]
+fn untyped_unauthorized() -> &'static str {
+    "untyped"
+}
+
+#[catch(422, error = "<errors>")]
+fn invalid_form(req: &Request, errors: form::Errors<'_>) -> String {
+    let mut names: Vec<_> = errors.iter()
+        .filter_map(|e| e.name.as_ref().map(|n| n.to_string()))
+        .collect();
+
+    names.sort();
+    format!("{}: {}", req.uri(), names.join(", "))
+}
+
+fn rocket() -> Rocket<Build> {
+    rocket::build()
+        .mount("/", routes![secret, other, login])
+        .register("/", catchers![unauthorized, untyped_unauthorized, invalid_form])
+}
+
+#[test]
+fn test_typed_request_guard_error() {
+    let client = Client::debug(rocket()).unwrap();
+
+    let response = client.get("/secret").header(Header::new("x-token", "valid")).dispatch();
+    assert_eq!(response.into_string().unwrap(), "secret");
+
+    let response = client.get("/secret").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_string().unwrap(), "token: Missing");
+
+    let response = client.get("/secret").header(Header::new("x-token", "bad")).dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_string().unwrap(), "token: Invalid(\"bad\")");
+}
+
+#[test]
+fn test_type_mismatch_falls_through() {
+    let client = Client::debug(rocket()).unwrap();
+
+    let response = client.get("/other").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert_eq!(response.into_string().unwrap(), "untyped");
+}
+
+#[test]
+fn test_typed_data_guard_error() {
+    let client = Client::debug(rocket()).unwrap();
+
+    let response = client.post("/login")
+        .header(ContentType::Form)
+        .body("user=bob&age=10")
+        .dispatch();
+
+    assert_eq!(response.into_string().unwrap(), "bob");
+
+    let response = client.post("/login")
+        .header(ContentType::Form)
+        .body("user=&age=0")
+        .dispatch();
+
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.into_string().unwrap(), "/login: age, user");
+}
+
+#[test]
+fn test_typed_catcher_collisions() {
+    #[catch(401, error = "<e>")]
+    fn other_unauthorized(e: &form::Errors<'_>) -> String { e.to_string() }
+
+    // Typed catchers with different types don't collide.
+    let typed = rocket().register("/", catchers![other_unauthorized]);
+    assert!(Client::debug(typed).is_ok());
+
+    // But those with the same type do.
+    let same = rocket().register("/", catchers![unauthorized]);
+    match Client::debug(same) {
+        Err(e) => assert!(matches!(e.kind(), rocket::error::ErrorKind::Collisions(..))),
+        Ok(_) => panic!("expected a collision"),
+    }
+}
+
+#[test]
+fn test_manually_typed_catcher() {
+    use rocket::catcher::{Catcher, BoxFuture};
+    use rocket::response::Responder;
+
+    fn handler<'r>(status: Status, req: &'r Request<'_>) -> BoxFuture<'r> {
+        let res = (status, format!("manual: {:?}", req.take_error::<TokenError>()));
+        Box::pin(async move { res.respond_to(req) })
+    }
+
+    let catcher = Catcher::new(401, handler).typed::<TokenError>();
+    let rocket = rocket::build()
+        .mount("/", routes![secret, other])
+        .register("/", catchers![untyped_unauthorized])
+        .register("/", vec![catcher]);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/secret").dispatch();
+    assert_eq!(response.into_string().unwrap(), "manual: Some(Missing)");
+
+    let response = client.get("/other").dispatch();
+    assert_eq!(response.into_string().unwrap(), "untyped");
+
+    // Catchers can also be constructed with a struct literal.
+    let catcher = Catcher {
+        name: None,
+        base: rocket::http::uri::Origin::ROOT,
+        code: Some(401),
+        handler: Box::new(handler),
+        format: None,
+        method: None,
+        error: Some(rocket::catcher::ErrorType::of::<TokenError>()),
+    };
+
+    let rocket = rocket::build().mount("/", routes![secret]).register("/", vec![catcher]);
+    let client = Client::debug(rocket).unwrap();
+    let response = client.get("/secret").dispatch();
+    assert_eq!(response.into_string().unwrap(), "manual: Some(Missing)");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_typed_json_error() {
+    use rocket::serde::json::{self, Json};
+
+    #[post("/", data = "<value>")]
+    fn echo(value: Json<Vec<u8>>) -> String { format!("{:?}", value.0) }
+
+    #[catch(422, error = "<e>")]
+    fn bad_json(e: &json::Error<'_>) -> String {
+        match e {
+            json::Error::Parse(raw, _) => format!("bad json: {}", raw),
+            json::Error::Io(e) => format!("io: {}", e),
+        }
+    }
+
+    let rocket = rocket::build()
+        .mount("/", routes![echo])
+        .register("/", catchers![bad_json]);
+
+    let client = Client::debug(rocket).unwrap();
+    let response = client.post("/").header(ContentType::JSON).body("[1, 2]").dispatch();
+    assert_eq!(response.into_string().unwrap(), "[1, 2]");
+
+    let response = client.post("/").header(ContentType::JSON).body("[1, -2]").dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert_eq!(response.into_string().unwrap(), "bad json: [1, -2]");
+}
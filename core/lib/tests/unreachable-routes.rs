@@ -0,0 +1,142 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Rocket, Build, Config};
+use rocket::config::Unreachable;
+use rocket::error::ErrorKind;
+use rocket::local::blocking::Client;
+
+#[get("/<id>", rank = 1)]
+fn user(id: usize) -> String { id.to_string() }
+
+#[get("/<_..>", rank = 2)]
+fn all() -> &'static str { "all" }
+
+#[get("/<_>", rank = 3)]
+fn fallback() -> &'static str { "fallback" }
+
+#[get("/hello?lang=en", rank = 4)]
+fn hello() -> &'static str { "hello" }
+
+#[post("/<_..>")]
+fn post() -> &'static str { "post" }
+
+#[head("/<_..>", rank = 5)]
+fn head() { }
+
+#[catch(404, method = HEAD)]
+fn head_not_found() { }
+
+#[catch(404)]
+fn not_found() { }
+
+fn custom(unreachable: Unreachable) -> Rocket<Build> {
+    rocket::custom(Config { unreachable, ..Config::debug_default() })
+}
+
+/// Returns the names of the unreachable routes, catchers, and mount points.
+fn unreachable(rocket: Rocket<Build>) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let error = match Client::debug(rocket) {
+        Ok(_) => return (vec![], vec![], vec![]),
+        Err(e) => e,
+    };
+
+    match error.kind() {
+        ErrorKind::Unreachable(u) => {
+            let name = |n: &Option<_>| n.as_ref().map(ToString::to_string).unwrap_or_default();
+            let routes = u.routes.iter()
+                .map(|(r, by)| format!("{}>{}", name(&by.name), name(&r.name)));
+
+            let catchers = u.catchers.iter().map(|c| name(&c.name));
+            (routes.collect(), catchers.collect(), u.mounts.clone())
+        }
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[test]
+fn test_shadowed_routes() {
+    let rocket = custom(Unreachable::Deny).mount("/", routes![user, all, fallback, hello, post]);
+    let (routes, catchers, mounts) = unreachable(rocket);
+    assert_eq!(routes, ["all>fallback", "all>hello"]);
+    assert!(catchers.is_empty() && mounts.is_empty());
+
+    // `user` forwards, so it doesn't shadow anything.
+    let rocket = custom(Unreachable::Deny).mount("/", routes![user, fallback]);
+    assert_eq!(unreachable(rocket), (vec![], vec![], vec![]));
+
+    // Routes mounted at a more specific base aren't covered.
+    let rocket = custom(Unreachable::Deny)
+        .mount("/", routes![fallback])
+        .mount("/api", routes![all]);
+
+    assert_eq!(unreachable(rocket), (vec![], vec![], vec![]));
+
+    // Routes for a host are only covered by those for the same host pattern.
+    let rocket = custom(Unreachable::Deny)
+        .mount_host("*.example.com", "/", routes![all])
+        .mount_host("*.example.com", "/", routes![fallback]);
+
+    assert_eq!(unreachable(rocket).0, ["all>fallback"]);
+
+    let rocket = custom(Unreachable::Deny)
+        .mount_host("*.example.com", "/", routes![all])
+        .mount_host("api.example.com", "/", routes![fallback]);
+
+    assert_eq!(unreachable(rocket), (vec![], vec![], vec![]));
+}
+
+#[test]
+fn test_unreachable_catchers_and_mounts() {
+    let rocket = custom(Unreachable::Deny)
+        .mount("/", routes![all])
+        .mount("/empty", routes![])
+        .register("/", catchers![head_not_found, not_found]);
+
+    let (routes, catchers, mounts) = unreachable(rocket);
+    assert!(routes.is_empty());
+    assert_eq!(catchers, ["head_not_found"]);
+    assert_eq!(mounts, ["/empty"]);
+
+    // With a `HEAD` route mounted, the `HEAD` catcher can be selected.
+    let rocket = custom(Unreachable::Deny)
+        .mount("/", routes![all, head])
+        .register("/", catchers![head_not_found, not_found]);
+
+    assert_eq!(unreachable(rocket), (vec![], vec![], vec![]));
+}
+
+#[test]
+fn test_shadowed_mounts() {
+    // Every route mounted at `/api` is shadowed by `all` mounted at `/`.
+    let rocket = custom(Unreachable::Deny)
+        .mount("/", routes![all])
+        .mount("/api", routes![fallback, hello]);
+
+    let (routes, catchers, mounts) = unreachable(rocket);
+    assert_eq!(routes.len(), 2);
+    assert!(catchers.is_empty());
+    assert_eq!(mounts, ["/api"]);
+
+    // A single reachable route keeps the mount point from being reported.
+    let rocket = custom(Unreachable::Deny)
+        .mount("/", routes![all])
+        .mount("/api", routes![fallback, post]);
+
+    let (routes, _, mounts) = unreachable(rocket);
+    assert_eq!(routes, ["all>fallback"]);
+    assert!(mounts.is_empty());
+}
+
+#[test]
+fn test_unreachable_allowed() {
+    for config in &[Unreachable::Allow, Unreachable::Warn] {
+        let rocket = custom(*config)
+            .mount("/", routes![all, fallback])
+            .mount("/empty", routes![])
+            .register("/", catchers![head_not_found]);
+
+        let client = Client::debug(rocket).unwrap();
+        let response = client.get("/foo").dispatch();
+        assert_eq!(response.into_string().unwrap(), "all");
+    }
+}
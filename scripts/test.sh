@@ -85,6 +85,8 @@ function test_contrib() {
   DYN_TEMPLATES_FEATURES=(
     tera
     handlebars
+    minijinja
+    tera,embed
   )
 
   for feature in "${SYNC_DB_POOLS_FEATURES[@]}"; do